@@ -0,0 +1,49 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    errors::AgendaError,
+    models::{Agenda, AgendaSpan},
+    repository::{AgendaRepository, SettingsRepository},
+    utils::parse_frontend_date,
+    AppState,
+};
+
+/// Tasks, study blocks and classes grouped by the user's local days
+/// `from..=to` (`YYYY-MM-DD`).
+#[tauri::command]
+pub async fn get_agenda(
+    user_id: u32,
+    from: String,
+    to: String,
+    state: State<'_, AppState>,
+) -> Result<Agenda, AgendaError> {
+    let from = parse_frontend_date(&from).map_err(AgendaError::InvalidDate)?;
+    let to = parse_frontend_date(&to).map_err(AgendaError::InvalidDate)?;
+
+    state
+        .read(move |conn| AgendaRepository::agenda(conn, user_id, from, to))
+        .await?
+}
+
+/// The agenda for the "day", "week" or "month" containing `date` (default:
+/// today). Weeks begin on the user's week start.
+#[tauri::command]
+pub async fn get_agenda_span(
+    user_id: u32,
+    span: String,
+    date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Agenda, AgendaError> {
+    let span: AgendaSpan = span.parse()?;
+    let date = date.map(|d| parse_frontend_date(&d)).transpose().map_err(AgendaError::InvalidDate)?;
+    let now = Utc::now().naive_utc();
+
+    state
+        .read(move |conn| {
+            let calendar = SettingsRepository::get(conn, user_id)?.calendar();
+            let (from, to) = span.bounds(date.unwrap_or(calendar.local_date(now)), &calendar);
+            AgendaRepository::agenda(conn, user_id, from, to)
+        })
+        .await?
+}
@@ -0,0 +1,139 @@
+use tauri::State;
+
+use crate::{
+    errors::AssessmentError,
+    models::{Assessment, GradeSummary, GradingScheme, Subject},
+    repository::{AssessmentRepository, SubjectRepository, UnitOfWork},
+    utils::parse_frontend_datetime,
+    AppState,
+};
+
+fn parse_date(date: Option<String>) -> Result<Option<chrono::NaiveDateTime>, AssessmentError> {
+    date.map(|date| parse_frontend_datetime(&date))
+        .transpose()
+        .map_err(AssessmentError::InvalidDate)
+}
+
+/// Adds an assessment; exams with a date also get an exam task.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn add_assessment(
+    subject_id: u32,
+    title: String,
+    weight: f64,
+    max_score: f64,
+    date: Option<String>,
+    is_exam: bool,
+    score: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<Assessment, AssessmentError> {
+    let mut assessment = Assessment::new(subject_id, title.trim().to_string(), weight, max_score)?;
+    assessment.date = parse_date(date)?;
+    assessment.is_exam = is_exam;
+    assessment.score = score;
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| AssessmentRepository::add(uow, &mut assessment))?;
+            Ok(assessment)
+        })
+        .await?
+}
+
+/// Replaces every field of the assessment.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_assessment(
+    assessment_id: u32,
+    title: String,
+    weight: f64,
+    max_score: f64,
+    date: Option<String>,
+    is_exam: bool,
+    score: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<Assessment, AssessmentError> {
+    let date = parse_date(date)?;
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                let mut assessment = AssessmentRepository::find(uow, assessment_id)?;
+                assessment.title = title.trim().to_string();
+                assessment.weight = weight;
+                assessment.max_score = max_score;
+                assessment.date = date;
+                assessment.is_exam = is_exam;
+                assessment.score = score;
+                AssessmentRepository::update(uow, &mut assessment).map(|_| assessment)
+            })
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn record_assessment_score(
+    assessment_id: u32,
+    score: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<Assessment, AssessmentError> {
+    state
+        .write(move |conn| AssessmentRepository::record_score(conn, assessment_id, score))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_assessment(assessment_id: u32, state: State<'_, AppState>) -> Result<bool, AssessmentError> {
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| AssessmentRepository::delete(uow, assessment_id)))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_assessments(subject_id: u32, state: State<'_, AppState>) -> Result<Vec<Assessment>, AssessmentError> {
+    state
+        .read(move |conn| AssessmentRepository::list_for_subject(conn, subject_id))
+        .await?
+}
+
+/// Sets how the subject's grades are written and, optionally, the passing
+/// grade in that scheme ("6", "60%", "D").
+#[tauri::command]
+pub async fn set_grading_scheme(
+    subject_id: u32,
+    scheme: String,
+    passing_grade: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Subject, AssessmentError> {
+    let scheme = scheme.parse::<GradingScheme>()?;
+    let passing_grade = passing_grade.map(|grade| scheme.parse(&grade)).transpose()?;
+
+    state
+        .write(move |conn| {
+            let mut subject = SubjectRepository::find(conn, subject_id)?;
+            subject.grading_scheme = scheme;
+            subject.passing_grade = passing_grade;
+            SubjectRepository::update(conn, &subject)?;
+            Ok(subject)
+        })
+        .await?
+}
+
+/// Current average and the scores still needed to reach `target` (written
+/// in the subject's scheme; defaults to its passing grade).
+#[tauri::command]
+pub async fn get_grade_summary(
+    subject_id: u32,
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GradeSummary, AssessmentError> {
+    state
+        .read(move |conn| {
+            let target = match target {
+                Some(target) => Some(SubjectRepository::find(conn, subject_id)?.grading_scheme.parse(&target)?),
+                None => None,
+            };
+            AssessmentRepository::summary(conn, subject_id, target)
+        })
+        .await?
+}
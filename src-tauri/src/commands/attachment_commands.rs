@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::{
+    errors::AttachmentError,
+    models::{Attachment, IntegrityReport},
+    repository::AttachmentRepository,
+    AppState,
+};
+
+/// Copies the file at `path` into the attachment store and attaches it to
+/// either `task_id` or `note_id`.
+#[tauri::command]
+pub async fn attach_file(
+    user_id: u32,
+    path: String,
+    task_id: Option<u32>,
+    note_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Attachment, AttachmentError> {
+    let store = state.attachments.clone();
+    state
+        .write(move |conn| {
+            AttachmentRepository::import(conn, &store, user_id, task_id, note_id, &PathBuf::from(path))
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_task_attachments(task_id: u32, state: State<'_, AppState>) -> Result<Vec<Attachment>, AttachmentError> {
+    state
+        .read(move |conn| AttachmentRepository::list_for_task(conn, task_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_note_attachments(note_id: u32, state: State<'_, AppState>) -> Result<Vec<Attachment>, AttachmentError> {
+    state
+        .read(move |conn| AttachmentRepository::list_for_note(conn, note_id))
+        .await?
+}
+
+/// Absolute path of the stored blob, for opening it with the system viewer.
+#[tauri::command]
+pub async fn get_attachment_path(attachment_id: u32, state: State<'_, AppState>) -> Result<String, AttachmentError> {
+    let store = state.attachments.clone();
+    state
+        .read(move |conn| {
+            let attachment = AttachmentRepository::find(conn, attachment_id)?;
+            Ok(store.path(&attachment.hash).to_string_lossy().into_owned())
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_attachment(attachment_id: u32, state: State<'_, AppState>) -> Result<bool, AttachmentError> {
+    let store = state.attachments.clone();
+    state
+        .write(move |conn| AttachmentRepository::delete(conn, &store, attachment_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn verify_attachments(user_id: u32, state: State<'_, AppState>) -> Result<IntegrityReport, AttachmentError> {
+    let store = state.attachments.clone();
+    state
+        .read(move |conn| AttachmentRepository::verify(conn, &store, user_id))
+        .await?
+}
+
+/// Removes blobs no attachment references; returns how many were removed.
+#[tauri::command]
+pub async fn collect_attachment_garbage(state: State<'_, AppState>) -> Result<u32, AttachmentError> {
+    let store = state.attachments.clone();
+    state
+        .write(move |conn| AttachmentRepository::collect_garbage(conn, &store))
+        .await?
+}
@@ -0,0 +1,102 @@
+use chrono::{NaiveDateTime, Utc};
+use tauri::State;
+
+use crate::{
+    errors::GoalError,
+    models::{Goal, GoalProgress},
+    repository::{GoalRepository, SettingsRepository},
+    utils::{parse_frontend_date, truncate_to_minute},
+    AppState,
+};
+
+fn now() -> NaiveDateTime {
+    truncate_to_minute(Utc::now().naive_utc())
+}
+
+/// Creates a goal counting from today in the user's calendar.
+#[tauri::command]
+pub async fn create_goal(
+    user_id: u32,
+    title: String,
+    metric: String,
+    period: String,
+    target: u32,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Goal, GoalError> {
+    let metric = metric.parse()?;
+    let period = period.parse()?;
+
+    state
+        .write(move |conn| {
+            let starts_on = SettingsRepository::get(conn, user_id)?.calendar().local_date(now());
+            let mut goal = Goal { id: None, user_id, title, metric, period, target, subject_id, starts_on };
+            GoalRepository::create(conn, &mut goal).map(|_| goal)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn update_goal(
+    goal_id: u32,
+    title: String,
+    target: u32,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Goal, GoalError> {
+    state
+        .write(move |conn| {
+            let goal = Goal { title, target, subject_id, ..GoalRepository::find(conn, goal_id)? };
+            GoalRepository::update(conn, &goal).map(|_| goal)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_goal(goal_id: u32, state: State<'_, AppState>) -> Result<bool, GoalError> {
+    state
+        .write(move |conn| GoalRepository::delete(conn, goal_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_goal_progress(goal_id: u32, state: State<'_, AppState>) -> Result<GoalProgress, GoalError> {
+    state
+        .read(move |conn| GoalRepository::progress(conn, goal_id, now()))
+        .await?
+}
+
+/// Every goal of the user with its current period and streaks.
+#[tauri::command]
+pub async fn list_goals(user_id: u32, state: State<'_, AppState>) -> Result<Vec<GoalProgress>, GoalError> {
+    state
+        .read(move |conn| GoalRepository::progress_for_user(conn, user_id, now()))
+        .await?
+}
+
+/// Freezes a day so that missing it does not break any streak.
+#[tauri::command]
+pub async fn freeze_day(user_id: u32, date: String, state: State<'_, AppState>) -> Result<(), GoalError> {
+    let date = parse_frontend_date(&date).map_err(GoalError::InvalidDate)?;
+    state
+        .write(move |conn| GoalRepository::freeze_day(conn, user_id, date))
+        .await?
+}
+
+#[tauri::command]
+pub async fn unfreeze_day(user_id: u32, date: String, state: State<'_, AppState>) -> Result<bool, GoalError> {
+    let date = parse_frontend_date(&date).map_err(GoalError::InvalidDate)?;
+    state
+        .write(move |conn| GoalRepository::unfreeze_day(conn, user_id, date))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_freeze_days(user_id: u32, state: State<'_, AppState>) -> Result<Vec<String>, GoalError> {
+    state
+        .read(move |conn| {
+            GoalRepository::freezes(conn, user_id)
+                .map(|dates| dates.into_iter().map(|d| d.format("%Y-%m-%d").to_string()).collect())
+        })
+        .await?
+}
@@ -5,4 +5,51 @@ pub mod tag_commands;
 pub use tag_commands::*;
 
 pub mod task_commands;
-pub use task_commands::*;
\ No newline at end of file
+pub use task_commands::*;
+
+pub mod workflow_status_commands;
+pub use workflow_status_commands::*;
+
+pub mod time_commands;
+pub use time_commands::*;
+pub mod reminder_commands;
+pub use reminder_commands::*;
+
+pub mod subject_commands;
+pub use subject_commands::*;
+
+pub mod timetable_commands;
+pub use timetable_commands::*;
+
+pub mod assessment_commands;
+pub use assessment_commands::*;
+
+pub mod study_plan_commands;
+pub use study_plan_commands::*;
+
+pub mod note_commands;
+pub use note_commands::*;
+
+pub mod attachment_commands;
+pub use attachment_commands::*;
+
+pub mod reading_commands;
+pub use reading_commands::*;
+
+pub mod quiz_commands;
+pub use quiz_commands::*;
+
+pub mod settings_commands;
+pub use settings_commands::*;
+
+pub mod goal_commands;
+pub use goal_commands::*;
+
+pub mod statistics_commands;
+pub use statistics_commands::*;
+
+pub mod agenda_commands;
+pub use agenda_commands::*;
+
+pub mod reschedule_commands;
+pub use reschedule_commands::*;
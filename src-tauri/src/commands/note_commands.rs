@@ -0,0 +1,110 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    errors::NoteError,
+    models::{Note, NoteLinks, NoteSearchHit},
+    repository::{AttachmentRepository, NoteRepository, UnitOfWork},
+    utils::truncate_to_minute,
+    AppState,
+};
+
+/// How many hits `search_notes` returns by default.
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+
+#[tauri::command]
+pub async fn create_note(
+    user_id: u32,
+    title: String,
+    body: String,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Note, NoteError> {
+    let mut note = Note::new(user_id, title.trim().to_string(), body)?;
+    note.subject_id = subject_id;
+
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| NoteRepository::create(uow, &mut note)).map(|_| note))
+        .await?
+}
+
+#[tauri::command]
+pub async fn update_note(
+    note_id: u32,
+    title: String,
+    body: String,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Note, NoteError> {
+    state
+        .write(move |conn| {
+            let mut note = NoteRepository::find(conn, note_id)?;
+            note.title = title.trim().to_string();
+            note.body = body;
+            note.subject_id = subject_id;
+            note.updated_at = truncate_to_minute(Utc::now().naive_utc());
+            UnitOfWork::run(conn, |uow| NoteRepository::update(uow, &note)).map(|_| note)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_note(note_id: u32, state: State<'_, AppState>) -> Result<Note, NoteError> {
+    state.read(move |conn| NoteRepository::find(conn, note_id)).await?
+}
+
+#[tauri::command]
+pub async fn list_notes(
+    user_id: u32,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Note>, NoteError> {
+    state
+        .read(move |conn| NoteRepository::list(conn, user_id, subject_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_note(note_id: u32, state: State<'_, AppState>) -> Result<bool, NoteError> {
+    let store = state.attachments.clone();
+    state
+        .write(move |conn| {
+            let deleted = NoteRepository::delete(conn, note_id)?;
+            // Best effort: blobs left behind are collected by the next sweep.
+            let _ = AttachmentRepository::collect_garbage(conn, &store);
+            Ok(deleted)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_note_links(note_id: u32, state: State<'_, AppState>) -> Result<NoteLinks, NoteError> {
+    state.read(move |conn| NoteRepository::links(conn, note_id)).await?
+}
+
+#[tauri::command]
+pub async fn get_backlinks(note_id: u32, state: State<'_, AppState>) -> Result<Vec<Note>, NoteError> {
+    state.read(move |conn| NoteRepository::backlinks(conn, note_id)).await?
+}
+
+#[tauri::command]
+pub async fn get_task_notes(task_id: u32, state: State<'_, AppState>) -> Result<Vec<Note>, NoteError> {
+    state.read(move |conn| NoteRepository::task_notes(conn, task_id)).await?
+}
+
+#[tauri::command]
+pub async fn get_orphan_notes(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Note>, NoteError> {
+    state.read(move |conn| NoteRepository::orphans(conn, user_id)).await?
+}
+
+#[tauri::command]
+pub async fn search_notes(
+    user_id: u32,
+    query: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteSearchHit>, NoteError> {
+    state
+        .read(move |conn| NoteRepository::search(conn, user_id, &query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)))
+        .await?
+}
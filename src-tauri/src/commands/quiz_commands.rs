@@ -0,0 +1,118 @@
+use chrono::Utc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::{
+    errors::QuizError,
+    models::{Question, QuizAttempt, QuizResult, QuizView, SubmittedAnswer, TopicScore, DEFAULT_QUIZ_SIZE},
+    repository::{QuizRepository, UnitOfWork},
+    utils::truncate_to_minute,
+    AppState,
+};
+
+/// Topics with fewer graded answers than this are left out of analytics.
+const DEFAULT_MIN_ANSWERS: u32 = 3;
+
+#[tauri::command]
+pub async fn create_question(question: Question, state: State<'_, AppState>) -> Result<Question, QuizError> {
+    let mut question = Question { id: None, ..question };
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| QuizRepository::create_question(uow, &mut question)).map(|_| question)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn update_question(
+    question_id: u32,
+    question: Question,
+    state: State<'_, AppState>,
+) -> Result<Question, QuizError> {
+    let question = Question { id: Some(question_id), ..question };
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| QuizRepository::update_question(uow, &question)).map(|_| question))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_question(question_id: u32, state: State<'_, AppState>) -> Result<bool, QuizError> {
+    state
+        .write(move |conn| QuizRepository::delete_question(conn, question_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_questions(
+    user_id: u32,
+    subject_id: Option<u32>,
+    tag_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Question>, QuizError> {
+    state
+        .read(move |conn| QuizRepository::list_questions(conn, user_id, subject_id, tag_id))
+        .await?
+}
+
+/// Starts a quiz of random questions from a subject and/or tag.
+#[tauri::command]
+pub async fn start_quiz(
+    user_id: u32,
+    subject_id: Option<u32>,
+    tag_id: Option<u32>,
+    size: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<QuizView, QuizError> {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() ^ elapsed.as_secs() as u32)
+        .unwrap_or_default();
+    let now = truncate_to_minute(Utc::now().naive_utc());
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                QuizRepository::start_quiz(uow, user_id, subject_id, tag_id, size.unwrap_or(DEFAULT_QUIZ_SIZE), seed, now)
+            })
+        })
+        .await?
+}
+
+/// Questions of an unfinished quiz, to resume it.
+#[tauri::command]
+pub async fn get_quiz(attempt_id: u32, state: State<'_, AppState>) -> Result<QuizView, QuizError> {
+    state.read(move |conn| QuizRepository::quiz(conn, attempt_id)).await?
+}
+
+#[tauri::command]
+pub async fn submit_quiz(
+    attempt_id: u32,
+    answers: Vec<SubmittedAnswer>,
+    state: State<'_, AppState>,
+) -> Result<QuizResult, QuizError> {
+    let now = truncate_to_minute(Utc::now().naive_utc());
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| QuizRepository::submit_quiz(uow, attempt_id, &answers, now)))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_quiz_result(attempt_id: u32, state: State<'_, AppState>) -> Result<QuizResult, QuizError> {
+    state.read(move |conn| QuizRepository::result(conn, attempt_id)).await?
+}
+
+#[tauri::command]
+pub async fn get_quiz_history(user_id: u32, state: State<'_, AppState>) -> Result<Vec<QuizAttempt>, QuizError> {
+    state.read(move |conn| QuizRepository::history(conn, user_id)).await?
+}
+
+#[tauri::command]
+pub async fn get_weakest_topics(
+    user_id: u32,
+    min_answers: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TopicScore>, QuizError> {
+    state
+        .read(move |conn| QuizRepository::weakest_topics(conn, user_id, min_answers.unwrap_or(DEFAULT_MIN_ANSWERS)))
+        .await?
+}
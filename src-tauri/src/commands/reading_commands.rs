@@ -0,0 +1,158 @@
+use chrono::{Local, NaiveDate};
+use rusqlite::Connection;
+use tauri::State;
+
+use crate::{
+    errors::ReadingError,
+    models::{Book, ReadingProgress, ReadingSession, ReadingUnit, Task},
+    repository::{ReadingRepository, UnitOfWork},
+    utils::{local_to_utc, parse_frontend_date},
+    AppState,
+};
+
+fn parse_optional_date(date: Option<String>) -> Result<Option<NaiveDate>, ReadingError> {
+    date.map(|date| parse_frontend_date(&date).map_err(ReadingError::InvalidDate))
+        .transpose()
+}
+
+/// Keeps the daily reading tasks in step with the book: whenever its target
+/// is still ahead, the pending tasks are planned again from today.
+fn sync_reading_tasks(conn: &Connection, book: &Book, today: NaiveDate) -> Result<(), ReadingError> {
+    if book.target_date.is_some_and(|target| target >= today) {
+        UnitOfWork::run(conn, |uow| {
+            ReadingRepository::schedule_tasks(uow, book.id.unwrap_or_default(), today, local_to_utc)
+        })?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_book(
+    user_id: u32,
+    title: String,
+    total_units: u32,
+    unit: Option<String>,
+    author: Option<String>,
+    subject_id: Option<u32>,
+    target_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ReadingProgress, ReadingError> {
+    let unit = unit.map(|unit| unit.parse::<ReadingUnit>()).transpose()?.unwrap_or_default();
+    let mut book = Book::new(user_id, title.trim().to_string(), total_units, unit)?;
+    book.author = author;
+    book.subject_id = subject_id;
+    book.target_date = parse_optional_date(target_date)?;
+    let today = Local::now().date_naive();
+
+    state
+        .write(move |conn| {
+            ReadingRepository::create_book(conn, &mut book)?;
+            sync_reading_tasks(conn, &book, today)?;
+            ReadingRepository::progress(conn, book.id.unwrap_or_default(), today)
+        })
+        .await?
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_book(
+    book_id: u32,
+    title: String,
+    total_units: u32,
+    unit: Option<String>,
+    author: Option<String>,
+    subject_id: Option<u32>,
+    target_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ReadingProgress, ReadingError> {
+    let unit = unit.map(|unit| unit.parse::<ReadingUnit>()).transpose()?;
+    let target_date = parse_optional_date(target_date)?;
+    let today = Local::now().date_naive();
+
+    state
+        .write(move |conn| {
+            let mut book = ReadingRepository::find_book(conn, book_id)?;
+            book.title = title.trim().to_string();
+            book.total_units = total_units;
+            book.unit = unit.unwrap_or(book.unit);
+            book.author = author;
+            book.subject_id = subject_id;
+            book.target_date = target_date;
+            ReadingRepository::update_book(conn, &book)?;
+            sync_reading_tasks(conn, &book, today)?;
+            ReadingRepository::progress(conn, book_id, today)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_books(user_id: u32, state: State<'_, AppState>) -> Result<Vec<ReadingProgress>, ReadingError> {
+    let today = Local::now().date_naive();
+    state
+        .read(move |conn| ReadingRepository::progress_for_user(conn, user_id, today))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_book_progress(book_id: u32, state: State<'_, AppState>) -> Result<ReadingProgress, ReadingError> {
+    let today = Local::now().date_naive();
+    state
+        .read(move |conn| ReadingRepository::progress(conn, book_id, today))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_book(book_id: u32, state: State<'_, AppState>) -> Result<bool, ReadingError> {
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| ReadingRepository::delete_book(uow, book_id)))
+        .await?
+}
+
+/// Logs pages or chapters read, on `read_on` or today.
+#[tauri::command]
+pub async fn log_reading_session(
+    book_id: u32,
+    units: u32,
+    minutes: Option<u32>,
+    read_on: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ReadingProgress, ReadingError> {
+    let today = Local::now().date_naive();
+    let read_on = parse_optional_date(read_on)?.unwrap_or(today);
+    let mut session = ReadingSession::new(book_id, read_on, units, minutes)?;
+
+    state
+        .write(move |conn| {
+            ReadingRepository::log_session(conn, &mut session)?;
+            let book = ReadingRepository::find_book(conn, book_id)?;
+            sync_reading_tasks(conn, &book, today)?;
+            ReadingRepository::progress(conn, book_id, today)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_reading_sessions(book_id: u32, state: State<'_, AppState>) -> Result<Vec<ReadingSession>, ReadingError> {
+    state
+        .read(move |conn| ReadingRepository::sessions(conn, book_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_reading_session(session_id: u32, state: State<'_, AppState>) -> Result<bool, ReadingError> {
+    state
+        .write(move |conn| ReadingRepository::delete_session(conn, session_id))
+        .await?
+}
+
+/// Plans one reading task per day from today until the book's target date.
+#[tauri::command]
+pub async fn generate_reading_tasks(book_id: u32, state: State<'_, AppState>) -> Result<Vec<Task>, ReadingError> {
+    let today = Local::now().date_naive();
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| ReadingRepository::schedule_tasks(uow, book_id, today, local_to_utc))
+        })
+        .await?
+}
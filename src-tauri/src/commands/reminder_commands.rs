@@ -0,0 +1,72 @@
+use chrono::{Duration, Utc};
+use tauri::State;
+
+use crate::{
+    errors::ReminderError,
+    models::{Reminder, ReminderRule},
+    repository::ReminderRepository,
+    utils::parse_frontend_datetime,
+    AppState,
+};
+
+/// Adds a reminder either at `remind_at` or `minutes_before_due`; exactly
+/// one of the two must be given.
+#[tauri::command]
+pub async fn add_reminder(
+    task_id: u32,
+    remind_at: Option<String>,
+    minutes_before_due: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Reminder, ReminderError> {
+    let rule = match (remind_at, minutes_before_due) {
+        (Some(at), None) => ReminderRule::At(parse_frontend_datetime(&at).map_err(ReminderError::InvalidDate)?),
+        (None, Some(minutes)) => ReminderRule::BeforeDue(minutes),
+        _ => {
+            return Err(ReminderError::InvalidRule(
+                "set either remind_at or minutes_before_due".to_string(),
+            ))
+        }
+    };
+
+    state
+        .write(move |conn| ReminderRepository::add(conn, task_id, rule))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_reminders(task_id: u32, state: State<'_, AppState>) -> Result<Vec<Reminder>, ReminderError> {
+    state
+        .read(move |conn| ReminderRepository::list_for_task(conn, task_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_reminder(reminder_id: u32, state: State<'_, AppState>) -> Result<bool, ReminderError> {
+    state
+        .write(move |conn| ReminderRepository::delete(conn, reminder_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn snooze_reminder(
+    reminder_id: u32,
+    minutes: u32,
+    state: State<'_, AppState>,
+) -> Result<Reminder, ReminderError> {
+    if minutes == 0 {
+        return Err(ReminderError::InvalidRule("snooze must be at least one minute".to_string()));
+    }
+    let until = Utc::now().naive_utc() + Duration::minutes(minutes as i64);
+
+    state
+        .write(move |conn| ReminderRepository::snooze(conn, reminder_id, until))
+        .await?
+}
+
+#[tauri::command]
+pub async fn dismiss_reminder(reminder_id: u32, state: State<'_, AppState>) -> Result<Reminder, ReminderError> {
+    let now = Utc::now().naive_utc();
+    state
+        .write(move |conn| ReminderRepository::dismiss(conn, reminder_id, now))
+        .await?
+}
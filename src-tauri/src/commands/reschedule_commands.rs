@@ -0,0 +1,37 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    errors::RescheduleError,
+    models::{BulkSummary, RescheduleChange, RescheduleOptions, ReschedulePreview},
+    repository::{RescheduleRepository, UnitOfWork},
+    AppState,
+};
+
+/// Proposes new due dates for the user's overdue tasks without saving
+/// anything. Missing options fall back to `RescheduleOptions::default`.
+#[tauri::command]
+pub async fn preview_reschedule(
+    user_id: u32,
+    options: Option<RescheduleOptions>,
+    state: State<'_, AppState>,
+) -> Result<ReschedulePreview, RescheduleError> {
+    let options = options.unwrap_or_default();
+    let now = Utc::now().naive_utc();
+    state
+        .read(move |conn| RescheduleRepository::preview(conn, user_id, &options, now))
+        .await?
+}
+
+/// Applies the changes accepted from `preview_reschedule` in one
+/// transaction; `undo_last_operation` restores the previous dates.
+#[tauri::command]
+pub async fn apply_reschedule(
+    user_id: u32,
+    changes: Vec<RescheduleChange>,
+    state: State<'_, AppState>,
+) -> Result<BulkSummary, RescheduleError> {
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| RescheduleRepository::apply(uow, user_id, &changes)))
+        .await?
+}
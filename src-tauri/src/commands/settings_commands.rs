@@ -0,0 +1,30 @@
+use chrono::Weekday;
+use tauri::State;
+
+use crate::{errors::SettingsError, models::UserSettings, repository::SettingsRepository, AppState};
+
+#[tauri::command]
+pub async fn get_user_settings(user_id: u32, state: State<'_, AppState>) -> Result<UserSettings, SettingsError> {
+    state
+        .read(move |conn| SettingsRepository::get(conn, user_id))
+        .await?
+}
+
+/// `week_start` is an English day name such as "mon"; a missing
+/// `utc_offset_minutes` follows the system time zone.
+#[tauri::command]
+pub async fn update_user_settings(
+    user_id: u32,
+    week_start: String,
+    utc_offset_minutes: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<UserSettings, SettingsError> {
+    let week_start = week_start
+        .parse::<Weekday>()
+        .map_err(|_| SettingsError::InvalidSettings(format!("Invalid weekday: {}", week_start)))?;
+    let settings = UserSettings { user_id, week_start, utc_offset_minutes };
+
+    state
+        .write(move |conn| SettingsRepository::save(conn, &settings).map(|_| settings))
+        .await?
+}
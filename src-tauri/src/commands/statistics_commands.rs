@@ -0,0 +1,42 @@
+use chrono::{Duration, Utc};
+use tauri::State;
+
+use crate::{
+    errors::StatisticsError,
+    models::{Statistics, StatsGrouping},
+    repository::{SettingsRepository, StatisticsRepository},
+    utils::parse_frontend_date,
+    AppState,
+};
+
+/// Days covered when no `from` date is given.
+const DEFAULT_STATISTICS_DAYS: i64 = 28;
+
+/// Chart-ready statistics between `from` and `to` (default: the four weeks
+/// ending today), bucketed by `grouping` ("day" or "week", default "day").
+#[tauri::command]
+pub async fn get_statistics(
+    user_id: u32,
+    from: Option<String>,
+    to: Option<String>,
+    grouping: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Statistics, StatisticsError> {
+    let from = from.map(|d| parse_frontend_date(&d)).transpose().map_err(StatisticsError::InvalidDate)?;
+    let to = to.map(|d| parse_frontend_date(&d)).transpose().map_err(StatisticsError::InvalidDate)?;
+    let grouping = match grouping {
+        Some(grouping) => grouping.parse()?,
+        None => StatsGrouping::Day,
+    };
+
+    state
+        .read(move |conn| {
+            let to = match to {
+                Some(to) => to,
+                None => SettingsRepository::get(conn, user_id)?.calendar().local_date(Utc::now().naive_utc()),
+            };
+            let from = from.unwrap_or(to - Duration::days(DEFAULT_STATISTICS_DAYS - 1));
+            StatisticsRepository::statistics(conn, user_id, from, to, grouping)
+        })
+        .await?
+}
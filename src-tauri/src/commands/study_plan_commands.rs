@@ -0,0 +1,86 @@
+use chrono::{Local, Weekday};
+use tauri::State;
+
+use crate::{
+    errors::StudyPlanError,
+    models::{StudyPlan, StudyPlanView, TopicEstimate},
+    repository::{StudyPlanRepository, UnitOfWork},
+    utils::{local_to_utc, parse_frontend_date},
+    AppState,
+};
+
+/// Creates a plan and schedules study blocks from today until the exam.
+/// `rest_days` are English day names such as "sat".
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_study_plan(
+    user_id: u32,
+    title: String,
+    exam_date: String,
+    topics: Vec<TopicEstimate>,
+    subject_id: Option<u32>,
+    daily_capacity_minutes: Option<u32>,
+    block_minutes: Option<u32>,
+    rest_days: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<StudyPlanView, StudyPlanError> {
+    let exam_date = parse_frontend_date(&exam_date).map_err(StudyPlanError::InvalidDate)?;
+    let mut plan = StudyPlan::new(user_id, title.trim().to_string(), exam_date)?;
+    plan.subject_id = subject_id;
+    if let Some(minutes) = daily_capacity_minutes {
+        plan.daily_capacity_minutes = minutes;
+    }
+    if let Some(minutes) = block_minutes {
+        plan.block_minutes = minutes;
+    }
+    plan.rest_days = rest_days
+        .unwrap_or_default()
+        .iter()
+        .map(|day| {
+            day.parse::<Weekday>()
+                .map_err(|_| StudyPlanError::InvalidPlan(format!("Invalid weekday: {}", day)))
+        })
+        .collect::<Result<_, _>>()?;
+    let today = Local::now().date_naive();
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                StudyPlanRepository::create(uow, &mut plan, &topics, today, local_to_utc)
+            })
+        })
+        .await?
+}
+
+/// Re-distributes unfinished topics from today, e.g. after missed blocks.
+#[tauri::command]
+pub async fn replan_study_plan(plan_id: u32, state: State<'_, AppState>) -> Result<StudyPlanView, StudyPlanError> {
+    let today = Local::now().date_naive();
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| StudyPlanRepository::replan(uow, plan_id, today, local_to_utc))
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_study_plan(plan_id: u32, state: State<'_, AppState>) -> Result<StudyPlanView, StudyPlanError> {
+    state
+        .read(move |conn| StudyPlanRepository::view(conn, plan_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_study_plans(user_id: u32, state: State<'_, AppState>) -> Result<Vec<StudyPlan>, StudyPlanError> {
+    state
+        .read(move |conn| StudyPlanRepository::list(conn, user_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_study_plan(plan_id: u32, state: State<'_, AppState>) -> Result<bool, StudyPlanError> {
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| StudyPlanRepository::delete(uow, plan_id)))
+        .await?
+}
@@ -0,0 +1,113 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    errors::SubjectError,
+    models::{Subject, SubjectDashboard},
+    repository::SubjectRepository,
+    AppState,
+};
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_subject(
+    user_id: u32,
+    name: String,
+    color: String,
+    teacher: Option<String>,
+    term: Option<String>,
+    credit_hours: Option<u32>,
+    weekly_goal_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Subject, SubjectError> {
+    let mut subject = Subject {
+        id: None,
+        user_id,
+        name: name.trim().to_string(),
+        teacher,
+        color,
+        term,
+        credit_hours,
+        weekly_goal_minutes,
+        grading_scheme: Default::default(),
+        passing_grade: None,
+    };
+
+    state
+        .write(move |conn| SubjectRepository::create(conn, &mut subject).map(|_| subject))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_subjects(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Subject>, SubjectError> {
+    state
+        .read(move |conn| SubjectRepository::list(conn, user_id))
+        .await?
+}
+
+/// Replaces every field of the subject; `None` clears optional fields.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_subject(
+    subject_id: u32,
+    name: String,
+    color: String,
+    teacher: Option<String>,
+    term: Option<String>,
+    credit_hours: Option<u32>,
+    weekly_goal_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Subject, SubjectError> {
+    state
+        .write(move |conn| {
+            let mut subject = SubjectRepository::find(conn, subject_id)?;
+            subject.name = name.trim().to_string();
+            subject.color = color;
+            subject.teacher = teacher;
+            subject.term = term;
+            subject.credit_hours = credit_hours;
+            subject.weekly_goal_minutes = weekly_goal_minutes;
+            SubjectRepository::update(conn, &subject).map(|_| subject)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_subject(subject_id: u32, state: State<'_, AppState>) -> Result<bool, SubjectError> {
+    state
+        .write(move |conn| SubjectRepository::delete(conn, subject_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn set_task_subject(
+    task_id: u32,
+    subject_id: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), SubjectError> {
+    state
+        .write(move |conn| SubjectRepository::set_task_subject(conn, task_id, subject_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_subject_dashboard(
+    subject_id: u32,
+    state: State<'_, AppState>,
+) -> Result<SubjectDashboard, SubjectError> {
+    let now = Utc::now().naive_utc();
+    state
+        .read(move |conn| SubjectRepository::dashboard(conn, subject_id, now))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_subject_dashboards(
+    user_id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<SubjectDashboard>, SubjectError> {
+    let now = Utc::now().naive_utc();
+    state
+        .read(move |conn| SubjectRepository::dashboards(conn, user_id, now))
+        .await?
+}
@@ -2,59 +2,49 @@ use tauri::State;
 
 use crate::{
     models::tag::Tag,
-    repository::tag_repository::TagRepository,
+    repository::{SqliteStore, TagStore},
     errors::command_errors::CommandError,
     AppState,
 };
 
 #[tauri::command]
-pub fn create_tag(name: String, color: String, state: State<AppState>) -> Result<String, CommandError> {
+pub async fn create_tag(name: String, color: String, state: State<'_, AppState>) -> Result<String, CommandError> {
     let mut tag = Tag::new(name, color)?;
     
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    
-    TagRepository::create(&conn, &mut tag)?;
+    let tag = state
+        .write(move |conn| SqliteStore::new(conn).create_tag(&mut tag).map(|_| tag))
+        .await??;
     
     Ok(format!("Tag {} criada com sucesso", tag.name))
 }
 
 
 #[tauri::command]
-pub fn get_tag_by_id(id: u32, state: State<AppState>) -> Result<Option<Tag>, CommandError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    
-    TagRepository::find_by_id(&conn, id)
-        .map_err(Into::into)
+pub async fn get_tag_by_id(id: u32, state: State<'_, AppState>) -> Result<Option<Tag>, CommandError> {
+    Ok(state.read(move |conn| SqliteStore::new(conn).find_tag(id)).await??)
 }
 
 #[tauri::command]
-pub fn list_tags(state: State<AppState>) -> Result<Vec<Tag>, CommandError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    
-    TagRepository::list_all(&conn)
-        .map_err(Into::into)
+pub async fn list_tags(state: State<'_, AppState>) -> Result<Vec<Tag>, CommandError> {
+    Ok(state.read(|conn| SqliteStore::new(conn).list_tags()).await??)
 }
 
 #[tauri::command]
-pub fn update_tag(id: u32, tag_name: String, state: State<AppState>) -> Result<String, CommandError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    
-    TagRepository::update_tag(&conn, id, tag_name.clone())?;
+pub async fn update_tag(id: u32, tag_name: String, state: State<'_, AppState>) -> Result<String, CommandError> {
+    let name = tag_name.clone();
+    state
+        .write(move |conn| SqliteStore::new(conn).rename_tag(id, name))
+        .await??;
     
     Ok(format!("Tag with id {} updated to {}", id, tag_name))
 }
 
 #[tauri::command]
-pub fn delete_tag(id: u32, state: State<AppState>) -> Result<String, CommandError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-
-    TagRepository::delete_by_id(&conn, id)
+pub async fn delete_tag(id: u32, state: State<'_, AppState>) -> Result<String, CommandError> {
+    state
+        .write(move |conn| SqliteStore::new(conn).delete_tag(id))
+        .await?
         .map_err(|e| CommandError::Database(e.to_string()))?;
 
     Ok("Tag deleted successfully".to_string())
-}
\ No newline at end of file
+}
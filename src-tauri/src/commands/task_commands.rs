@@ -1,48 +1,50 @@
-use chrono::NaiveDateTime;
+use chrono::{Duration, Utc};
+use rusqlite::Connection;
 use tauri::State;
 
 use crate::{
-    repository::{TaskRepository, TagRepository}, 
+    repository::{AttachmentRepository, SettingsRepository, SqliteStore, TaskStore, TaskRepository, BulkTaskRepository, UndoRepository, UndoResult, UnitOfWork,
+        WorkflowStatusRepository, TagRepository}, 
     errors::TaskError, 
     models::{Task, 
         task_status::TaskStatus, 
         task_priority::TaskPriority,
-        Tag, FrontendTag}, 
+        task_kind::TaskKind,
+        Tag, FrontendTag,
+        TaskSelection, BulkTaskChanges, BulkSummary,
+        BoardColumn, QuadrantGroup, group_by_quadrant,
+        QuickAddPreview}, 
+    utils::{parse_frontend_date, parse_frontend_datetime, parse_quick_add},
     AppState,
 };
 
 #[tauri::command]
-pub fn create_task(
+#[allow(clippy::too_many_arguments)]
+pub async fn create_task(
     title: String,
     user_id: u32,
     description: Option<String>,
-    due_date: String,
+    due_date: Option<String>,
+    all_day: Option<bool>,
+    start_date: Option<String>,
     priority: String,
     tags: Vec<FrontendTag>,
-    state: State<AppState>,
+    estimated_minutes: Option<u32>,
+    subject_id: Option<u32>,
+    kind: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<Task, String> {
-    let date_replaced = due_date.replace("Z", "");
-    let trimmed_date = if date_replaced.len() > 16 {
-        &date_replaced[..16]
-    } else {
-        &date_replaced
-    };
-
-    let _due_date = NaiveDateTime::parse_from_str(trimmed_date, "%Y-%m-%dT%H:%M")
-        .map_err(|e| format!("Invalid date format: {}", e))?;
-
     let priority = Some(priority
         .parse::<TaskPriority>()
         .map_err(|_| format!("Invalid priority value: {}", priority))?);
 
     let mut task = Task::new(title, user_id, description, priority)
         .map_err(|err| err.to_string())?;
-
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| "Database lock failed")?;
-
-    TaskRepository::insert_task(&conn, &mut task)
-        .map_err(|e| format!("Database error: {}", e))?;
+    task.estimated_minutes = estimated_minutes.filter(|&minutes| minutes > 0);
+    task.subject_id = subject_id;
+    if let Some(kind) = kind {
+        task.kind = kind.parse::<TaskKind>().map_err(|e| e.to_string())?;
+    }
 
     let tag_objects: Vec<Tag> = tags
         .iter()
@@ -53,70 +55,150 @@ pub fn create_task(
         })
         .collect();
 
-    TagRepository::update_task_tags(&conn, task.id.unwrap(), &tag_objects)
-        .map_err(|e| format!("Failed to associate tags: {}", e))?;
+    state
+        .write(move |conn| {
+            apply_dates(conn, &mut task, due_date.as_deref(), all_day.unwrap_or(false), start_date.as_deref())
+                .map_err(|e| e.to_string())?;
+            insert_new_task(conn, task, &tag_objects)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Sets `task`'s dates from the frontend's values. An all-day due date is a
+/// plain date, due at the end of that day in the user's time zone; any other
+/// date is a UTC datetime.
+fn apply_dates(
+    conn: &Connection,
+    task: &mut Task,
+    due_date: Option<&str>,
+    all_day: bool,
+    start_date: Option<&str>,
+) -> Result<(), TaskError> {
+    task.all_day = false;
+    task.due_date = match (due_date, all_day) {
+        (Some(due_date), true) => {
+            let date = parse_frontend_date(due_date).map_err(TaskError::InvalidDate)?;
+            let calendar = SettingsRepository::get(conn, task.user_id)
+                .map_err(|e| TaskError::DatabaseError(e.to_string()))?
+                .calendar();
+            task.set_due_day(date, |local| calendar.to_utc(local));
+            task.due_date
+        }
+        (Some(due_date), false) => Some(parse_frontend_datetime(due_date).map_err(TaskError::InvalidDate)?),
+        (None, _) => None,
+    };
+    task.start_date = start_date
+        .map(parse_frontend_datetime)
+        .transpose()
+        .map_err(TaskError::InvalidDate)?;
+    task.check_dates()
+}
+
+/// Saves a task built by `create_task` or quick-add in the user's initial status.
+fn insert_new_task(conn: &Connection, mut task: Task, tags: &[Tag]) -> Result<Task, String> {
+    task.status = WorkflowStatusRepository::initial_status(conn, task.user_id)
+        .map_err(|e| e.to_string())?;
+    SqliteStore::new(conn)
+        .insert_task(&mut task, tags)
+        .map(|_| task)
+        .map_err(|e| e.to_string())
+}
 
-    Ok(task)
+/// Parses `input` against the user's current local time.
+fn preview_quick_add_on(conn: &Connection, user_id: u32, input: &str) -> Result<QuickAddPreview, String> {
+    let calendar = SettingsRepository::get(conn, user_id).map_err(|e| e.to_string())?.calendar();
+    let parsed = parse_quick_add(input, calendar.to_local(Utc::now().naive_utc()));
+    let existing = TagRepository::list_all(conn).map_err(|e| e.to_string())?;
+    Ok(QuickAddPreview::new(parsed, &existing, &calendar))
 }
 
+/// Shows what `quick_add_task` would create from `input`, without saving.
 #[tauri::command]
-pub fn get_all_tasks(state: State<AppState>) -> Result<Vec<Task>, TaskError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|e| TaskError::DatabaseError(e.to_string()))?;
-    TaskRepository::get_all_tasks(&conn)
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))
+pub async fn preview_quick_add(
+    user_id: u32,
+    input: String,
+    state: State<'_, AppState>,
+) -> Result<QuickAddPreview, String> {
+    state
+        .read(move |conn| preview_quick_add_on(conn, user_id, &input))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
+/// Creates a task from a quick-add line such as
+/// `Read chapter 4 of Physics tomorrow 18h #physics !high`.
 #[tauri::command]
-pub fn delete_task(state: State<AppState>, task_id: u32) -> Result<(), TaskError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|e| TaskError::DatabaseError(e.to_string()))?;
-    TaskRepository::delete_task(&conn, task_id)
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))
+pub async fn quick_add_task(user_id: u32, input: String, state: State<'_, AppState>) -> Result<Task, String> {
+    state
+        .write(move |conn| {
+            let preview = preview_quick_add_on(conn, user_id, &input)?;
+            let mut task = Task::new(preview.title, user_id, None, Some(preview.priority))
+                .map_err(|err| err.to_string())?;
+            task.due_date = preview.due_date;
+            task.all_day = preview.all_day;
+            insert_new_task(conn, task, &preview.tags)
+        })
+        .await
+        .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
-pub fn get_tasks_for_today(state: State<AppState>) -> Result<Vec<Task>, TaskError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|e| TaskError::DatabaseError(e.to_string()))?;
-    
-    TaskRepository::get_tasks_for_today(&conn)
+pub async fn get_all_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, TaskError> {
+    state.read(|conn| SqliteStore::new(conn).list_tasks()).await?
+}
+
+#[tauri::command]
+pub async fn delete_task(state: State<'_, AppState>, task_id: u32) -> Result<(), TaskError> {
+    let store = state.attachments.clone();
+    state
+        .write(move |conn| {
+            SqliteStore::new(conn).delete_task(task_id)?;
+            // Best effort: blobs left behind are collected by the next sweep.
+            let _ = AttachmentRepository::collect_garbage(conn, &store);
+            Ok(())
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_tasks_for_today(state: State<'_, AppState>) -> Result<Vec<Task>, TaskError> {
+    let today = Utc::now().date_naive();
+    let start_of_day = today.and_hms_opt(0, 0, 0).unwrap();
+    let end_of_day = today.and_hms_opt(23, 59, 59).unwrap();
+
+    state
+        .read(move |conn| SqliteStore::new(conn).get_tasks_due_between(start_of_day, end_of_day))
+        .await?
 }
 
 
 #[tauri::command]
-pub fn update_task(
+#[allow(clippy::too_many_arguments)]
+pub async fn update_task(
     task_id: u32,
     title: Option<String>,
     description: Option<String>,
     status: Option<String>,
+    priority: Option<String>,
     due_date: Option<String>,
     tags: Option<Vec<FrontendTag>>,
-    state: State<AppState>,
+    state: State<'_, AppState>,
 ) -> Result<Task, String> {
     let status = match status {
-        Some(s) => Some(s.parse::<TaskStatus>().map_err(|e| e.to_string())?),
+        Some(s) => Some(TaskStatus::from_key(&s).map_err(|e| e.to_string())?),
         None => None,
     };
 
-    let due_date = match due_date {
-        Some(d) => {
-            let date_replaced = d.replace("Z", "");
-            let trimmed_date = if date_replaced.len() > 16 {
-                &date_replaced[..16]
-            } else {
-                &date_replaced
-            };
-            Some(
-                NaiveDateTime::parse_from_str(trimmed_date, "%Y-%m-%dT%H:%M")
-                    .map_err(|e| format!("Invalid date format: {}", e))?,
-            )
-        }
+    let priority = match priority {
+        Some(p) => Some(p.parse::<TaskPriority>().map_err(|_| format!("Invalid priority value: {}", p))?),
         None => None,
     };
 
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| "Database lock failed".to_string())?;
+    let due_date = match due_date {
+        Some(d) => Some(parse_frontend_datetime(&d)?),
+        None => None,
+    };
 
     let tag_objs: Option<Vec<crate::models::tag::Tag>> = tags.map(|frontend_tags| {
         frontend_tags
@@ -130,20 +212,230 @@ pub fn update_task(
     });
     
 
-    TaskRepository::update_task(
-        &conn,
-        task_id,
-        title,
-        description,
-        status,
-        None,
-        due_date,
-        tag_objs,
-    )
-    .map_err(|e| format!("Database error: {}", e))?;
-
-    let updated_task = TaskRepository::get_task_by_id(&conn, task_id)
-        .map_err(|e| format!("Error fetching updated task: {}", e))?;
-
-    Ok(updated_task)
+    state
+        .write(move |conn| {
+            let mut store = SqliteStore::new(conn);
+            store
+                .update_task(task_id, title, description, status, priority, due_date, tag_objs)
+                .map_err(|e| format!("Database error: {}", e))?;
+
+            store
+                .get_task(task_id)
+                .map_err(|e| format!("Error fetching updated task: {}", e))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn bulk_update_tasks(
+    user_id: u32,
+    selection: TaskSelection,
+    changes: BulkTaskChanges,
+    state: State<'_, AppState>,
+) -> Result<BulkSummary, TaskError> {
+    let attachments = state.attachments.clone();
+    state
+        .write(move |conn| {
+            let summary = UnitOfWork::run(conn, |uow| {
+                let mut store = SqliteStore::new(uow);
+                let (mut summary, snapshot) =
+                    BulkTaskRepository::update(&mut store, user_id, &selection, &changes)?;
+                if !snapshot.is_empty() {
+                    summary.undo_id = Some(UndoRepository::record(uow, user_id, "bulk_update_tasks", &snapshot)?);
+                }
+                Ok::<_, TaskError>(summary)
+            })?;
+            // Recording may prune an older bulk delete and release its blobs.
+            let _ = AttachmentRepository::collect_garbage(conn, &attachments);
+            Ok(summary)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn bulk_delete_tasks(
+    user_id: u32,
+    selection: TaskSelection,
+    state: State<'_, AppState>,
+) -> Result<BulkSummary, TaskError> {
+    let attachments = state.attachments.clone();
+    state
+        .write(move |conn| {
+            let summary = UnitOfWork::run(conn, |uow| {
+                let mut store = SqliteStore::new(uow);
+                let (selected, _) = BulkTaskRepository::select(&store, user_id, &selection)?;
+                let ids: Vec<u32> = selected.iter().filter_map(|task| task.id).collect();
+                let dependents = UndoRepository::capture_dependents(uow, &ids)?;
+                let (mut summary, deleted) = BulkTaskRepository::delete(&mut store, user_id, &selection)?;
+                if !deleted.is_empty() {
+                    summary.undo_id = Some(UndoRepository::record_delete(
+                        uow,
+                        user_id,
+                        "bulk_delete_tasks",
+                        &deleted,
+                        &dependents,
+                    )?);
+                }
+                Ok::<_, TaskError>(summary)
+            })?;
+            // Best effort: blobs released by pruned undo entries are collected by the next sweep.
+            let _ = AttachmentRepository::collect_garbage(conn, &attachments);
+            Ok(summary)
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn undo_last_operation(user_id: u32, state: State<'_, AppState>) -> Result<Option<UndoResult>, TaskError> {
+    state
+        .write(move |conn| UnitOfWork::run(conn, |uow| UndoRepository::undo_last(uow, user_id)))
+        .await?
+}
+
+#[tauri::command]
+pub async fn move_task(
+    task_id: u32,
+    status: String,
+    index: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Task, TaskError> {
+    let status = TaskStatus::from_key(&status)?;
+
+    state
+        .write(move |conn| SqliteStore::new(conn).move_task(task_id, status, index))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_board(user_id: u32, state: State<'_, AppState>) -> Result<Vec<BoardColumn>, TaskError> {
+    state
+        .read(move |conn| {
+            let store = SqliteStore::new(conn);
+            WorkflowStatusRepository::list(conn, user_id)
+                .map_err(|e| TaskError::DatabaseError(e.to_string()))?
+                .into_iter()
+                .map(|definition| {
+                    Ok(BoardColumn {
+                        wip_limit: store.wip_limit(user_id, &definition.status)?,
+                        tasks: store.list_column(user_id, &definition.status)?,
+                        status: definition,
+                    })
+                })
+                .collect()
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn set_wip_limit(
+    user_id: u32,
+    status: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), TaskError> {
+    let status = TaskStatus::from_key(&status)?;
+
+    state
+        .write(move |conn| SqliteStore::new(conn).set_wip_limit(user_id, &status, limit))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_tasks_by_quadrant(
+    user_id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<QuadrantGroup>, TaskError> {
+    let now = Utc::now().naive_utc();
+
+    let tasks = state
+        .read(move |conn| TaskRepository::get_available_tasks(conn, user_id, now))
+        .await??;
+    Ok(group_by_quadrant(tasks, now))
+}
+
+/// Replaces a task's due date, all-day flag and start date; `None` clears a
+/// date. See `create_task` for the formats.
+#[tauri::command]
+pub async fn set_task_dates(
+    task_id: u32,
+    due_date: Option<String>,
+    all_day: bool,
+    start_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Task, TaskError> {
+    state
+        .write(move |conn| {
+            let mut task = TaskRepository::get_task_by_id(conn, task_id)?;
+            apply_dates(conn, &mut task, due_date.as_deref(), all_day, start_date.as_deref())?;
+            TaskRepository::set_dates(conn, &task)?;
+            TaskRepository::get_task_by_id(conn, task_id)
+        })
+        .await?
+}
+
+/// The user's open tasks whose due date has passed, most overdue first.
+#[tauri::command]
+pub async fn get_overdue_tasks(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Task>, TaskError> {
+    let now = Utc::now().naive_utc();
+    state
+        .read(move |conn| TaskRepository::get_overdue_tasks(conn, user_id, now))
+        .await?
+}
+
+/// The user's tasks due on the local days `from..=to` (`YYYY-MM-DD`),
+/// including completed ones.
+#[tauri::command]
+pub async fn get_tasks_in_range(
+    user_id: u32,
+    from: String,
+    to: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Task>, TaskError> {
+    let from = parse_frontend_date(&from).map_err(TaskError::InvalidDate)?;
+    let to = parse_frontend_date(&to).map_err(TaskError::InvalidDate)?;
+    if from > to {
+        return Err(TaskError::InvalidDate(format!("{} is after {}", from, to)));
+    }
+    state
+        .read(move |conn| {
+            let calendar = SettingsRepository::get(conn, user_id)
+                .map_err(|e| TaskError::DatabaseError(e.to_string()))?
+                .calendar();
+            TaskRepository::get_tasks_in_range(
+                conn,
+                user_id,
+                calendar.day_start(from),
+                calendar.day_start(to + Duration::days(1)),
+            )
+        })
+        .await?
+}
+
+/// Tasks completed since midnight in the user's time zone.
+#[tauri::command]
+pub async fn get_completed_today(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Task>, TaskError> {
+    let now = Utc::now().naive_utc();
+    state
+        .read(move |conn| {
+            let calendar = SettingsRepository::get(conn, user_id)
+                .map_err(|e| TaskError::DatabaseError(e.to_string()))?
+                .calendar();
+            let today = calendar.local_date(now);
+            TaskRepository::get_completed_between(
+                conn,
+                user_id,
+                calendar.day_start(today),
+                calendar.day_start(today + Duration::days(1)),
+            )
+        })
+        .await?
+}
+
+/// Tasks that were completed after their due date, most recent first.
+#[tauri::command]
+pub async fn get_completed_late(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Task>, TaskError> {
+    state
+        .read(move |conn| TaskRepository::get_completed_late(conn, user_id))
+        .await?
 }
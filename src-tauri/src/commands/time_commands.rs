@@ -0,0 +1,73 @@
+use chrono::Utc;
+use tauri::State;
+
+use crate::{
+    errors::{TaskError, TimeLogError},
+    models::{EffortReport, TimeLog},
+    repository::{TaskRepository, TimeLogRepository},
+    utils::parse_frontend_datetime,
+    AppState,
+};
+
+#[tauri::command]
+pub async fn set_task_estimate(
+    task_id: u32,
+    estimated_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), TaskError> {
+    state
+        .write(move |conn| TaskRepository::set_estimated_minutes(conn, task_id, estimated_minutes))
+        .await?
+}
+
+#[tauri::command]
+pub async fn start_timer(task_id: u32, state: State<'_, AppState>) -> Result<TimeLog, TimeLogError> {
+    let now = Utc::now().naive_utc();
+    state
+        .write(move |conn| TimeLogRepository::start_timer(conn, task_id, now))
+        .await?
+}
+
+#[tauri::command]
+pub async fn stop_timer(task_id: u32, state: State<'_, AppState>) -> Result<TimeLog, TimeLogError> {
+    let now = Utc::now().naive_utc();
+    state
+        .write(move |conn| TimeLogRepository::stop_timer(conn, task_id, now))
+        .await?
+}
+
+#[tauri::command]
+pub async fn log_time(
+    task_id: u32,
+    started_at: String,
+    minutes: u32,
+    note: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TimeLog, TimeLogError> {
+    let started_at = parse_frontend_datetime(&started_at).map_err(TimeLogError::InvalidDate)?;
+
+    state
+        .write(move |conn| TimeLogRepository::log_time(conn, task_id, started_at, minutes, note))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_time_log(log_id: u32, state: State<'_, AppState>) -> Result<bool, TimeLogError> {
+    state
+        .write(move |conn| TimeLogRepository::delete(conn, log_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_time_logs(task_id: u32, state: State<'_, AppState>) -> Result<Vec<TimeLog>, TimeLogError> {
+    state
+        .read(move |conn| TimeLogRepository::list_for_task(conn, task_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_effort_report(user_id: u32, state: State<'_, AppState>) -> Result<EffortReport, TimeLogError> {
+    state
+        .read(move |conn| TimeLogRepository::report(conn, user_id))
+        .await?
+}
@@ -0,0 +1,172 @@
+use chrono::{Duration, Local, Weekday};
+use tauri::State;
+
+use crate::{
+    errors::TimetableError,
+    models::{ClassSlot, ScheduledClass, Task, Term, TermException, TimetableDay},
+    repository::{TimetableRepository, UnitOfWork},
+    utils::{local_to_utc, parse_frontend_date, parse_frontend_time},
+    AppState,
+};
+
+/// How far ahead `generate_class_prep_tasks` looks by default.
+const DEFAULT_PREP_DAYS: u32 = 7;
+/// Default time between a prep task's due date and its class.
+const DEFAULT_PREP_LEAD_MINUTES: u32 = 12 * 60;
+
+#[tauri::command]
+pub async fn create_term(
+    user_id: u32,
+    name: String,
+    start_date: String,
+    end_date: String,
+    state: State<'_, AppState>,
+) -> Result<Term, TimetableError> {
+    let start_date = parse_frontend_date(&start_date).map_err(TimetableError::InvalidDate)?;
+    let end_date = parse_frontend_date(&end_date).map_err(TimetableError::InvalidDate)?;
+    let mut term = Term::new(user_id, name.trim().to_string(), start_date, end_date)?;
+
+    state
+        .write(move |conn| TimetableRepository::create_term(conn, &mut term).map(|_| term))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_terms(user_id: u32, state: State<'_, AppState>) -> Result<Vec<Term>, TimetableError> {
+    state
+        .read(move |conn| TimetableRepository::list_terms(conn, user_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_term(term_id: u32, state: State<'_, AppState>) -> Result<bool, TimetableError> {
+    state
+        .write(move |conn| TimetableRepository::delete_term(conn, term_id))
+        .await?
+}
+
+/// Adds a weekly class; `weekday` is an English day name such as "mon".
+#[tauri::command]
+pub async fn add_class(
+    term_id: u32,
+    subject_id: u32,
+    weekday: String,
+    start_time: String,
+    end_time: String,
+    room: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ClassSlot, TimetableError> {
+    let weekday = weekday
+        .parse::<Weekday>()
+        .map_err(|_| TimetableError::InvalidDate(format!("Invalid weekday: {}", weekday)))?;
+    let start_time = parse_frontend_time(&start_time).map_err(TimetableError::InvalidDate)?;
+    let end_time = parse_frontend_time(&end_time).map_err(TimetableError::InvalidDate)?;
+    let mut class = ClassSlot::new(term_id, subject_id, weekday, start_time, end_time, room)?;
+
+    state
+        .write(move |conn| TimetableRepository::add_class(conn, &mut class).map(|_| class))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_classes(term_id: u32, state: State<'_, AppState>) -> Result<Vec<ClassSlot>, TimetableError> {
+    state
+        .read(move |conn| TimetableRepository::list_classes(conn, term_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_class(class_id: u32, state: State<'_, AppState>) -> Result<bool, TimetableError> {
+    state
+        .write(move |conn| TimetableRepository::delete_class(conn, class_id))
+        .await?
+}
+
+/// Adds a holiday for the whole term, or cancels one class when `class_id`
+/// is given.
+#[tauri::command]
+pub async fn add_term_exception(
+    term_id: u32,
+    class_id: Option<u32>,
+    start_date: String,
+    end_date: Option<String>,
+    description: String,
+    state: State<'_, AppState>,
+) -> Result<TermException, TimetableError> {
+    let start_date = parse_frontend_date(&start_date).map_err(TimetableError::InvalidDate)?;
+    let end_date = match end_date {
+        Some(end_date) => parse_frontend_date(&end_date).map_err(TimetableError::InvalidDate)?,
+        None => start_date,
+    };
+    let mut exception = TermException::new(term_id, class_id, start_date, end_date, description)?;
+
+    state
+        .write(move |conn| TimetableRepository::add_exception(conn, &mut exception).map(|_| exception))
+        .await?
+}
+
+#[tauri::command]
+pub async fn list_term_exceptions(
+    term_id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<TermException>, TimetableError> {
+    state
+        .read(move |conn| TimetableRepository::list_exceptions(conn, term_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_term_exception(exception_id: u32, state: State<'_, AppState>) -> Result<bool, TimetableError> {
+    state
+        .write(move |conn| TimetableRepository::delete_exception(conn, exception_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn get_todays_classes(
+    user_id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<ScheduledClass>, TimetableError> {
+    let today = Local::now().date_naive();
+    state
+        .read(move |conn| TimetableRepository::classes_between(conn, user_id, today, today))
+        .await?
+}
+
+/// The timetable for the week containing `date` (default: this week).
+#[tauri::command]
+pub async fn get_week_grid(
+    user_id: u32,
+    date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TimetableDay>, TimetableError> {
+    let date = match date {
+        Some(date) => parse_frontend_date(&date).map_err(TimetableError::InvalidDate)?,
+        None => Local::now().date_naive(),
+    };
+    state
+        .read(move |conn| TimetableRepository::week_grid(conn, user_id, date))
+        .await?
+}
+
+/// Creates prep tasks for classes in the next `days` days (default a week),
+/// due `lead_minutes` before each class (default 12 hours).
+#[tauri::command]
+pub async fn generate_class_prep_tasks(
+    user_id: u32,
+    days: Option<u32>,
+    lead_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Task>, TimetableError> {
+    let today = Local::now().date_naive();
+    let to = today + Duration::days(days.unwrap_or(DEFAULT_PREP_DAYS) as i64);
+    let lead = Duration::minutes(lead_minutes.unwrap_or(DEFAULT_PREP_LEAD_MINUTES) as i64);
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                TimetableRepository::generate_prep_tasks(uow, user_id, today, to, lead, local_to_utc)
+            })
+        })
+        .await?
+}
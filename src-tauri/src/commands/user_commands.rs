@@ -1,33 +1,28 @@
 use tauri::State;
 use crate::{
     models::user::User,
-    repository::UserRepository,
+    repository::{SqliteStore, UserStore},
     errors::command_errors::CommandError,
     AppState,
 };
 
 #[tauri::command]
-pub fn create_user(name: String, state: State<AppState>) -> Result<String, CommandError> {
+pub async fn create_user(name: String, state: State<'_, AppState>) -> Result<String, CommandError> {
     let mut user = User::new(name)?;
-    
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
 
-    UserRepository::create(&conn, &mut user)?;
+    let user = state
+        .write(move |conn| SqliteStore::new(conn).create_user(&mut user).map(|_| user))
+        .await??;
 
     Ok(format!("Usuário {} criado com sucesso", user.name))
 }
 
 #[tauri::command]
-pub fn get_active_users_count(state: State<AppState>) -> Result<u32, CommandError> { 
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    Ok(UserRepository::count_active(&conn)?)
+pub async fn get_active_users_count(state: State<'_, AppState>) -> Result<u32, CommandError> { 
+    Ok(state.read(|conn| SqliteStore::new(conn).count_active_users()).await??)
 }
 
 #[tauri::command]
-pub fn get_active_user_id(state: State<AppState>) -> Result<Option<u32>, CommandError> {
-    let db_conn = state.db_conn();
-    let conn = db_conn.lock().map_err(|_| CommandError::LockFailed)?;
-    Ok(UserRepository::find_active_id(&conn)?)
-}
\ No newline at end of file
+pub async fn get_active_user_id(state: State<'_, AppState>) -> Result<Option<u32>, CommandError> {
+    Ok(state.read(|conn| SqliteStore::new(conn).find_active_user_id()).await??)
+}
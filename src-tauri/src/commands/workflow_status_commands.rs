@@ -0,0 +1,96 @@
+use tauri::State;
+
+use crate::{
+    errors::WorkflowStatusError,
+    models::{task_status::TaskStatus, StatusCategory, WorkflowStatus},
+    repository::{UnitOfWork, WorkflowStatusRepository},
+    AppState,
+};
+
+fn parse_status(key: &str) -> Result<TaskStatus, WorkflowStatusError> {
+    TaskStatus::from_key(key).map_err(|_| WorkflowStatusError::NotFound(key.to_string()))
+}
+
+#[tauri::command]
+pub async fn list_workflow_statuses(
+    user_id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>, WorkflowStatusError> {
+    state
+        .read(move |conn| WorkflowStatusRepository::list(conn, user_id))
+        .await?
+}
+
+#[tauri::command]
+pub async fn create_workflow_status(
+    user_id: u32,
+    name: String,
+    category: String,
+    color: String,
+    state: State<'_, AppState>,
+) -> Result<WorkflowStatus, WorkflowStatusError> {
+    let category = category.parse::<StatusCategory>()?;
+    let mut status = WorkflowStatus::new(user_id, name, category, color)?;
+
+    state
+        .write(move |conn| WorkflowStatusRepository::create(conn, &mut status).map(|_| status))
+        .await?
+}
+
+#[tauri::command]
+pub async fn update_workflow_status(
+    user_id: u32,
+    status: String,
+    name: Option<String>,
+    color: Option<String>,
+    category: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WorkflowStatus, WorkflowStatusError> {
+    let status = parse_status(&status)?;
+    let category = category.map(|c| c.parse::<StatusCategory>()).transpose()?;
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                WorkflowStatusRepository::update(uow, user_id, &status, name, color, category)
+            })
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn reorder_workflow_statuses(
+    user_id: u32,
+    order: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorkflowStatus>, WorkflowStatusError> {
+    let order = order
+        .iter()
+        .map(|key| parse_status(key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| WorkflowStatusRepository::reorder(uow, user_id, &order))
+        })
+        .await?
+}
+
+#[tauri::command]
+pub async fn delete_workflow_status(
+    user_id: u32,
+    status: String,
+    move_tasks_to: String,
+    state: State<'_, AppState>,
+) -> Result<u32, WorkflowStatusError> {
+    let status = parse_status(&status)?;
+    let move_tasks_to = parse_status(&move_tasks_to)?;
+
+    state
+        .write(move |conn| {
+            UnitOfWork::run(conn, |uow| {
+                WorkflowStatusRepository::delete(uow, user_id, &status, &move_tasks_to)
+            })
+        })
+        .await?
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum AgendaError {
+    InvalidRange(String),
+    InvalidSpan(String),
+    InvalidDate(String),
+    DatabaseError(String),
+}
+
+impl fmt::Display for AgendaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgendaError::InvalidRange(msg) => write!(f, "Invalid range: {}", msg),
+            AgendaError::InvalidSpan(value) => write!(f, "Invalid span: {}", value),
+            AgendaError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            AgendaError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AgendaError {}
+
+impl From<rusqlite::Error> for AgendaError {
+    fn from(value: rusqlite::Error) -> Self {
+        AgendaError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for AgendaError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        AgendaError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SettingsError> for AgendaError {
+    fn from(e: crate::errors::SettingsError) -> Self {
+        AgendaError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AgendaError::InvalidSpan("year".to_string()).to_string(), "Invalid span: year");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: AgendaError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, AgendaError::DatabaseError(_)));
+    }
+}
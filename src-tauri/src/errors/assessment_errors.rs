@@ -0,0 +1,74 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum AssessmentError {
+    InvalidTitle(String),
+    InvalidWeight(String),
+    InvalidScore(String),
+    InvalidGrade(String),
+    InvalidScheme(String),
+    InvalidDate(String),
+    NotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for AssessmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssessmentError::InvalidTitle(msg) => write!(f, "Invalid assessment title: {}", msg),
+            AssessmentError::InvalidWeight(msg) => write!(f, "Invalid assessment weight: {}", msg),
+            AssessmentError::InvalidScore(msg) => write!(f, "Invalid score: {}", msg),
+            AssessmentError::InvalidGrade(msg) => write!(f, "Invalid grade: {}", msg),
+            AssessmentError::InvalidScheme(msg) => write!(f, "Invalid grading scheme: {}", msg),
+            AssessmentError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            AssessmentError::NotFound(id) => write!(f, "Assessment {} not found", id),
+            AssessmentError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            AssessmentError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AssessmentError {}
+
+impl From<rusqlite::Error> for AssessmentError {
+    fn from(value: rusqlite::Error) -> Self {
+        AssessmentError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for AssessmentError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        AssessmentError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SubjectError> for AssessmentError {
+    fn from(e: crate::errors::SubjectError) -> Self {
+        match e {
+            crate::errors::SubjectError::NotFound(id) => AssessmentError::SubjectNotFound(id),
+            e => AssessmentError::DatabaseError(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AssessmentError::NotFound(5).to_string(), "Assessment 5 not found");
+        assert_eq!(
+            AssessmentError::InvalidGrade("Z".to_string()).to_string(),
+            "Invalid grade: Z"
+        );
+    }
+
+    #[test]
+    fn test_from_subject_error() {
+        let error: AssessmentError = crate::errors::SubjectError::NotFound(2).into();
+        assert!(matches!(error, AssessmentError::SubjectNotFound(2)));
+    }
+}
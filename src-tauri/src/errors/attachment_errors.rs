@@ -0,0 +1,60 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum AttachmentError {
+    InvalidAttachment(String),
+    OwnerNotFound(String),
+    NotFound(u32),
+    FileError(String),
+    DatabaseError(String),
+}
+
+impl fmt::Display for AttachmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttachmentError::InvalidAttachment(msg) => write!(f, "Invalid attachment: {}", msg),
+            AttachmentError::OwnerNotFound(owner) => write!(f, "{} not found", owner),
+            AttachmentError::NotFound(id) => write!(f, "Attachment {} not found", id),
+            AttachmentError::FileError(msg) => write!(f, "File error: {}", msg),
+            AttachmentError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AttachmentError {}
+
+impl From<rusqlite::Error> for AttachmentError {
+    fn from(value: rusqlite::Error) -> Self {
+        AttachmentError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<std::io::Error> for AttachmentError {
+    fn from(value: std::io::Error) -> Self {
+        AttachmentError::FileError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for AttachmentError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        AttachmentError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AttachmentError::NotFound(3).to_string(), "Attachment 3 not found");
+        assert_eq!(AttachmentError::OwnerNotFound("Task 4".to_string()).to_string(), "Task 4 not found");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: AttachmentError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, AttachmentError::DatabaseError(_)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::errors::{UserError, TagError};
+use crate::errors::{UserError, TagError, PoolError};
 
 
 #[derive(Debug, serde::Serialize)]
@@ -26,6 +26,15 @@ impl From<TagError> for CommandError {
     }
 }
 
+impl From<PoolError> for CommandError {
+    fn from(e: PoolError) -> Self {
+        match e {
+            PoolError::LockFailed => CommandError::LockFailed,
+            _ => CommandError::Database(e.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,5 +52,17 @@ mod tests {
             panic!("Esperado CommandError::Validation");
         }
     }
-    
+
+    #[test]
+    fn test_pool_error_to_command_error() {
+        let command_error: CommandError = PoolError::LockFailed.into();
+        assert!(matches!(command_error, CommandError::LockFailed));
+
+        let command_error: CommandError = PoolError::WorkerFailed("cancelled".to_string()).into();
+        if let CommandError::Database(msg) = command_error {
+            assert_eq!(msg, "Database worker failed: cancelled");
+        } else {
+            panic!("Esperado CommandError::Database");
+        }
+    }
 }
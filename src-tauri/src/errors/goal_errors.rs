@@ -0,0 +1,63 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum GoalError {
+    InvalidGoal(String),
+    InvalidDate(String),
+    NotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for GoalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalError::InvalidGoal(msg) => write!(f, "Invalid goal: {}", msg),
+            GoalError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            GoalError::NotFound(id) => write!(f, "Goal {} not found", id),
+            GoalError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            GoalError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GoalError {}
+
+impl From<rusqlite::Error> for GoalError {
+    fn from(value: rusqlite::Error) -> Self {
+        GoalError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for GoalError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        GoalError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SettingsError> for GoalError {
+    fn from(e: crate::errors::SettingsError) -> Self {
+        GoalError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GoalError::NotFound(3).to_string(), "Goal 3 not found");
+        assert_eq!(
+            GoalError::InvalidGoal("Goal target must be positive".to_string()).to_string(),
+            "Invalid goal: Goal target must be positive"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: GoalError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, GoalError::DatabaseError(_)));
+    }
+}
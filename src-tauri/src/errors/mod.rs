@@ -5,4 +5,38 @@ pub use tag_errors::*;
 pub mod command_errors;
 pub use command_errors::*;
 pub mod task_errors;
-pub use task_errors::*;
\ No newline at end of file
+pub use task_errors::*;
+pub mod pool_errors;
+pub use pool_errors::*;
+pub mod workflow_status_errors;
+pub use workflow_status_errors::*;
+pub mod time_log_errors;
+pub use time_log_errors::*;
+pub mod reminder_errors;
+pub use reminder_errors::*;
+pub mod subject_errors;
+pub use subject_errors::*;
+pub mod timetable_errors;
+pub use timetable_errors::*;
+pub mod assessment_errors;
+pub use assessment_errors::*;
+pub mod study_plan_errors;
+pub use study_plan_errors::*;
+pub mod note_errors;
+pub use note_errors::*;
+pub mod attachment_errors;
+pub use attachment_errors::*;
+pub mod reading_errors;
+pub use reading_errors::*;
+pub mod quiz_errors;
+pub use quiz_errors::*;
+pub mod settings_errors;
+pub use settings_errors::*;
+pub mod goal_errors;
+pub use goal_errors::*;
+pub mod statistics_errors;
+pub use statistics_errors::*;
+pub mod agenda_errors;
+pub use agenda_errors::*;
+pub mod reschedule_errors;
+pub use reschedule_errors::*;
\ No newline at end of file
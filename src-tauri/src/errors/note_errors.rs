@@ -0,0 +1,57 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum NoteError {
+    InvalidNote(String),
+    DuplicateTitle(String),
+    NotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for NoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoteError::InvalidNote(msg) => write!(f, "Invalid note: {}", msg),
+            NoteError::DuplicateTitle(title) => write!(f, "Note '{}' already exists", title),
+            NoteError::NotFound(id) => write!(f, "Note {} not found", id),
+            NoteError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            NoteError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for NoteError {}
+
+impl From<rusqlite::Error> for NoteError {
+    fn from(value: rusqlite::Error) -> Self {
+        NoteError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for NoteError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        NoteError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoteError::NotFound(3).to_string(), "Note 3 not found");
+        assert_eq!(
+            NoteError::DuplicateTitle("Optics".to_string()).to_string(),
+            "Note 'Optics' already exists"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: NoteError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, NoteError::DatabaseError(_)));
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum PoolError {
+    LockFailed,
+    ConnectionFailed(String),
+    WorkerFailed(String),
+}
+
+impl std::error::Error for PoolError {}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::LockFailed => write!(f, "Database lock failed"),
+            PoolError::ConnectionFailed(msg) => write!(f, "Database connection failed: {}", msg),
+            PoolError::WorkerFailed(msg) => write!(f, "Database worker failed: {}", msg),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for PoolError {
+    fn from(value: rusqlite::Error) -> Self {
+        PoolError::ConnectionFailed(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_error_display() {
+        assert_eq!(PoolError::LockFailed.to_string(), "Database lock failed");
+        assert_eq!(
+            PoolError::ConnectionFailed("disk I/O error".into()).to_string(),
+            "Database connection failed: disk I/O error"
+        );
+        assert_eq!(
+            PoolError::WorkerFailed("task cancelled".into()).to_string(),
+            "Database worker failed: task cancelled"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let pool_error: PoolError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(pool_error, PoolError::ConnectionFailed(_)));
+    }
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum QuizError {
+    InvalidQuestion(String),
+    InvalidQuiz(String),
+    NotFound(u32),
+    AttemptNotFound(u32),
+    AlreadySubmitted(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for QuizError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuizError::InvalidQuestion(msg) => write!(f, "Invalid question: {}", msg),
+            QuizError::InvalidQuiz(msg) => write!(f, "Invalid quiz: {}", msg),
+            QuizError::NotFound(id) => write!(f, "Question {} not found", id),
+            QuizError::AttemptNotFound(id) => write!(f, "Quiz {} not found", id),
+            QuizError::AlreadySubmitted(id) => write!(f, "Quiz {} was already submitted", id),
+            QuizError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for QuizError {}
+
+impl From<rusqlite::Error> for QuizError {
+    fn from(value: rusqlite::Error) -> Self {
+        QuizError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for QuizError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        QuizError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(QuizError::NotFound(3).to_string(), "Question 3 not found");
+        assert_eq!(QuizError::AlreadySubmitted(4).to_string(), "Quiz 4 was already submitted");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: QuizError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, QuizError::DatabaseError(_)));
+    }
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum ReadingError {
+    InvalidBook(String),
+    InvalidSession(String),
+    InvalidDate(String),
+    NotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for ReadingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadingError::InvalidBook(msg) => write!(f, "Invalid book: {}", msg),
+            ReadingError::InvalidSession(msg) => write!(f, "Invalid reading session: {}", msg),
+            ReadingError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            ReadingError::NotFound(id) => write!(f, "Book {} not found", id),
+            ReadingError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            ReadingError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ReadingError {}
+
+impl From<rusqlite::Error> for ReadingError {
+    fn from(value: rusqlite::Error) -> Self {
+        ReadingError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for ReadingError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        ReadingError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ReadingError::NotFound(3).to_string(), "Book 3 not found");
+        assert_eq!(
+            ReadingError::InvalidSession("empty".to_string()).to_string(),
+            "Invalid reading session: empty"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: ReadingError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, ReadingError::DatabaseError(_)));
+    }
+}
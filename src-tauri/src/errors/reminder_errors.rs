@@ -0,0 +1,60 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum ReminderError {
+    InvalidRule(String),
+    InvalidDate(String),
+    NotFound(u32),
+    TaskNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for ReminderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReminderError::InvalidRule(msg) => write!(f, "Invalid reminder: {}", msg),
+            ReminderError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            ReminderError::NotFound(id) => write!(f, "Reminder {} not found", id),
+            ReminderError::TaskNotFound(id) => write!(f, "Task {} not found", id),
+            ReminderError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ReminderError {}
+
+impl From<rusqlite::Error> for ReminderError {
+    fn from(value: rusqlite::Error) -> Self {
+        ReminderError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for ReminderError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        ReminderError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SettingsError> for ReminderError {
+    fn from(e: crate::errors::SettingsError) -> Self {
+        ReminderError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_error() {
+        assert_eq!(ReminderError::NotFound(4).to_string(), "Reminder 4 not found");
+        assert_eq!(ReminderError::TaskNotFound(2).to_string(), "Task 2 not found");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: ReminderError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, ReminderError::DatabaseError(_)));
+    }
+}
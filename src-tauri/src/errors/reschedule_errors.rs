@@ -0,0 +1,69 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::errors::TaskError;
+
+#[derive(Debug, Serialize)]
+pub enum RescheduleError {
+    InvalidOptions(String),
+    InvalidDate(String),
+    /// The task's due date changed after the preview was made.
+    StaleChange(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for RescheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RescheduleError::InvalidOptions(msg) => write!(f, "Invalid options: {}", msg),
+            RescheduleError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            RescheduleError::StaleChange(id) => write!(f, "Task {} changed since the preview", id),
+            RescheduleError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RescheduleError {}
+
+impl From<rusqlite::Error> for RescheduleError {
+    fn from(value: rusqlite::Error) -> Self {
+        RescheduleError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for RescheduleError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        RescheduleError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SettingsError> for RescheduleError {
+    fn from(e: crate::errors::SettingsError) -> Self {
+        RescheduleError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<TaskError> for RescheduleError {
+    fn from(e: TaskError) -> Self {
+        match e {
+            TaskError::InvalidDate(msg) => RescheduleError::InvalidDate(msg),
+            other => RescheduleError::DatabaseError(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(RescheduleError::StaleChange(4).to_string(), "Task 4 changed since the preview");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: RescheduleError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, RescheduleError::DatabaseError(_)));
+    }
+}
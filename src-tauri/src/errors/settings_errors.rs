@@ -0,0 +1,50 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum SettingsError {
+    InvalidSettings(String),
+    DatabaseError(String),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::InvalidSettings(msg) => write!(f, "Invalid settings: {}", msg),
+            SettingsError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl From<rusqlite::Error> for SettingsError {
+    fn from(value: rusqlite::Error) -> Self {
+        SettingsError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for SettingsError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        SettingsError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            SettingsError::InvalidSettings("bad offset".to_string()).to_string(),
+            "Invalid settings: bad offset"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: SettingsError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, SettingsError::DatabaseError(_)));
+    }
+}
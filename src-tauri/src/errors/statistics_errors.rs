@@ -0,0 +1,60 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum StatisticsError {
+    InvalidRange(String),
+    InvalidGrouping(String),
+    InvalidDate(String),
+    DatabaseError(String),
+}
+
+impl fmt::Display for StatisticsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatisticsError::InvalidRange(msg) => write!(f, "Invalid range: {}", msg),
+            StatisticsError::InvalidGrouping(value) => write!(f, "Invalid grouping: {}", value),
+            StatisticsError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            StatisticsError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StatisticsError {}
+
+impl From<rusqlite::Error> for StatisticsError {
+    fn from(value: rusqlite::Error) -> Self {
+        StatisticsError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for StatisticsError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        StatisticsError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<crate::errors::SettingsError> for StatisticsError {
+    fn from(e: crate::errors::SettingsError) -> Self {
+        StatisticsError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            StatisticsError::InvalidGrouping("month".to_string()).to_string(),
+            "Invalid grouping: month"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: StatisticsError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, StatisticsError::DatabaseError(_)));
+    }
+}
@@ -0,0 +1,55 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum StudyPlanError {
+    InvalidPlan(String),
+    InvalidTopic(String),
+    InvalidDate(String),
+    NotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for StudyPlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StudyPlanError::InvalidPlan(msg) => write!(f, "Invalid study plan: {}", msg),
+            StudyPlanError::InvalidTopic(msg) => write!(f, "Invalid topic: {}", msg),
+            StudyPlanError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            StudyPlanError::NotFound(id) => write!(f, "Study plan {} not found", id),
+            StudyPlanError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            StudyPlanError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StudyPlanError {}
+
+impl From<rusqlite::Error> for StudyPlanError {
+    fn from(value: rusqlite::Error) -> Self {
+        StudyPlanError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for StudyPlanError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        StudyPlanError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(StudyPlanError::NotFound(7).to_string(), "Study plan 7 not found");
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: StudyPlanError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, StudyPlanError::DatabaseError(_)));
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum SubjectError {
+    InvalidName(String),
+    InvalidColor(String),
+    InvalidGoal(String),
+    DuplicateName(String),
+    NotFound(u32),
+    TaskNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for SubjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubjectError::InvalidName(msg) => write!(f, "Invalid subject name: {}", msg),
+            SubjectError::InvalidColor(msg) => write!(f, "Invalid subject color: {}", msg),
+            SubjectError::InvalidGoal(msg) => write!(f, "Invalid subject goal: {}", msg),
+            SubjectError::DuplicateName(name) => write!(f, "Subject '{}' already exists", name),
+            SubjectError::NotFound(id) => write!(f, "Subject {} not found", id),
+            SubjectError::TaskNotFound(id) => write!(f, "Task {} not found", id),
+            SubjectError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SubjectError {}
+
+impl From<rusqlite::Error> for SubjectError {
+    fn from(value: rusqlite::Error) -> Self {
+        SubjectError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for SubjectError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        SubjectError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(SubjectError::NotFound(3).to_string(), "Subject 3 not found");
+        assert_eq!(
+            SubjectError::DuplicateName("Physics".to_string()).to_string(),
+            "Subject 'Physics' already exists"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: SubjectError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, SubjectError::DatabaseError(_)));
+    }
+}
@@ -10,6 +10,12 @@ pub enum TagError {
 
 impl std::error::Error for TagError {}
 
+impl From<rusqlite::Error> for TagError {
+    fn from(value: rusqlite::Error) -> Self {
+        TagError::DatabaseError(value.to_string())
+    }
+}
+
 impl fmt::Display for TagError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -54,6 +60,12 @@ mod tests {
         assert!(matches!(error, TagError::DatabaseError(_)));
     }
 
+    #[test]
+    fn test_from_rusqlite_error() {
+        let tag_error: TagError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(tag_error, TagError::DatabaseError(_)));
+    }
+
     #[test]
     fn test_tag_error_display() {
         assert_eq!(
@@ -9,6 +9,9 @@ pub enum TaskError {
     InvalidDate(String),
     DatabaseError(String),
     InvalidTag(String),
+    WipLimitReached(String),
+    InvalidEstimate(String),
+    InvalidKind(String),
 }
 
 impl fmt::Display for TaskError {
@@ -20,12 +23,27 @@ impl fmt::Display for TaskError {
             TaskError::InvalidDate(msg) => write!(f, "Invalid task date: {}", msg),
             TaskError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             TaskError::InvalidTag(msg) => write!(f, "Invalid tag: {}", msg),
+            TaskError::WipLimitReached(msg) => write!(f, "WIP limit reached: {}", msg),
+            TaskError::InvalidEstimate(msg) => write!(f, "Invalid task estimate: {}", msg),
+            TaskError::InvalidKind(msg) => write!(f, "Invalid task kind: {}", msg),
         }
     }
 }
 
 impl std::error::Error for TaskError {}
 
+impl From<rusqlite::Error> for TaskError {
+    fn from(value: rusqlite::Error) -> Self {
+        TaskError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for TaskError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        TaskError::DatabaseError(e.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +98,22 @@ mod tests {
         assert!(matches!(error, TaskError::DatabaseError(_)));
     }
 
+    #[test]
+    fn test_wip_limit_reached_error() {
+        let error = TaskError::WipLimitReached("column 'in_progress' already has 3 of 3 tasks".to_string());
+        assert_eq!(
+            error.to_string(),
+            "WIP limit reached: column 'in_progress' already has 3 of 3 tasks"
+        );
+        assert!(matches!(error, TaskError::WipLimitReached(_)));
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let task_error: TaskError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(task_error, TaskError::DatabaseError(_)));
+    }
+
     #[test]
     fn test_debug_output() {
         let error = TaskError::InvalidName("Test error".to_string());
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum TimeLogError {
+    InvalidDuration(String),
+    InvalidDate(String),
+    TaskNotFound(u32),
+    TimerRunning(u32),
+    NoTimerRunning(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for TimeLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeLogError::InvalidDuration(msg) => write!(f, "Invalid duration: {}", msg),
+            TimeLogError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            TimeLogError::TaskNotFound(id) => write!(f, "Task {} not found", id),
+            TimeLogError::TimerRunning(id) => write!(f, "A timer is already running for task {}", id),
+            TimeLogError::NoTimerRunning(id) => write!(f, "No timer is running for task {}", id),
+            TimeLogError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TimeLogError {}
+
+impl From<rusqlite::Error> for TimeLogError {
+    fn from(value: rusqlite::Error) -> Self {
+        TimeLogError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for TimeLogError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        TimeLogError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_errors() {
+        assert_eq!(
+            TimeLogError::TimerRunning(3).to_string(),
+            "A timer is already running for task 3"
+        );
+        assert_eq!(
+            TimeLogError::NoTimerRunning(3).to_string(),
+            "No timer is running for task 3"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: TimeLogError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, TimeLogError::DatabaseError(_)));
+    }
+}
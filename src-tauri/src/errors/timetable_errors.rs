@@ -0,0 +1,63 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum TimetableError {
+    InvalidName(String),
+    InvalidDate(String),
+    InvalidRange(String),
+    TermNotFound(u32),
+    ClassNotFound(u32),
+    ExceptionNotFound(u32),
+    SubjectNotFound(u32),
+    DatabaseError(String),
+}
+
+impl fmt::Display for TimetableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimetableError::InvalidName(msg) => write!(f, "Invalid name: {}", msg),
+            TimetableError::InvalidDate(msg) => write!(f, "Invalid date: {}", msg),
+            TimetableError::InvalidRange(msg) => write!(f, "Invalid range: {}", msg),
+            TimetableError::TermNotFound(id) => write!(f, "Term {} not found", id),
+            TimetableError::ClassNotFound(id) => write!(f, "Class {} not found", id),
+            TimetableError::ExceptionNotFound(id) => write!(f, "Exception {} not found", id),
+            TimetableError::SubjectNotFound(id) => write!(f, "Subject {} not found", id),
+            TimetableError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TimetableError {}
+
+impl From<rusqlite::Error> for TimetableError {
+    fn from(value: rusqlite::Error) -> Self {
+        TimetableError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for TimetableError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        TimetableError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TimetableError::TermNotFound(2).to_string(), "Term 2 not found");
+        assert_eq!(
+            TimetableError::InvalidRange("ends before it starts".to_string()).to_string(),
+            "Invalid range: ends before it starts"
+        );
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: TimetableError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, TimetableError::DatabaseError(_)));
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub enum WorkflowStatusError {
+    InvalidName(String),
+    InvalidColor(String),
+    InvalidCategory(String),
+    NotFound(String),
+    /// The change would leave a category without any status.
+    LastInCategory(String),
+    DatabaseError(String),
+}
+
+impl fmt::Display for WorkflowStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkflowStatusError::InvalidName(msg) => write!(f, "Invalid status name: {}", msg),
+            WorkflowStatusError::InvalidColor(msg) => write!(f, "Invalid status color: {}", msg),
+            WorkflowStatusError::InvalidCategory(msg) => write!(f, "Invalid status category: {}", msg),
+            WorkflowStatusError::NotFound(msg) => write!(f, "Status not found: {}", msg),
+            WorkflowStatusError::LastInCategory(msg) => {
+                write!(f, "Category needs at least one status: {}", msg)
+            }
+            WorkflowStatusError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for WorkflowStatusError {}
+
+impl From<rusqlite::Error> for WorkflowStatusError {
+    fn from(value: rusqlite::Error) -> Self {
+        WorkflowStatusError::DatabaseError(value.to_string())
+    }
+}
+
+impl From<crate::errors::PoolError> for WorkflowStatusError {
+    fn from(e: crate::errors::PoolError) -> Self {
+        WorkflowStatusError::DatabaseError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_error() {
+        let error = WorkflowStatusError::NotFound("reading".to_string());
+        assert_eq!(error.to_string(), "Status not found: reading");
+    }
+
+    #[test]
+    fn test_last_in_category_error() {
+        let error = WorkflowStatusError::LastInCategory("completed".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Category needs at least one status: completed"
+        );
+        assert!(matches!(error, WorkflowStatusError::LastInCategory(_)));
+    }
+
+    #[test]
+    fn test_from_rusqlite_error() {
+        let error: WorkflowStatusError = rusqlite::Error::InvalidQuery.into();
+        assert!(matches!(error, WorkflowStatusError::DatabaseError(_)));
+    }
+}
@@ -1,9 +1,13 @@
-use rusqlite::{Connection, Result};
-use std::sync::{Arc, Mutex};
+use rusqlite::Connection;
+use std::sync::Arc;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
 
-use crate::utils::initialize_database::initialize_database;
+use crate::errors::PoolError;
+use crate::utils::db_pool::{DbPool, DEFAULT_READERS};
+use crate::utils::reminder_scheduler::{ReminderScheduler, TauriNotifier, POLL_INTERVAL};
+use crate::utils::attachment_store::{AttachmentStore, ATTACHMENTS_DIR};
 
 pub mod models;
 pub mod errors;
@@ -12,7 +16,8 @@ pub mod commands;
 pub mod utils;
 
 pub struct AppState {
-    pub db_conn: Arc<Mutex<Connection>>
+    pub pool: Arc<DbPool>,
+    pub attachments: AttachmentStore,
 }
 
 impl AppState {
@@ -23,17 +28,47 @@ impl AppState {
         fs::create_dir_all(parent).map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
       }
 
-      let conn = Connection::open(db_path)?;
-
-      initialize_database(&conn)?;
+      let pool = DbPool::open(&db_path, DEFAULT_READERS)?;
+      let attachments = AttachmentStore::new(db_path.parent().unwrap_or(Path::new(".")).join(ATTACHMENTS_DIR));
 
       Ok(Self {
-        db_conn: Arc::new(Mutex::new(conn)),
+        pool: Arc::new(pool),
+        attachments,
       })
     }
 
-    pub fn db_conn(&self) -> Arc<Mutex<Connection>> {
-      Arc::clone(&self.db_conn)
+    pub fn pool(&self) -> Arc<DbPool> {
+      Arc::clone(&self.pool)
+    }
+
+    /// Runs `f` on a pooled read-only connection off the main thread.
+    pub async fn read<F, T>(&self, f: F) -> Result<T, PoolError>
+    where
+      F: FnOnce(&Connection) -> T + Send + 'static,
+      T: Send + 'static,
+    {
+      let pool = self.pool();
+      tauri::async_runtime::spawn_blocking(move || {
+        let conn = pool.reader()?;
+        Ok(f(&conn))
+      })
+      .await
+      .map_err(|e| PoolError::WorkerFailed(e.to_string()))?
+    }
+
+    /// Runs `f` on the single writer connection off the main thread.
+    pub async fn write<F, T>(&self, f: F) -> Result<T, PoolError>
+    where
+      F: FnOnce(&mut Connection) -> T + Send + 'static,
+      T: Send + 'static,
+    {
+      let pool = self.pool();
+      tauri::async_runtime::spawn_blocking(move || {
+        let mut conn = pool.writer()?;
+        Ok(f(&mut conn))
+      })
+      .await
+      .map_err(|e| PoolError::WorkerFailed(e.to_string()))?
     }
 
 }
@@ -46,10 +81,17 @@ pub fn run() {
 
     let app_state = AppState::new(db_path.to_str().unwrap())
         .expect("Failed to initialize the application state");
+    let pool = app_state.pool();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(app_state) 
+        .plugin(tauri_plugin_notification::init())
+        .manage(app_state)
+        .setup(move |app| {
+            let notifier = TauriNotifier(app.handle().clone());
+            app.manage(ReminderScheduler::spawn(pool, notifier, POLL_INTERVAL));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
           commands::create_user,
             commands::get_active_users_count,
@@ -58,12 +100,125 @@ pub fn run() {
             commands::get_tag_by_id,
             commands::list_tags,
             commands::create_task,
+            commands::preview_quick_add,
+            commands::quick_add_task,
             commands::update_task,
             commands::get_all_tasks,
             commands::delete_task,
             commands::update_tag,
             commands::delete_tag,
             commands::get_tasks_for_today,
+            commands::bulk_update_tasks,
+            commands::bulk_delete_tasks,
+            commands::undo_last_operation,
+            commands::move_task,
+            commands::get_board,
+            commands::set_wip_limit,
+            commands::get_tasks_by_quadrant,
+            commands::list_workflow_statuses,
+            commands::create_workflow_status,
+            commands::update_workflow_status,
+            commands::reorder_workflow_statuses,
+            commands::delete_workflow_status,
+            commands::set_task_estimate,
+            commands::start_timer,
+            commands::stop_timer,
+            commands::log_time,
+            commands::delete_time_log,
+            commands::list_time_logs,
+            commands::get_effort_report,
+            commands::add_reminder,
+            commands::list_reminders,
+            commands::delete_reminder,
+            commands::snooze_reminder,
+            commands::dismiss_reminder,
+            commands::create_subject,
+            commands::list_subjects,
+            commands::update_subject,
+            commands::delete_subject,
+            commands::set_task_subject,
+            commands::get_subject_dashboard,
+            commands::get_subject_dashboards,
+            commands::create_term,
+            commands::list_terms,
+            commands::delete_term,
+            commands::add_class,
+            commands::list_classes,
+            commands::delete_class,
+            commands::add_term_exception,
+            commands::list_term_exceptions,
+            commands::delete_term_exception,
+            commands::get_todays_classes,
+            commands::get_week_grid,
+            commands::generate_class_prep_tasks,
+            commands::add_assessment,
+            commands::update_assessment,
+            commands::record_assessment_score,
+            commands::delete_assessment,
+            commands::list_assessments,
+            commands::set_grading_scheme,
+            commands::get_grade_summary,
+            commands::create_study_plan,
+            commands::replan_study_plan,
+            commands::get_study_plan,
+            commands::list_study_plans,
+            commands::delete_study_plan,
+            commands::create_note,
+            commands::update_note,
+            commands::get_note,
+            commands::list_notes,
+            commands::delete_note,
+            commands::get_note_links,
+            commands::get_backlinks,
+            commands::get_task_notes,
+            commands::get_orphan_notes,
+            commands::search_notes,
+            commands::attach_file,
+            commands::list_task_attachments,
+            commands::list_note_attachments,
+            commands::get_attachment_path,
+            commands::delete_attachment,
+            commands::verify_attachments,
+            commands::collect_attachment_garbage,
+            commands::create_book,
+            commands::update_book,
+            commands::list_books,
+            commands::get_book_progress,
+            commands::delete_book,
+            commands::log_reading_session,
+            commands::list_reading_sessions,
+            commands::delete_reading_session,
+            commands::generate_reading_tasks,
+            commands::create_question,
+            commands::update_question,
+            commands::delete_question,
+            commands::list_questions,
+            commands::start_quiz,
+            commands::get_quiz,
+            commands::submit_quiz,
+            commands::get_quiz_result,
+            commands::get_quiz_history,
+            commands::get_weakest_topics,
+            commands::get_user_settings,
+            commands::update_user_settings,
+            commands::create_goal,
+            commands::update_goal,
+            commands::delete_goal,
+            commands::get_goal_progress,
+            commands::list_goals,
+            commands::freeze_day,
+            commands::unfreeze_day,
+            commands::list_freeze_days,
+            commands::get_statistics,
+            commands::get_overdue_tasks,
+            commands::get_completed_today,
+            commands::get_completed_late,
+            commands::set_task_dates,
+            commands::get_tasks_in_range,
+            commands::get_agenda,
+            commands::get_agenda_span,
+            commands::preview_reschedule,
+            commands::apply_reschedule,
           ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,189 @@
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::AgendaError;
+use crate::models::{Calendar, ScheduledClass, Task};
+
+/// The calendar views the agenda is usually asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgendaSpan {
+    Day,
+    Week,
+    Month,
+}
+
+impl AgendaSpan {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// First and last day of the span containing `date`; weeks begin on the
+    /// user's week start.
+    pub fn bounds(&self, date: NaiveDate, calendar: &Calendar) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::Day => (date, date),
+            Self::Week => {
+                let start = calendar.week_start_of(date);
+                (start, start + Duration::days(6))
+            }
+            Self::Month => {
+                let start = date.with_day(1).unwrap_or(date);
+                (start, start + Months::new(1) - Duration::days(1))
+            }
+        }
+    }
+}
+
+impl FromStr for AgendaSpan {
+    type Err = AgendaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            _ => Err(AgendaError::InvalidSpan(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for AgendaSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A task scheduled by a study plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgendaStudyBlock {
+    pub plan_id: u32,
+    pub plan_title: String,
+    pub minutes: u32,
+    pub task: Task,
+}
+
+/// Everything on one local day. Classes are in wall-clock time; tasks are
+/// placed by their due date in the user's time zone, all-day tasks first.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgendaDay {
+    pub date: NaiveDate,
+    /// Occurrences of the weekly timetable falling on this day.
+    pub classes: Vec<ScheduledClass>,
+    pub study_blocks: Vec<AgendaStudyBlock>,
+    pub tasks: Vec<Task>,
+}
+
+/// The days `from..=to`, including empty ones.
+#[derive(Debug, Clone, Serialize)]
+pub struct Agenda {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<AgendaDay>,
+}
+
+impl Agenda {
+    /// Groups `tasks` and `study_blocks` by their local due day. Classes
+    /// come expanded into their meetings, one per date.
+    pub fn new(
+        from: NaiveDate,
+        to: NaiveDate,
+        tasks: Vec<Task>,
+        study_blocks: Vec<AgendaStudyBlock>,
+        classes: Vec<ScheduledClass>,
+        calendar: &Calendar,
+    ) -> Self {
+        let mut days: Vec<AgendaDay> = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| AgendaDay { date, classes: Vec::new(), study_blocks: Vec::new(), tasks: Vec::new() })
+            .collect();
+        let index = |date: NaiveDate| (from..=to).contains(&date).then(|| (date - from).num_days() as usize);
+        let due_day = |task: &Task| task.due_date.and_then(|due| index(calendar.local_date(due)));
+
+        for class in classes {
+            if let Some(i) = index(class.date) {
+                days[i].classes.push(class);
+            }
+        }
+        for block in study_blocks {
+            if let Some(i) = due_day(&block.task) {
+                days[i].study_blocks.push(block);
+            }
+        }
+        for task in tasks {
+            if let Some(i) = due_day(&task) {
+                days[i].tasks.push(task);
+            }
+        }
+        for day in &mut days {
+            day.classes.sort_by_key(|class| (class.start_at, class.class_id));
+            day.study_blocks.sort_by_key(|block| block.task.id);
+            day.tasks.sort_by_key(|task| (!task.all_day, task.due_date, task.id));
+        }
+
+        Self { from, to, days }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserSettings;
+    use chrono::{NaiveDateTime, Weekday};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn task(id: u32, due: &str, all_day: bool) -> Task {
+        let mut task = Task::new(format!("Task {}", id), 1, None, None).unwrap();
+        task.id = Some(id);
+        task.due_date = Some(at(due));
+        task.all_day = all_day;
+        task
+    }
+
+    #[test]
+    fn test_tasks_are_grouped_by_local_day() {
+        let calendar = UserSettings { utc_offset_minutes: Some(-300), ..UserSettings::new(1) }.calendar();
+        let tasks = vec![
+            // 21:00 local on the 10th.
+            task(1, "2025-03-11 02:00", false),
+            task(2, "2025-03-11 04:59", true),
+            task(3, "2025-03-11 05:00", false),
+            task(4, "2025-03-20 12:00", false),
+        ];
+
+        let agenda = Agenda::new(date("2025-03-10"), date("2025-03-12"), tasks, Vec::new(), Vec::new(), &calendar);
+
+        assert_eq!(agenda.days.len(), 3);
+        let ids = |day: &AgendaDay| day.tasks.iter().filter_map(|task| task.id).collect::<Vec<_>>();
+        assert_eq!(ids(&agenda.days[0]), [2, 1]);
+        assert_eq!(ids(&agenda.days[1]), [3]);
+        assert!(agenda.days[2].tasks.is_empty());
+    }
+
+    #[test]
+    fn test_span_bounds_follow_week_start() {
+        let mut calendar = UserSettings::new(1).calendar();
+        calendar.week_start = Weekday::Sun;
+        // Wednesday.
+        let day = date("2024-02-14");
+
+        assert_eq!(AgendaSpan::Day.bounds(day, &calendar), (day, day));
+        assert_eq!(AgendaSpan::Week.bounds(day, &calendar), (date("2024-02-11"), date("2024-02-17")));
+        assert_eq!(AgendaSpan::Month.bounds(day, &calendar), (date("2024-02-01"), date("2024-02-29")));
+        assert!("year".parse::<AgendaSpan>().is_err());
+    }
+}
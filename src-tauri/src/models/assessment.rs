@@ -0,0 +1,329 @@
+use chrono::NaiveDateTime;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::AssessmentError;
+use crate::utils::SqliteDateTime;
+
+/// Passing grade when a subject does not set one: 6/10, 60% or a D.
+pub const DEFAULT_PASSING_GRADE: f64 = 0.6;
+
+/// Letter grades and the fraction of the maximum each one starts at.
+const LETTER_CUTOFFS: [(&str, f64); 5] = [("A", 0.9), ("B", 0.8), ("C", 0.7), ("D", 0.6), ("F", 0.0)];
+
+/// How a subject's grades are written. Grades are kept internally as a
+/// fraction of the maximum (0.0–1.0); the scheme only parses and formats them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GradingScheme {
+    #[default]
+    TenPoint,
+    Percentage,
+    Letter,
+}
+
+impl GradingScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TenPoint => "ten_point",
+            Self::Percentage => "percentage",
+            Self::Letter => "letter",
+        }
+    }
+
+    pub fn format(&self, fraction: f64) -> String {
+        match self {
+            Self::TenPoint => format!("{:.1}", fraction * 10.0),
+            Self::Percentage => format!("{:.1}%", fraction * 100.0),
+            Self::Letter => LETTER_CUTOFFS
+                .iter()
+                .find(|(_, cutoff)| fraction >= *cutoff - f64::EPSILON)
+                .map_or("F", |(letter, _)| letter)
+                .to_string(),
+        }
+    }
+
+    /// Parses a grade written in this scheme ("7.5", "75%", "B") into a
+    /// fraction. Letters map to the lowest score that earns them.
+    pub fn parse(&self, grade: &str) -> Result<f64, AssessmentError> {
+        let invalid = || AssessmentError::InvalidGrade(grade.to_string());
+        let grade = grade.trim();
+        let fraction = match self {
+            Self::TenPoint => grade.replace(',', ".").parse::<f64>().map_err(|_| invalid())? / 10.0,
+            Self::Percentage => {
+                grade.trim_end_matches('%').trim().replace(',', ".").parse::<f64>().map_err(|_| invalid())? / 100.0
+            }
+            Self::Letter => LETTER_CUTOFFS
+                .iter()
+                .find(|(letter, _)| letter.eq_ignore_ascii_case(grade))
+                .map(|(_, cutoff)| *cutoff)
+                .ok_or_else(invalid)?,
+        };
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(invalid());
+        }
+        Ok(fraction)
+    }
+}
+
+impl FromStr for GradingScheme {
+    type Err = AssessmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ten_point" => Ok(Self::TenPoint),
+            "percentage" => Ok(Self::Percentage),
+            "letter" => Ok(Self::Letter),
+            _ => Err(AssessmentError::InvalidScheme(s.to_string())),
+        }
+    }
+}
+
+impl FromSql for GradingScheme {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl ToSql for GradingScheme {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl fmt::Display for GradingScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A graded piece of work in a subject. Exams with a date are mirrored by an
+/// exam task (`task_id`) so they appear on the board and in the agenda.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Assessment {
+    pub id: Option<u32>,
+    pub subject_id: u32,
+    pub title: String,
+    pub weight: f64,
+    pub date: Option<NaiveDateTime>,
+    pub max_score: f64,
+    pub score: Option<f64>,
+    pub is_exam: bool,
+    pub task_id: Option<u32>,
+}
+
+impl Assessment {
+    pub fn new(subject_id: u32, title: String, weight: f64, max_score: f64) -> Result<Self, AssessmentError> {
+        let assessment = Self {
+            id: None,
+            subject_id,
+            title,
+            weight,
+            date: None,
+            max_score,
+            score: None,
+            is_exam: false,
+            task_id: None,
+        };
+        assessment.validate()?;
+        Ok(assessment)
+    }
+
+    pub fn validate(&self) -> Result<(), AssessmentError> {
+        if self.title.trim().is_empty() {
+            return Err(AssessmentError::InvalidTitle("Assessment title cannot be empty".into()));
+        }
+        if !(self.weight.is_finite() && self.weight > 0.0) {
+            return Err(AssessmentError::InvalidWeight(format!("Weight must be positive, got {}", self.weight)));
+        }
+        if !(self.max_score.is_finite() && self.max_score > 0.0) {
+            return Err(AssessmentError::InvalidScore(format!("Maximum score must be positive, got {}", self.max_score)));
+        }
+        if let Some(score) = self.score {
+            if !(0.0..=self.max_score).contains(&score) {
+                return Err(AssessmentError::InvalidScore(format!(
+                    "Score must be between 0 and {}, got {}",
+                    self.max_score, score
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn fraction(&self) -> Option<f64> {
+        self.score.map(|score| score / self.max_score)
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Assessment {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            subject_id: row.get("subject_id")?,
+            title: row.get("title")?,
+            weight: row.get("weight")?,
+            date: row.get::<_, Option<SqliteDateTime>>("date")?.map(Into::into),
+            max_score: row.get("max_score")?,
+            score: row.get("score")?,
+            is_exam: row.get("is_exam")?,
+            task_id: row.get("task_id")?,
+        })
+    }
+}
+
+/// Score an ungraded assessment needs for the subject to reach the target.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequiredScore {
+    pub assessment_id: u32,
+    pub title: String,
+    /// In the assessment's own points.
+    pub score: f64,
+    pub max_score: f64,
+}
+
+/// Where a subject's grade stands and what is needed to reach `target`.
+/// Fractions are of the maximum grade; `*_grade` fields are formatted in the
+/// subject's scheme.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GradeSummary {
+    pub scheme: GradingScheme,
+    pub target: f64,
+    pub target_grade: String,
+    /// Weighted average of graded assessments only.
+    pub current_average: Option<f64>,
+    pub current_grade: Option<String>,
+    /// Final grade if every remaining assessment scored zero.
+    pub guaranteed: f64,
+    pub graded_weight: f64,
+    pub remaining_weight: f64,
+    /// Fraction needed on every remaining assessment; `None` once all are
+    /// graded. Zero or less means the target is already secured, above one
+    /// means it can no longer be reached.
+    pub required_fraction: Option<f64>,
+    pub required: Vec<RequiredScore>,
+}
+
+impl GradeSummary {
+    pub fn new(assessments: &[Assessment], scheme: GradingScheme, target: f64) -> Self {
+        let total_weight: f64 = assessments.iter().map(|a| a.weight).sum();
+        let graded_weight: f64 = assessments.iter().filter(|a| a.score.is_some()).map(|a| a.weight).sum();
+        let earned: f64 = assessments.iter().filter_map(|a| a.fraction().map(|f| f * a.weight)).sum();
+        let remaining_weight = total_weight - graded_weight;
+
+        let current_average = (graded_weight > 0.0).then(|| earned / graded_weight);
+        let required_fraction = (remaining_weight > 0.0).then(|| (target * total_weight - earned) / remaining_weight);
+        let required = match required_fraction {
+            Some(fraction) => assessments
+                .iter()
+                .filter(|a| a.score.is_none())
+                .map(|a| RequiredScore {
+                    assessment_id: a.id.unwrap_or_default(),
+                    title: a.title.clone(),
+                    score: round_up_cents(fraction.max(0.0) * a.max_score),
+                    max_score: a.max_score,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Self {
+            scheme,
+            target,
+            target_grade: scheme.format(target),
+            current_average,
+            current_grade: current_average.map(|average| scheme.format(average)),
+            guaranteed: if total_weight > 0.0 { earned / total_weight } else { 0.0 },
+            graded_weight,
+            remaining_weight,
+            required_fraction,
+            required,
+        }
+    }
+}
+
+/// Rounds up to two decimals, ignoring floating-point noise, so the score
+/// shown is always enough.
+fn round_up_cents(score: f64) -> f64 {
+    (score * 100.0 - 1e-6).ceil() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assessment(id: u32, weight: f64, max_score: f64, score: Option<f64>) -> Assessment {
+        let mut assessment = Assessment::new(1, format!("A{}", id), weight, max_score).unwrap();
+        assessment.id = Some(id);
+        assessment.score = score;
+        assessment
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(matches!(Assessment::new(1, "P1".into(), 0.0, 10.0), Err(AssessmentError::InvalidWeight(_))));
+        assert!(matches!(Assessment::new(1, "P1".into(), 1.0, 0.0), Err(AssessmentError::InvalidScore(_))));
+        let mut graded = Assessment::new(1, "P1".into(), 1.0, 10.0).unwrap();
+        graded.score = Some(10.5);
+        assert!(matches!(graded.validate(), Err(AssessmentError::InvalidScore(_))));
+    }
+
+    #[test]
+    fn test_schemes_parse_and_format() {
+        assert_eq!(GradingScheme::TenPoint.parse("7,5").unwrap(), 0.75);
+        assert_eq!(GradingScheme::Percentage.parse("60%").unwrap(), 0.6);
+        assert_eq!(GradingScheme::Letter.parse("b").unwrap(), 0.8);
+        assert!(GradingScheme::TenPoint.parse("11").is_err());
+        assert!(GradingScheme::Letter.parse("E").is_err());
+
+        assert_eq!(GradingScheme::TenPoint.format(0.75), "7.5");
+        assert_eq!(GradingScheme::Percentage.format(0.625), "62.5%");
+        assert_eq!(GradingScheme::Letter.format(0.7), "C");
+        assert_eq!(GradingScheme::Letter.format(0.59), "F");
+    }
+
+    #[test]
+    fn test_required_score_on_the_final() {
+        // P1 (weight 2) scored 5/10, P2 (weight 3) scored 6/10, final (weight 5) pending.
+        let assessments = [
+            assessment(1, 2.0, 10.0, Some(5.0)),
+            assessment(2, 3.0, 10.0, Some(6.0)),
+            assessment(3, 5.0, 10.0, None),
+        ];
+
+        let summary = GradeSummary::new(&assessments, GradingScheme::TenPoint, 0.6);
+
+        assert_eq!(summary.current_grade.as_deref(), Some("5.6"));
+        assert!((summary.guaranteed - 0.28).abs() < 1e-9);
+        assert_eq!(summary.remaining_weight, 5.0);
+        assert!((summary.required_fraction.unwrap() - 0.64).abs() < 1e-9);
+        assert_eq!(summary.required[0].assessment_id, 3);
+        assert_eq!(summary.required[0].score, 6.4);
+    }
+
+    #[test]
+    fn test_secured_and_unreachable_targets() {
+        let secured = GradeSummary::new(
+            &[assessment(1, 3.0, 100.0, Some(100.0)), assessment(2, 1.0, 100.0, None)],
+            GradingScheme::Percentage,
+            0.6,
+        );
+        assert!(secured.required_fraction.unwrap() <= 0.0);
+        assert_eq!(secured.required[0].score, 0.0);
+
+        let unreachable = GradeSummary::new(
+            &[assessment(1, 3.0, 10.0, Some(0.0)), assessment(2, 1.0, 10.0, None)],
+            GradingScheme::Letter,
+            0.6,
+        );
+        assert!(unreachable.required_fraction.unwrap() > 1.0);
+
+        let finished = GradeSummary::new(&[assessment(1, 1.0, 10.0, Some(8.0))], GradingScheme::Letter, 0.6);
+        assert_eq!(finished.required_fraction, None);
+        assert_eq!(finished.current_grade.as_deref(), Some("B"));
+    }
+}
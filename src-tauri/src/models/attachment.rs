@@ -0,0 +1,137 @@
+use chrono::{NaiveDateTime, Utc};
+use rusqlite::Row;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::errors::AttachmentError;
+use crate::utils::{truncate_to_minute, SqliteDateTime, StoredBlob};
+
+/// A file attached to exactly one task or note. Attachments with the same
+/// contents share one blob in the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Attachment {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub task_id: Option<u32>,
+    pub note_id: Option<u32>,
+    pub hash: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub created_at: NaiveDateTime,
+}
+
+impl Attachment {
+    pub fn new(
+        user_id: u32,
+        task_id: Option<u32>,
+        note_id: Option<u32>,
+        original_name: String,
+        blob: &StoredBlob,
+    ) -> Result<Self, AttachmentError> {
+        let attachment = Self {
+            id: None,
+            user_id,
+            task_id,
+            note_id,
+            hash: blob.hash.clone(),
+            mime_type: mime_type_for(&original_name).to_string(),
+            original_name,
+            size: blob.size,
+            created_at: truncate_to_minute(Utc::now().naive_utc()),
+        };
+        attachment.validate()?;
+        Ok(attachment)
+    }
+
+    pub fn validate(&self) -> Result<(), AttachmentError> {
+        if self.task_id.is_some() == self.note_id.is_some() {
+            return Err(AttachmentError::InvalidAttachment(
+                "An attachment belongs to exactly one task or note".into(),
+            ));
+        }
+        if self.original_name.trim().is_empty() {
+            return Err(AttachmentError::InvalidAttachment("File name cannot be empty".into()));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Attachment {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            task_id: row.get("task_id")?,
+            note_id: row.get("note_id")?,
+            hash: row.get("hash")?,
+            original_name: row.get("original_name")?,
+            mime_type: row.get("mime_type")?,
+            size: row.get("size")?,
+            created_at: row.get::<_, SqliteDateTime>("created_at")?.into(),
+        })
+    }
+}
+
+/// MIME type guessed from the file extension, for the formats students
+/// usually attach.
+pub fn mime_type_for(name: &str) -> &'static str {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "odp" => "application/vnd.oasis.opendocument.presentation",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "zip" => "application/zip",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Result of re-hashing every blob a user's attachments point to.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    pub checked: u32,
+    pub missing: Vec<Attachment>,
+    pub corrupted: Vec<Attachment>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_for() {
+        assert_eq!(mime_type_for("Aula 3.PDF"), "application/pdf");
+        assert_eq!(mime_type_for("photo.jpeg"), "image/jpeg");
+        assert_eq!(mime_type_for("Makefile"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_owner_must_be_unique() {
+        let blob = StoredBlob { hash: "ab".repeat(32), size: 3 };
+        assert!(Attachment::new(1, Some(1), Some(2), "a.pdf".to_string(), &blob).is_err());
+        assert!(Attachment::new(1, None, None, "a.pdf".to_string(), &blob).is_err());
+        assert!(Attachment::new(1, None, Some(2), "a.pdf".to_string(), &blob).is_ok());
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use crate::models::{Task, WorkflowStatus};
+
+/// One Kanban column: a user's status definition and its tasks in rank order.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardColumn {
+    pub status: WorkflowStatus,
+    pub wip_limit: Option<u32>,
+    pub tasks: Vec<Task>,
+}
@@ -0,0 +1,125 @@
+use chrono::NaiveDateTime;
+use serde::{Serialize, Deserialize};
+
+use crate::models::{
+    task_priority::TaskPriority,
+    task_status::TaskStatus,
+    FrontendTag, Task,
+};
+
+/// Which tasks a bulk operation applies to: explicit IDs or a filter.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSelection {
+    Ids(Vec<u32>),
+    Filter(TaskFilter),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub tag: Option<String>,
+    pub due_after: Option<NaiveDateTime>,
+    pub due_before: Option<NaiveDateTime>,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        self.status.as_ref().is_none_or(|status| &task.status == status)
+            && self.priority.as_ref().is_none_or(|priority| &task.priority == priority)
+            && self.tag.as_ref().is_none_or(|name| task.tags.iter().any(|tag| &tag.name == name))
+            && self.due_after.is_none_or(|after| task.due_date.is_some_and(|due| due >= after))
+            && self.due_before.is_none_or(|before| task.due_date.is_some_and(|due| due <= before))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BulkTaskChanges {
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub shift_due_minutes: Option<i64>,
+    #[serde(default)]
+    pub add_tags: Vec<FrontendTag>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+}
+
+impl BulkTaskChanges {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.priority.is_none()
+            && self.shift_due_minutes.is_none_or(|minutes| minutes == 0)
+            && self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct BulkSummary {
+    pub matched: u32,
+    pub affected: u32,
+    pub missing_ids: Vec<u32>,
+    pub undo_id: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        let mut task = Task::new("Study".to_string(), 1, None, Some(TaskPriority::High)).unwrap();
+        task.due_date = Some(NaiveDateTime::parse_from_str("2025-03-10 09:00", "%Y-%m-%d %H:%M").unwrap());
+        task.tags = vec![crate::models::Tag::new("Physics".to_string(), "red".to_string()).unwrap()];
+        task
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        assert!(TaskFilter::default().matches(&task()));
+    }
+
+    #[test]
+    fn test_filter_combines_conditions() {
+        let filter = TaskFilter {
+            priority: Some(TaskPriority::High),
+            tag: Some("Physics".to_string()),
+            due_before: Some(NaiveDateTime::parse_from_str("2025-03-10 09:00", "%Y-%m-%d %H:%M").unwrap()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&task()));
+
+        let filter = TaskFilter { tag: Some("Math".to_string()), ..filter };
+        assert!(!filter.matches(&task()));
+    }
+
+    #[test]
+    fn test_selection_deserializes_ids_and_filter() {
+        let ids: TaskSelection = serde_json::from_str(r#"{"ids": [1, 2]}"#).unwrap();
+        assert!(matches!(ids, TaskSelection::Ids(ids) if ids == vec![1, 2]));
+
+        let filter: TaskSelection =
+            serde_json::from_str(r#"{"filter": {"status": "Done", "tag": "Physics"}}"#).unwrap();
+        assert!(matches!(
+            filter,
+            TaskSelection::Filter(TaskFilter { status: Some(TaskStatus::Done), .. })
+        ));
+    }
+
+    #[test]
+    fn test_filter_by_builtin_key_matches() {
+        let filter: TaskFilter = serde_json::from_str(r#"{"status": "done"}"#).unwrap();
+        let mut done = task();
+        done.status = TaskStatus::Done;
+
+        assert!(filter.matches(&done));
+        assert!(!filter.matches(&task()));
+    }
+
+    #[test]
+    fn test_changes_is_empty() {
+        assert!(BulkTaskChanges::default().is_empty());
+        assert!(BulkTaskChanges { shift_due_minutes: Some(0), ..Default::default() }.is_empty());
+        assert!(!BulkTaskChanges { remove_tags: vec!["Physics".to_string()], ..Default::default() }.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SettingsError;
+use crate::utils::local_to_utc;
+
+/// Largest UTC offset accepted, in minutes (UTC+14).
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Per-user preferences that decide how days and weeks are cut.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserSettings {
+    pub user_id: u32,
+    pub week_start: Weekday,
+    /// Fixed offset from UTC; `None` follows the system time zone,
+    /// including its daylight saving changes.
+    pub utc_offset_minutes: Option<i32>,
+}
+
+impl UserSettings {
+    pub fn new(user_id: u32) -> Self {
+        Self { user_id, week_start: Weekday::Mon, utc_offset_minutes: None }
+    }
+
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if self.utc_offset_minutes.is_some_and(|offset| offset.abs() > MAX_UTC_OFFSET_MINUTES) {
+            return Err(SettingsError::InvalidSettings("UTC offset must be within ±14 hours".into()));
+        }
+        Ok(())
+    }
+
+    pub fn calendar(&self) -> Calendar {
+        Calendar {
+            week_start: self.week_start,
+            offset: self.utc_offset_minutes.and_then(|minutes| FixedOffset::east_opt(minutes * 60)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for UserSettings {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        let week_start: u8 = row.get("week_start")?;
+        Ok(Self {
+            user_id: row.get("user_id")?,
+            week_start: Weekday::try_from(week_start).unwrap_or(Weekday::Mon),
+            utc_offset_minutes: row.get("utc_offset_minutes")?,
+        })
+    }
+}
+
+/// Converts between stored UTC times and the user's local days and weeks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    pub week_start: Weekday,
+    /// `None` means the system time zone.
+    pub offset: Option<FixedOffset>,
+}
+
+impl Calendar {
+    pub fn to_local(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => utc + Duration::seconds(offset.local_minus_utc() as i64),
+            None => Local.from_utc_datetime(&utc).naive_local(),
+        }
+    }
+
+    pub fn to_utc(&self, local: NaiveDateTime) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => local - Duration::seconds(offset.local_minus_utc() as i64),
+            None => local_to_utc(local),
+        }
+    }
+
+    pub fn local_date(&self, utc: NaiveDateTime) -> NaiveDate {
+        self.to_local(utc).date()
+    }
+
+    /// UTC instant at which the local `date` begins.
+    pub fn day_start(&self, date: NaiveDate) -> NaiveDateTime {
+        self.to_utc(date.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    /// First day of the week containing `date`.
+    pub fn week_start_of(&self, date: NaiveDate) -> NaiveDate {
+        let offset = (7 + date.weekday().num_days_from_monday() - self.week_start.num_days_from_monday()) % 7;
+        date - Duration::days(offset as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_fixed_offset_moves_day_boundaries() {
+        let settings = UserSettings { user_id: 1, week_start: Weekday::Mon, utc_offset_minutes: Some(-180) };
+        let calendar = settings.calendar();
+
+        assert_eq!(calendar.local_date(at("2025-03-11 02:00")), date("2025-03-10"));
+        assert_eq!(calendar.day_start(date("2025-03-10")), at("2025-03-10 03:00"));
+        assert_eq!(calendar.to_utc(calendar.to_local(at("2025-03-10 12:34"))), at("2025-03-10 12:34"));
+    }
+
+    #[test]
+    fn test_week_start_of() {
+        let mut calendar = UserSettings::new(1).calendar();
+        // Wednesday 2025-03-12.
+        assert_eq!(calendar.week_start_of(date("2025-03-12")), date("2025-03-10"));
+        calendar.week_start = Weekday::Sun;
+        assert_eq!(calendar.week_start_of(date("2025-03-12")), date("2025-03-09"));
+        assert_eq!(calendar.week_start_of(date("2025-03-09")), date("2025-03-09"));
+        calendar.week_start = Weekday::Sat;
+        assert_eq!(calendar.week_start_of(date("2025-03-14")), date("2025-03-08"));
+    }
+
+    #[test]
+    fn test_validate_offset() {
+        let mut settings = UserSettings::new(1);
+        settings.utc_offset_minutes = Some(15 * 60);
+        assert!(settings.validate().is_err());
+        settings.utc_offset_minutes = Some(5 * 60 + 30);
+        assert!(settings.validate().is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
+
+use crate::models::Task;
+
+/// Tasks due within this window (or already overdue) count as urgent.
+pub const URGENT_WITHIN: Duration = Duration::hours(48);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EisenhowerQuadrant {
+    /// Important and urgent.
+    DoFirst,
+    /// Important, not urgent.
+    Schedule,
+    /// Urgent, not important.
+    Delegate,
+    /// Neither important nor urgent.
+    Eliminate,
+}
+
+impl EisenhowerQuadrant {
+    pub const ALL: [EisenhowerQuadrant; 4] = [
+        EisenhowerQuadrant::DoFirst,
+        EisenhowerQuadrant::Schedule,
+        EisenhowerQuadrant::Delegate,
+        EisenhowerQuadrant::Eliminate,
+    ];
+
+    /// Importance comes from the priority level, urgency from how close the
+    /// due date is to `now`.
+    pub fn of(task: &Task, now: NaiveDateTime) -> Self {
+        let important = task.priority.is_important();
+        let urgent = task.due_date.is_some_and(|due| due <= now + URGENT_WITHIN);
+        match (important, urgent) {
+            (true, true) => Self::DoFirst,
+            (true, false) => Self::Schedule,
+            (false, true) => Self::Delegate,
+            (false, false) => Self::Eliminate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuadrantGroup {
+    pub quadrant: EisenhowerQuadrant,
+    pub tasks: Vec<Task>,
+}
+
+/// Splits tasks into the four quadrants (always all four, in matrix order),
+/// each sorted by priority, highest first, then by due date with undated
+/// tasks last.
+pub fn group_by_quadrant(tasks: Vec<Task>, now: NaiveDateTime) -> Vec<QuadrantGroup> {
+    let mut groups: Vec<QuadrantGroup> = EisenhowerQuadrant::ALL
+        .into_iter()
+        .map(|quadrant| QuadrantGroup { quadrant, tasks: Vec::new() })
+        .collect();
+
+    for task in tasks {
+        let quadrant = EisenhowerQuadrant::of(&task, now);
+        if let Some(group) = groups.iter_mut().find(|group| group.quadrant == quadrant) {
+            group.tasks.push(task);
+        }
+    }
+
+    for group in &mut groups {
+        group
+            .tasks
+            .sort_by_key(|task| (std::cmp::Reverse(task.priority), task.due_date.is_none(), task.due_date));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task_priority::TaskPriority;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn task(title: &str, priority: TaskPriority, due: &str) -> Task {
+        let mut task = Task::new(title.to_string(), 1, None, Some(priority)).unwrap();
+        task.due_date = Some(at(due));
+        task
+    }
+
+    #[test]
+    fn test_quadrant_from_priority_and_due_date() {
+        let now = at("2025-03-10 09:00");
+
+        let quadrant = |priority, due| EisenhowerQuadrant::of(&task("T", priority, due), now);
+        assert_eq!(quadrant(TaskPriority::High, "2025-03-12 09:00"), EisenhowerQuadrant::DoFirst);
+        assert_eq!(quadrant(TaskPriority::Highest, "2025-03-12 09:01"), EisenhowerQuadrant::Schedule);
+        assert_eq!(quadrant(TaskPriority::Medium, "2025-03-01 09:00"), EisenhowerQuadrant::Delegate);
+        assert_eq!(quadrant(TaskPriority::Lowest, "2025-04-01 09:00"), EisenhowerQuadrant::Eliminate);
+    }
+
+    #[test]
+    fn test_groups_are_complete_and_sorted() {
+        let now = at("2025-03-10 09:00");
+        let tasks = vec![
+            task("Report", TaskPriority::High, "2025-03-11 09:00"),
+            task("Exam", TaskPriority::Highest, "2025-03-11 10:00"),
+            task("Essay", TaskPriority::High, "2025-03-10 10:00"),
+        ];
+
+        let groups = group_by_quadrant(tasks, now);
+
+        let quadrants: Vec<_> = groups.iter().map(|group| group.quadrant).collect();
+        assert_eq!(quadrants, EisenhowerQuadrant::ALL);
+        let titles: Vec<_> = groups[0].tasks.iter().map(|task| task.title.as_str()).collect();
+        assert_eq!(titles, ["Exam", "Essay", "Report"]);
+        assert!(groups[1..].iter().all(|group| group.tasks.is_empty()));
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::GoalError;
+use crate::models::Calendar;
+use crate::utils::SqliteDate;
+
+/// Largest accepted target, well above any real day or week of study and
+/// small enough that prorating it over a week's days cannot overflow.
+pub const MAX_GOAL_TARGET: u32 = 1_000_000;
+
+/// What a goal counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalMetric {
+    /// Minutes logged on tasks, by the day the session started.
+    StudyMinutes,
+    /// Tasks moved into a completed status.
+    TasksCompleted,
+    /// Pages or chapters logged in reading sessions.
+    ReadingUnits,
+    /// Questions answered in finished quizzes. There are no flashcards to
+    /// review yet, so this is the review metric for now.
+    QuestionsAnswered,
+}
+
+impl GoalMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StudyMinutes => "study_minutes",
+            Self::TasksCompleted => "tasks_completed",
+            Self::ReadingUnits => "reading_units",
+            Self::QuestionsAnswered => "questions_answered",
+        }
+    }
+}
+
+impl FromStr for GoalMetric {
+    type Err = GoalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "study_minutes" => Ok(Self::StudyMinutes),
+            "tasks_completed" => Ok(Self::TasksCompleted),
+            "reading_units" => Ok(Self::ReadingUnits),
+            "questions_answered" => Ok(Self::QuestionsAnswered),
+            _ => Err(GoalError::InvalidGoal(format!("Unknown goal metric: {}", s))),
+        }
+    }
+}
+
+impl FromSql for GoalMetric {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl ToSql for GoalMetric {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl fmt::Display for GoalMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalPeriod {
+    Daily,
+    Weekly,
+}
+
+impl GoalPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    /// First and last day of the period containing `date`.
+    pub fn bounds(&self, date: NaiveDate, calendar: &Calendar) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::Daily => (date, date),
+            Self::Weekly => {
+                let start = calendar.week_start_of(date);
+                (start, start + Duration::days(6))
+            }
+        }
+    }
+}
+
+impl FromStr for GoalPeriod {
+    type Err = GoalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            _ => Err(GoalError::InvalidGoal(format!("Unknown goal period: {}", s))),
+        }
+    }
+}
+
+impl FromSql for GoalPeriod {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl ToSql for GoalPeriod {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl fmt::Display for GoalPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// "600 study minutes per week", "3 tasks completed per day". Study minutes
+/// and completed tasks can be limited to one subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub title: String,
+    pub metric: GoalMetric,
+    pub period: GoalPeriod,
+    pub target: u32,
+    pub subject_id: Option<u32>,
+    /// Streaks are counted from the period containing this day.
+    pub starts_on: NaiveDate,
+}
+
+impl Goal {
+    pub fn validate(&self) -> Result<(), GoalError> {
+        if self.title.trim().is_empty() {
+            return Err(GoalError::InvalidGoal("Goal title cannot be empty".into()));
+        }
+        if self.target == 0 {
+            return Err(GoalError::InvalidGoal("Goal target must be positive".into()));
+        }
+        if self.target > MAX_GOAL_TARGET {
+            return Err(GoalError::InvalidGoal(format!("Goal target cannot exceed {}", MAX_GOAL_TARGET)));
+        }
+        if self.subject_id.is_some()
+            && !matches!(self.metric, GoalMetric::StudyMinutes | GoalMetric::TasksCompleted)
+        {
+            return Err(GoalError::InvalidGoal(format!("{} goals cannot be limited to a subject", self.metric)));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Goal {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            title: row.get("title")?,
+            metric: row.get("metric")?,
+            period: row.get("period")?,
+            target: row.get("target")?,
+            subject_id: row.get("subject_id")?,
+            starts_on: row.get::<_, SqliteDate>("starts_on")?.into(),
+        })
+    }
+}
+
+/// Where a goal stands in its current period, ready for a progress ring.
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub value: u32,
+    /// The goal's target, reduced in proportion to frozen days.
+    pub target: u32,
+    /// `value / target`, capped at 1.
+    pub fraction: f64,
+    pub met: bool,
+    /// Every day of the current period is frozen.
+    pub frozen: bool,
+    /// Consecutive met periods up to now. The current period extends the
+    /// streak once met but does not break it while still open; frozen
+    /// periods neither extend nor break it.
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+impl GoalProgress {
+    /// Evaluates `goal` from its start until the period containing `today`.
+    /// `values` holds the metric per local day.
+    pub fn new(
+        goal: Goal,
+        values: &HashMap<NaiveDate, u32>,
+        freezes: &HashSet<NaiveDate>,
+        today: NaiveDate,
+        calendar: &Calendar,
+    ) -> Self {
+        let (current_start, current_end) = goal.period.bounds(today, calendar);
+        let (mut start, _) = goal.period.bounds(goal.starts_on.min(today), calendar);
+        let mut streak = 0;
+        let mut best_streak = 0;
+
+        let (value, target, frozen) = loop {
+            let (_, end) = goal.period.bounds(start, calendar);
+            let days: Vec<NaiveDate> = start.iter_days().take_while(|day| *day <= end).collect();
+            let active = days.iter().filter(|day| !freezes.contains(day)).count() as u32;
+            let value: u32 = days.iter().filter_map(|day| values.get(day)).sum();
+            let target = (goal.target * active).div_ceil(days.len() as u32);
+            let met = active > 0 && value >= target;
+            let is_current = start == current_start;
+
+            if met {
+                streak += 1;
+            } else if active > 0 && !is_current {
+                streak = 0;
+            }
+            best_streak = best_streak.max(streak);
+
+            if is_current {
+                break (value, target, active == 0);
+            }
+            start = end + Duration::days(1);
+        };
+
+        Self {
+            goal,
+            period_start: current_start,
+            period_end: current_end,
+            value,
+            target,
+            fraction: if target == 0 { 1.0 } else { (value as f64 / target as f64).min(1.0) },
+            met: !frozen && value >= target,
+            frozen,
+            current_streak: streak,
+            best_streak,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserSettings;
+    use chrono::Weekday;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn goal(period: GoalPeriod, target: u32, starts_on: &str) -> Goal {
+        Goal {
+            id: Some(1),
+            user_id: 1,
+            title: "Study".to_string(),
+            metric: GoalMetric::StudyMinutes,
+            period,
+            target,
+            subject_id: None,
+            starts_on: date(starts_on),
+        }
+    }
+
+    fn values(entries: &[(&str, u32)]) -> HashMap<NaiveDate, u32> {
+        entries.iter().map(|(day, value)| (date(day), *value)).collect()
+    }
+
+    fn calendar(week_start: Weekday) -> Calendar {
+        UserSettings { user_id: 1, week_start, utc_offset_minutes: Some(0) }.calendar()
+    }
+
+    #[test]
+    fn test_target_is_bounded() {
+        assert!(goal(GoalPeriod::Weekly, MAX_GOAL_TARGET, "2025-03-01").validate().is_ok());
+        assert!(matches!(
+            goal(GoalPeriod::Weekly, u32::MAX, "2025-03-01").validate(),
+            Err(GoalError::InvalidGoal(_))
+        ));
+    }
+
+    #[test]
+    fn test_daily_streak_survives_open_day_and_freezes() {
+        let goal = goal(GoalPeriod::Daily, 30, "2025-03-01");
+        let values = values(&[
+            ("2025-03-05", 10),
+            ("2025-03-06", 30),
+            ("2025-03-07", 45),
+            ("2025-03-09", 30),
+            ("2025-03-10", 5),
+        ]);
+        let freezes = HashSet::from([date("2025-03-08")]);
+
+        let progress = GoalProgress::new(goal, &values, &freezes, date("2025-03-10"), &calendar(Weekday::Mon));
+
+        assert_eq!((progress.value, progress.target, progress.met), (5, 30, false));
+        assert!((progress.fraction - 5.0 / 30.0).abs() < 1e-9);
+        assert_eq!(progress.current_streak, 3);
+        assert_eq!(progress.best_streak, 3);
+    }
+
+    #[test]
+    fn test_weekly_goal_follows_week_start() {
+        let goal = goal(GoalPeriod::Weekly, 100, "2025-03-01");
+        // Saturday and Sunday belong to different weeks when weeks start on
+        // Sunday.
+        let values = values(&[("2025-03-08", 60), ("2025-03-09", 60)]);
+
+        let monday = GoalProgress::new(goal.clone(), &values, &HashSet::new(), date("2025-03-09"), &calendar(Weekday::Mon));
+        let sunday = GoalProgress::new(goal, &values, &HashSet::new(), date("2025-03-09"), &calendar(Weekday::Sun));
+
+        assert_eq!((monday.period_start, monday.value, monday.met), (date("2025-03-03"), 120, true));
+        assert_eq!((sunday.period_start, sunday.value, sunday.met), (date("2025-03-09"), 60, false));
+        assert_eq!((monday.current_streak, sunday.current_streak), (1, 0));
+    }
+
+    #[test]
+    fn test_frozen_days_prorate_weekly_target() {
+        let goal = goal(GoalPeriod::Weekly, 700, "2025-03-10");
+        let freezes: HashSet<NaiveDate> = date("2025-03-10").iter_days().take(4).collect();
+
+        let progress = GoalProgress::new(goal, &values(&[("2025-03-15", 300)]), &freezes, date("2025-03-16"), &calendar(Weekday::Mon));
+
+        assert_eq!(progress.target, 300);
+        assert!(progress.met);
+    }
+
+    #[test]
+    fn test_missed_period_resets_streak() {
+        let goal = goal(GoalPeriod::Daily, 1, "2025-03-01");
+        let values = values(&[("2025-03-01", 1), ("2025-03-02", 1), ("2025-03-04", 1)]);
+
+        let progress = GoalProgress::new(goal, &values, &HashSet::new(), date("2025-03-04"), &calendar(Weekday::Mon));
+
+        assert_eq!((progress.current_streak, progress.best_streak), (1, 2));
+    }
+}
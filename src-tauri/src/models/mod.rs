@@ -3,4 +3,44 @@ pub use user::*;
 pub mod task;
 pub use task::*;
 pub mod tag;
-pub use tag::*;
\ No newline at end of file
+pub use tag::*;
+pub mod bulk_operation;
+pub use bulk_operation::*;
+pub mod board;
+pub use board::*;
+pub mod workflow_status;
+pub use workflow_status::*;
+pub mod eisenhower;
+pub use eisenhower::*;
+pub mod time_log;
+pub use time_log::*;
+pub mod reminder;
+pub use reminder::*;
+pub mod quick_add;
+pub use quick_add::*;
+pub mod subject;
+pub use subject::*;
+pub mod timetable;
+pub use timetable::*;
+pub mod assessment;
+pub use assessment::*;
+pub mod study_plan;
+pub use study_plan::*;
+pub mod note;
+pub use note::*;
+pub mod attachment;
+pub use attachment::*;
+pub mod reading;
+pub use reading::*;
+pub mod quiz;
+pub use quiz::*;
+pub mod calendar;
+pub use calendar::*;
+pub mod goal;
+pub use goal::*;
+pub mod statistics;
+pub use statistics::*;
+pub mod agenda;
+pub use agenda::*;
+pub mod reschedule;
+pub use reschedule::*;
\ No newline at end of file
@@ -0,0 +1,146 @@
+use chrono::{NaiveDateTime, Utc};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::NoteError;
+use crate::utils::{truncate_to_minute, SqliteDateTime};
+
+/// A Markdown note. `[[Title]]` links to another note by title and
+/// `[[task:42]]` attaches the note to a task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub subject_id: Option<u32>,
+    pub title: String,
+    pub body: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Note {
+    pub fn new(user_id: u32, title: String, body: String) -> Result<Self, NoteError> {
+        let now = truncate_to_minute(Utc::now().naive_utc());
+        let note = Self {
+            id: None,
+            user_id,
+            subject_id: None,
+            title,
+            body,
+            created_at: now,
+            updated_at: now,
+        };
+        note.validate()?;
+        Ok(note)
+    }
+
+    pub fn validate(&self) -> Result<(), NoteError> {
+        if self.title.trim().is_empty() {
+            return Err(NoteError::InvalidNote("Note title cannot be empty".into()));
+        }
+        if self.title.contains(['[', ']', '|']) {
+            return Err(NoteError::InvalidNote("Note titles cannot contain '[', ']' or '|'".into()));
+        }
+        Ok(())
+    }
+
+    pub fn links(&self) -> Vec<NoteLink> {
+        parse_links(&self.body)
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Note {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            subject_id: row.get("subject_id")?,
+            title: row.get("title")?,
+            body: row.get("body")?,
+            created_at: row.get::<_, SqliteDateTime>("created_at")?.into(),
+            updated_at: row.get::<_, SqliteDateTime>("updated_at")?.into(),
+        })
+    }
+}
+
+/// Target of a `[[wiki-link]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum NoteLink {
+    Note(String),
+    Task(u32),
+}
+
+/// Extracts the distinct links of a note body, in order of appearance.
+/// `[[Title|label]]` links to "Title"; links inside code are not skipped.
+pub fn parse_links(body: &str) -> Vec<NoteLink> {
+    let mut links = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else { break };
+        let inner = &rest[..end];
+        rest = &rest[end + 2..];
+
+        let target = inner.split('|').next().unwrap_or_default().trim();
+        if target.is_empty() || target.contains('[') {
+            continue;
+        }
+        let link = match target.strip_prefix("task:").map(|id| id.trim().parse::<u32>()) {
+            Some(Ok(id)) => NoteLink::Task(id),
+            _ => NoteLink::Note(target.to_string()),
+        };
+        let duplicate = links.iter().any(|existing| match (existing, &link) {
+            (NoteLink::Note(a), NoteLink::Note(b)) => a.to_lowercase() == b.to_lowercase(),
+            (a, b) => a == b,
+        });
+        if !duplicate {
+            links.push(link);
+        }
+    }
+    links
+}
+
+/// Notes and tasks a note links to; `missing` lists note titles that do not
+/// exist yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteLinks {
+    pub notes: Vec<Note>,
+    pub tasks: Vec<crate::models::Task>,
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteSearchHit {
+    pub note: Note,
+    /// Matching excerpt with hits wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        let body = "See [[Newton's laws]], [[newton's LAWS|again]] and [[task:42]].\n\
+                    Broken [[ ]] and [[task:x]] and [[unclosed";
+
+        assert_eq!(
+            parse_links(body),
+            vec![
+                NoteLink::Note("Newton's laws".to_string()),
+                NoteLink::Task(42),
+                NoteLink::Note("task:x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_link_syntax_in_title() {
+        assert!(Note::new(1, "  ".to_string(), String::new()).is_err());
+        assert!(Note::new(1, "a|b".to_string(), String::new()).is_err());
+        assert!(Note::new(1, "Optics".to_string(), String::new()).is_ok());
+    }
+}
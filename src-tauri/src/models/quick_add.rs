@@ -0,0 +1,90 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::models::{task_priority::TaskPriority, Calendar, Tag};
+use crate::utils::QuickAdd;
+
+/// Color given to tags created from a quick-add line.
+pub const QUICK_ADD_TAG_COLOR: &str = "gray";
+
+/// The task a quick-add line would create, shown to the user before saving.
+/// Tags without an id do not exist yet and are created on save.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuickAddPreview {
+    pub title: String,
+    /// UTC, like every stored due date.
+    pub due_date: Option<NaiveDateTime>,
+    pub all_day: bool,
+    pub priority: TaskPriority,
+    pub tags: Vec<Tag>,
+}
+
+impl QuickAddPreview {
+    /// Resolves tag names against `existing` tags, ignoring case. The parsed
+    /// due date is in the user's local time, given by `calendar`.
+    pub fn new(parsed: QuickAdd, existing: &[Tag], calendar: &Calendar) -> Self {
+        let tags = parsed
+            .tags
+            .into_iter()
+            .map(|name| {
+                existing
+                    .iter()
+                    .find(|tag| tag.name.to_lowercase() == name.to_lowercase())
+                    .cloned()
+                    .unwrap_or(Tag { id: None, name, color: QUICK_ADD_TAG_COLOR.to_string() })
+            })
+            .collect();
+
+        Self {
+            title: parsed.title,
+            due_date: parsed.due_date.map(|due| calendar.to_utc(due)),
+            all_day: parsed.all_day,
+            priority: parsed.priority.unwrap_or(TaskPriority::Medium),
+            tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserSettings;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_due_date_is_converted_with_the_users_offset() {
+        let calendar = UserSettings { utc_offset_minutes: Some(-180), ..UserSettings::new(1) }.calendar();
+        let due = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap().and_hms_opt(18, 0, 0).unwrap();
+        let parsed = QuickAdd {
+            title: "Essay".to_string(),
+            due_date: Some(due),
+            all_day: false,
+            priority: None,
+            tags: Vec::new(),
+        };
+
+        let preview = QuickAddPreview::new(parsed, &[], &calendar);
+
+        assert_eq!(preview.due_date, Some(due + chrono::Duration::hours(3)));
+    }
+
+    #[test]
+    fn test_matches_existing_tags_and_creates_the_rest() {
+        let existing = vec![Tag { id: Some(3), name: "Physics".to_string(), color: "#336699".to_string() }];
+        let parsed = QuickAdd {
+            title: "Read chapter 4".to_string(),
+            due_date: None,
+            all_day: false,
+            priority: None,
+            tags: vec!["physics".to_string(), "reading".to_string()],
+        };
+
+        let preview = QuickAddPreview::new(parsed, &existing, &UserSettings::new(1).calendar());
+
+        assert_eq!(preview.priority, TaskPriority::Medium);
+        assert_eq!(preview.tags[0].id, Some(3));
+        assert_eq!(preview.tags[0].name, "Physics");
+        assert_eq!(preview.tags[1].id, None);
+        assert_eq!(preview.tags[1].color, QUICK_ADD_TAG_COLOR);
+    }
+}
@@ -0,0 +1,371 @@
+use chrono::NaiveDateTime;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Type, ValueRef};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::QuizError;
+use crate::utils::SqliteDateTime;
+
+/// Questions per quiz when the caller does not choose.
+pub const DEFAULT_QUIZ_SIZE: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionKind {
+    #[default]
+    MultipleChoice,
+    ShortAnswer,
+}
+
+impl QuestionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MultipleChoice => "multiple_choice",
+            Self::ShortAnswer => "short_answer",
+        }
+    }
+}
+
+impl FromStr for QuestionKind {
+    type Err = QuizError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "multiple_choice" => Ok(Self::MultipleChoice),
+            "short_answer" => Ok(Self::ShortAnswer),
+            _ => Err(QuizError::InvalidQuestion(format!("Unknown question kind: {}", s))),
+        }
+    }
+}
+
+impl FromSql for QuestionKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl ToSql for QuestionKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl fmt::Display for QuestionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A question in the bank. Multiple-choice questions have one answer, which
+/// must be one of the choices; short-answer questions accept any of their
+/// answers, compared case- and whitespace-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Question {
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub user_id: u32,
+    #[serde(default)]
+    pub subject_id: Option<u32>,
+    /// Note or task the question was written from.
+    #[serde(default)]
+    pub note_id: Option<u32>,
+    #[serde(default)]
+    pub task_id: Option<u32>,
+    #[serde(default)]
+    pub kind: QuestionKind,
+    pub prompt: String,
+    #[serde(default)]
+    pub choices: Vec<String>,
+    pub answers: Vec<String>,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub tag_ids: Vec<u32>,
+}
+
+impl Question {
+    pub fn validate(&self) -> Result<(), QuizError> {
+        if self.prompt.trim().is_empty() {
+            return Err(QuizError::InvalidQuestion("Question prompt cannot be empty".into()));
+        }
+        if self.answers.is_empty() || self.answers.iter().any(|answer| normalize_answer(answer).is_empty()) {
+            return Err(QuizError::InvalidQuestion("A question needs a non-empty answer".into()));
+        }
+        match self.kind {
+            QuestionKind::MultipleChoice => {
+                let mut choices: Vec<String> = self.choices.iter().map(|choice| normalize_answer(choice)).collect();
+                if choices.len() < 2 || choices.iter().any(|choice| choice.is_empty()) {
+                    return Err(QuizError::InvalidQuestion("Multiple choice needs at least two choices".into()));
+                }
+                choices.sort();
+                choices.dedup();
+                if choices.len() != self.choices.len() {
+                    return Err(QuizError::InvalidQuestion("Choices must be distinct".into()));
+                }
+                if self.answers.len() != 1 || !choices.contains(&normalize_answer(&self.answers[0])) {
+                    return Err(QuizError::InvalidQuestion("The answer must be exactly one of the choices".into()));
+                }
+            }
+            QuestionKind::ShortAnswer => {
+                if !self.choices.is_empty() {
+                    return Err(QuizError::InvalidQuestion("Short-answer questions have no choices".into()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_correct(&self, given: &str) -> bool {
+        let given = normalize_answer(given);
+        !given.is_empty() && self.answers.iter().any(|answer| normalize_answer(answer) == given)
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Question {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            subject_id: row.get("subject_id")?,
+            note_id: row.get("note_id")?,
+            task_id: row.get("task_id")?,
+            kind: row.get("kind")?,
+            prompt: row.get("prompt")?,
+            choices: json_column(row, "choices")?,
+            answers: json_column(row, "answers")?,
+            explanation: row.get("explanation")?,
+            tag_ids: Vec::new(),
+        })
+    }
+}
+
+fn json_column(row: &Row<'_>, column: &str) -> rusqlite::Result<Vec<String>> {
+    let json: String = row.get(column)?;
+    serde_json::from_str(&json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(row.as_ref().column_index(column).unwrap_or(0), Type::Text, Box::new(e))
+    })
+}
+
+/// Lowercased, trimmed, with runs of whitespace collapsed and trailing
+/// punctuation dropped.
+pub fn normalize_answer(answer: &str) -> String {
+    answer
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', '!', '?'])
+        .to_lowercase()
+}
+
+/// Deterministic shuffle (SplitMix64 + Fisher–Yates), so a quiz can be
+/// rebuilt in the same order from its seed.
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    for i in (1..items.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// A question as shown while taking a quiz: no answers, choices shuffled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuizQuestion {
+    pub question_id: u32,
+    pub kind: QuestionKind,
+    pub prompt: String,
+    pub choices: Vec<String>,
+}
+
+impl QuizQuestion {
+    pub fn new(question: &Question, seed: u64) -> Self {
+        let mut choices = question.choices.clone();
+        shuffle(&mut choices, seed ^ question.id.unwrap_or_default() as u64);
+        Self {
+            question_id: question.id.unwrap_or_default(),
+            kind: question.kind,
+            prompt: question.prompt.clone(),
+            choices,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuizView {
+    pub attempt_id: u32,
+    pub questions: Vec<QuizQuestion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubmittedAnswer {
+    pub question_id: u32,
+    pub answer: String,
+}
+
+/// One quiz taken by the user; `subject_id` and `tag_id` are the filters it
+/// was drawn with.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuizAttempt {
+    pub id: u32,
+    pub user_id: u32,
+    pub subject_id: Option<u32>,
+    pub tag_id: Option<u32>,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+    pub total: u32,
+    pub correct: u32,
+    /// Percentage of correct answers, once finished.
+    pub score: Option<f64>,
+}
+
+impl<'a> TryFrom<&Row<'a>> for QuizAttempt {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        let finished_at: Option<NaiveDateTime> = row.get::<_, Option<SqliteDateTime>>("finished_at")?.map(Into::into);
+        let total: u32 = row.get("total")?;
+        let correct: u32 = row.get("correct")?;
+        Ok(Self {
+            id: row.get("id")?,
+            user_id: row.get("user_id")?,
+            subject_id: row.get("subject_id")?,
+            tag_id: row.get("tag_id")?,
+            started_at: row.get::<_, SqliteDateTime>("started_at")?.into(),
+            finished_at,
+            total,
+            correct,
+            score: finished_at
+                .filter(|_| total > 0)
+                .map(|_| (correct as f64 / total as f64 * 1000.0).round() / 10.0),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerReview {
+    pub question: Question,
+    pub given: Option<String>,
+    pub correct: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuizResult {
+    pub attempt: QuizAttempt,
+    pub answers: Vec<AnswerReview>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicKind {
+    Subject,
+    Tag,
+}
+
+/// Accuracy on the questions of one subject or tag, across finished quizzes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopicScore {
+    pub kind: TopicKind,
+    pub id: u32,
+    pub name: String,
+    pub answered: u32,
+    pub correct: u32,
+    pub accuracy: f64,
+}
+
+impl TopicScore {
+    pub fn new(kind: TopicKind, id: u32, name: String, answered: u32, correct: u32) -> Self {
+        let accuracy = if answered == 0 { 0.0 } else { correct as f64 / answered as f64 };
+        Self { kind, id, name, answered, correct, accuracy }
+    }
+}
+
+/// Weakest first; among equally weak topics, the most practiced first.
+pub fn rank_weakest(mut topics: Vec<TopicScore>, min_answers: u32) -> Vec<TopicScore> {
+    topics.retain(|topic| topic.answered >= min_answers.max(1));
+    topics.sort_by(|a, b| {
+        a.accuracy
+            .total_cmp(&b.accuracy)
+            .then(b.answered.cmp(&a.answered))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    topics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(kind: QuestionKind, choices: &[&str], answers: &[&str]) -> Question {
+        Question {
+            id: Some(1),
+            user_id: 1,
+            subject_id: None,
+            note_id: None,
+            task_id: None,
+            kind,
+            prompt: "Capital of France?".to_string(),
+            choices: choices.iter().map(|c| c.to_string()).collect(),
+            answers: answers.iter().map(|a| a.to_string()).collect(),
+            explanation: None,
+            tag_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(question(QuestionKind::MultipleChoice, &["Paris", "Lyon"], &["Paris"]).validate().is_ok());
+        assert!(question(QuestionKind::MultipleChoice, &["Paris"], &["Paris"]).validate().is_err());
+        assert!(question(QuestionKind::MultipleChoice, &["Paris", "paris"], &["Paris"]).validate().is_err());
+        assert!(question(QuestionKind::MultipleChoice, &["Paris", "Lyon"], &["Nice"]).validate().is_err());
+        assert!(question(QuestionKind::ShortAnswer, &[], &["Paris"]).validate().is_ok());
+        assert!(question(QuestionKind::ShortAnswer, &[], &[" "]).validate().is_err());
+    }
+
+    #[test]
+    fn test_short_answers_ignore_case_and_spacing() {
+        let q = question(QuestionKind::ShortAnswer, &[], &["Paris", "Paris, France"]);
+
+        assert!(q.is_correct("  paris. "));
+        assert!(q.is_correct("PARIS,  france"));
+        assert!(!q.is_correct("Lyon"));
+        assert!(!q.is_correct(""));
+    }
+
+    #[test]
+    fn test_shuffle_is_deterministic_permutation() {
+        let mut a: Vec<u32> = (0..10).collect();
+        let mut b = a.clone();
+        shuffle(&mut a, 42);
+        shuffle(&mut b, 42);
+
+        assert_eq!(a, b);
+        assert_ne!(a, (0..10).collect::<Vec<_>>());
+        b.sort();
+        assert_eq!(b, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rank_weakest() {
+        let topics = vec![
+            TopicScore::new(TopicKind::Subject, 1, "Physics".to_string(), 10, 9),
+            TopicScore::new(TopicKind::Tag, 2, "optics".to_string(), 4, 1),
+            TopicScore::new(TopicKind::Subject, 3, "History".to_string(), 8, 2),
+            TopicScore::new(TopicKind::Tag, 4, "rare".to_string(), 1, 0),
+        ];
+
+        let ranked = rank_weakest(topics, 2);
+
+        let names: Vec<&str> = ranked.iter().map(|topic| topic.name.as_str()).collect();
+        assert_eq!(names, ["History", "optics", "Physics"]);
+    }
+}
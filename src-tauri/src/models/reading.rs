@@ -0,0 +1,317 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::ReadingError;
+use crate::utils::SqliteDate;
+
+/// Days of reading history the pace is averaged over.
+pub const PACE_WINDOW_DAYS: i64 = 14;
+
+/// What a book's progress is counted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingUnit {
+    #[default]
+    Pages,
+    Chapters,
+}
+
+impl ReadingUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pages => "pages",
+            Self::Chapters => "chapters",
+        }
+    }
+
+    /// "page 4" or "pages 4–9".
+    pub fn describe_range(&self, from: u32, to: u32) -> String {
+        let (one, many) = match self {
+            Self::Pages => ("page", "pages"),
+            Self::Chapters => ("chapter", "chapters"),
+        };
+        if from == to {
+            format!("{} {}", one, from)
+        } else {
+            format!("{} {}–{}", many, from, to)
+        }
+    }
+}
+
+impl FromStr for ReadingUnit {
+    type Err = ReadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pages" => Ok(Self::Pages),
+            "chapters" => Ok(Self::Chapters),
+            _ => Err(ReadingError::InvalidBook(format!("Unknown reading unit: {}", s))),
+        }
+    }
+}
+
+impl FromSql for ReadingUnit {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl ToSql for ReadingUnit {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl fmt::Display for ReadingUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A book (or any long reading) tracked by pages or chapters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Book {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub subject_id: Option<u32>,
+    pub title: String,
+    pub author: Option<String>,
+    pub total_units: u32,
+    #[serde(default)]
+    pub unit: ReadingUnit,
+    /// Date the user wants to finish by; daily reading tasks aim for it.
+    pub target_date: Option<NaiveDate>,
+}
+
+impl Book {
+    pub fn new(user_id: u32, title: String, total_units: u32, unit: ReadingUnit) -> Result<Self, ReadingError> {
+        let book = Self {
+            id: None,
+            user_id,
+            subject_id: None,
+            title,
+            author: None,
+            total_units,
+            unit,
+            target_date: None,
+        };
+        book.validate()?;
+        Ok(book)
+    }
+
+    pub fn validate(&self) -> Result<(), ReadingError> {
+        if self.title.trim().is_empty() {
+            return Err(ReadingError::InvalidBook("Book title cannot be empty".into()));
+        }
+        if self.total_units == 0 {
+            return Err(ReadingError::InvalidBook(format!("A book needs at least one of its {}", self.unit)));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Book {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            subject_id: row.get("subject_id")?,
+            title: row.get("title")?,
+            author: row.get("author")?,
+            total_units: row.get("total_units")?,
+            unit: row.get("unit")?,
+            target_date: row.get::<_, Option<SqliteDate>>("target_date")?.map(Into::into),
+        })
+    }
+}
+
+/// Pages or chapters read on one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReadingSession {
+    pub id: Option<u32>,
+    pub book_id: u32,
+    pub read_on: NaiveDate,
+    pub units: u32,
+    pub minutes: Option<u32>,
+}
+
+impl ReadingSession {
+    pub fn new(book_id: u32, read_on: NaiveDate, units: u32, minutes: Option<u32>) -> Result<Self, ReadingError> {
+        if units == 0 {
+            return Err(ReadingError::InvalidSession("A session must cover at least one page or chapter".into()));
+        }
+        if minutes == Some(0) {
+            return Err(ReadingError::InvalidSession("Minutes must be positive".into()));
+        }
+        Ok(Self { id: None, book_id, read_on, units, minutes })
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for ReadingSession {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            book_id: row.get("book_id")?,
+            read_on: row.get::<_, SqliteDate>("read_on")?.into(),
+            units: row.get("units")?,
+            minutes: row.get("minutes")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingProgress {
+    pub book: Book,
+    pub units_read: u32,
+    pub remaining: u32,
+    pub percent: f64,
+    /// Average units per day over the last `PACE_WINDOW_DAYS`, counted from
+    /// the first session when that is more recent.
+    pub pace_per_day: Option<f64>,
+    pub estimated_finish: Option<NaiveDate>,
+    /// Units per day needed from today to finish by the target date.
+    pub required_per_day: Option<u32>,
+    pub on_track: Option<bool>,
+}
+
+impl ReadingProgress {
+    pub fn new(book: Book, sessions: &[ReadingSession], today: NaiveDate) -> Self {
+        let units_read = sessions.iter().map(|s| s.units).sum::<u32>().min(book.total_units);
+        let remaining = book.total_units - units_read;
+        let percent = (units_read as f64 / book.total_units as f64 * 1000.0).round() / 10.0;
+
+        let window_start = sessions
+            .iter()
+            .map(|s| s.read_on)
+            .min()
+            .map(|first| first.max(today - Duration::days(PACE_WINDOW_DAYS - 1)));
+        let pace_per_day = window_start.and_then(|start| {
+            let days = (today - start).num_days() + 1;
+            let units: u32 = sessions
+                .iter()
+                .filter(|s| s.read_on >= start && s.read_on <= today)
+                .map(|s| s.units)
+                .sum();
+            (days > 0 && units > 0).then(|| units as f64 / days as f64)
+        });
+
+        let estimated_finish = if remaining == 0 {
+            sessions.iter().map(|s| s.read_on).max()
+        } else {
+            pace_per_day.map(|pace| today + Duration::days((remaining as f64 / pace).ceil() as i64))
+        };
+        let required_per_day = book
+            .target_date
+            .filter(|target| *target >= today && remaining > 0)
+            .map(|target| remaining.div_ceil((target - today).num_days() as u32 + 1));
+        let on_track = book.target_date.map(|target| estimated_finish.is_some_and(|finish| finish <= target));
+
+        Self { book, units_read, remaining, percent, pace_per_day, estimated_finish, required_per_day, on_track }
+    }
+}
+
+/// One day of a reading schedule, covering units `from..=to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadingAssignment {
+    pub date: NaiveDate,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Splits what is left of the book evenly over every day from `today` to
+/// the target date, giving the extra units to the earliest days.
+pub fn reading_schedule(units_read: u32, total_units: u32, today: NaiveDate, target: NaiveDate) -> Vec<ReadingAssignment> {
+    let remaining = total_units.saturating_sub(units_read);
+    if target < today || remaining == 0 {
+        return Vec::new();
+    }
+    let days = (target - today).num_days() as u32 + 1;
+    let (base, extra) = (remaining / days, remaining % days);
+
+    let mut next = units_read + 1;
+    let mut assignments = Vec::new();
+    for day in 0..days {
+        let units = base + u32::from(day < extra);
+        if units == 0 {
+            continue;
+        }
+        assignments.push(ReadingAssignment {
+            date: today + Duration::days(day as i64),
+            from: next,
+            to: next + units - 1,
+        });
+        next += units;
+    }
+    assignments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn session(read_on: &str, units: u32) -> ReadingSession {
+        ReadingSession::new(1, date(read_on), units, None).unwrap()
+    }
+
+    #[test]
+    fn test_progress_projects_finish_from_pace() {
+        let mut book = Book::new(1, "Dom Casmurro".to_string(), 200, ReadingUnit::Pages).unwrap();
+        book.target_date = Some(date("2025-03-20"));
+        // 60 pages over the 3 days since the first session: 20 a day.
+        let sessions = [session("2025-03-08", 30), session("2025-03-10", 30)];
+
+        let progress = ReadingProgress::new(book, &sessions, date("2025-03-10"));
+
+        assert_eq!(progress.units_read, 60);
+        assert_eq!(progress.percent, 30.0);
+        assert_eq!(progress.pace_per_day, Some(20.0));
+        assert_eq!(progress.estimated_finish, Some(date("2025-03-17")));
+        assert_eq!(progress.required_per_day, Some(13));
+        assert_eq!(progress.on_track, Some(true));
+    }
+
+    #[test]
+    fn test_pace_only_counts_recent_sessions() {
+        let book = Book::new(1, "Long read".to_string(), 500, ReadingUnit::Pages).unwrap();
+        let sessions = [session("2025-01-01", 300), session("2025-03-10", 28)];
+
+        let progress = ReadingProgress::new(book, &sessions, date("2025-03-10"));
+
+        assert_eq!(progress.pace_per_day, Some(2.0));
+        assert_eq!(progress.on_track, None);
+    }
+
+    #[test]
+    fn test_reading_schedule_spreads_remaining_units() {
+        let schedule = reading_schedule(40, 50, date("2025-03-10"), date("2025-03-12"));
+
+        assert_eq!(
+            schedule,
+            vec![
+                ReadingAssignment { date: date("2025-03-10"), from: 41, to: 44 },
+                ReadingAssignment { date: date("2025-03-11"), from: 45, to: 47 },
+                ReadingAssignment { date: date("2025-03-12"), from: 48, to: 50 },
+            ]
+        );
+        assert_eq!(reading_schedule(0, 2, date("2025-03-10"), date("2025-03-13")).len(), 2);
+        assert!(reading_schedule(0, 2, date("2025-03-10"), date("2025-03-09")).is_empty());
+    }
+
+    #[test]
+    fn test_describe_range() {
+        assert_eq!(ReadingUnit::Pages.describe_range(4, 9), "pages 4–9");
+        assert_eq!(ReadingUnit::Chapters.describe_range(3, 3), "chapter 3");
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::SqliteDateTime;
+
+/// When a reminder fires: at a fixed time, or a number of minutes before the
+/// task's due date (following the due date if it moves). A relative reminder
+/// on a task without a due date does not fire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderRule {
+    At(NaiveDateTime),
+    BeforeDue(u32),
+}
+
+impl ReminderRule {
+    pub fn trigger_at(&self, due_date: Option<NaiveDateTime>) -> Option<NaiveDateTime> {
+        match self {
+            Self::At(at) => Some(*at),
+            Self::BeforeDue(minutes) => due_date.map(|due| due - Duration::minutes(*minutes as i64)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reminder {
+    pub id: Option<u32>,
+    pub task_id: u32,
+    pub rule: ReminderRule,
+    /// Overrides the rule's time after a snooze.
+    pub snoozed_until: Option<NaiveDateTime>,
+    pub fired_at: Option<NaiveDateTime>,
+    pub dismissed_at: Option<NaiveDateTime>,
+}
+
+impl Reminder {
+    pub fn trigger_at(&self, due_date: Option<NaiveDateTime>) -> Option<NaiveDateTime> {
+        self.snoozed_until.or_else(|| self.rule.trigger_at(due_date))
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.fired_at.is_none() && self.dismissed_at.is_none()
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Reminder {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        let rule = match row.get::<_, Option<SqliteDateTime>>("remind_at")? {
+            Some(at) => ReminderRule::At(at.into()),
+            None => ReminderRule::BeforeDue(row.get("minutes_before_due")?),
+        };
+        let date = |column: &str| -> Result<Option<NaiveDateTime>, rusqlite::Error> {
+            Ok(row.get::<_, Option<SqliteDateTime>>(column)?.map(Into::into))
+        };
+
+        Ok(Self {
+            id: Some(row.get("id")?),
+            task_id: row.get("task_id")?,
+            rule,
+            snoozed_until: date("snoozed_until")?,
+            fired_at: date("fired_at")?,
+            dismissed_at: date("dismissed_at")?,
+        })
+    }
+}
+
+/// A reminder whose time has come, as sent to the frontend with the
+/// `reminder-fired` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DueReminder {
+    pub reminder_id: u32,
+    pub task_id: u32,
+    pub user_id: u32,
+    pub title: String,
+    pub due_date: Option<NaiveDateTime>,
+    pub trigger_at: NaiveDateTime,
+    /// The reminder should have fired while the app was closed.
+    pub missed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_trigger_follows_rule_then_snooze() {
+        let due = Some(at("2025-03-10 18:00"));
+        let mut reminder = Reminder {
+            id: Some(1),
+            task_id: 1,
+            rule: ReminderRule::BeforeDue(90),
+            snoozed_until: None,
+            fired_at: None,
+            dismissed_at: None,
+        };
+        assert_eq!(reminder.trigger_at(due), Some(at("2025-03-10 16:30")));
+        assert_eq!(reminder.trigger_at(None), None);
+
+        reminder.rule = ReminderRule::At(at("2025-03-09 08:00"));
+        assert_eq!(reminder.trigger_at(None), Some(at("2025-03-09 08:00")));
+
+        reminder.snoozed_until = Some(at("2025-03-09 08:10"));
+        assert_eq!(reminder.trigger_at(due), Some(at("2025-03-09 08:10")));
+    }
+
+    #[test]
+    fn test_rule_deserializes_from_frontend_shape() {
+        let rule: ReminderRule = serde_json::from_str(r#"{"before_due": 30}"#).unwrap();
+        assert_eq!(rule, ReminderRule::BeforeDue(30));
+
+        let rule: ReminderRule = serde_json::from_str(r#"{"at": "2025-03-10T09:00:00"}"#).unwrap();
+        assert_eq!(rule, ReminderRule::At(at("2025-03-10 09:00")));
+    }
+}
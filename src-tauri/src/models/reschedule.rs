@@ -0,0 +1,213 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+use crate::errors::RescheduleError;
+use crate::models::task_priority::TaskPriority;
+use crate::models::{Calendar, DayCapacity, Task};
+use crate::utils::default_due_time;
+
+/// Longest horizon, in days, the assistant plans over.
+pub const MAX_RESCHEDULE_DAYS: u32 = 60;
+
+/// How overdue work is spread over the coming days.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RescheduleOptions {
+    /// Minutes of work per day, including classes and tasks already due.
+    pub daily_capacity_minutes: u32,
+    /// Days from today over which tasks may be placed.
+    pub horizon_days: u32,
+    /// Assumed length of tasks without an estimate.
+    pub default_task_minutes: u32,
+    pub rest_days: Vec<Weekday>,
+}
+
+impl Default for RescheduleOptions {
+    fn default() -> Self {
+        Self { daily_capacity_minutes: 120, horizon_days: 14, default_task_minutes: 30, rest_days: Vec::new() }
+    }
+}
+
+impl RescheduleOptions {
+    pub fn validate(&self) -> Result<(), RescheduleError> {
+        if self.daily_capacity_minutes == 0 || self.default_task_minutes == 0 {
+            return Err(RescheduleError::InvalidOptions("Capacity and default length must be positive".into()));
+        }
+        if self.horizon_days == 0 || self.horizon_days > MAX_RESCHEDULE_DAYS {
+            return Err(RescheduleError::InvalidOptions(format!(
+                "Horizon must be between 1 and {} days",
+                MAX_RESCHEDULE_DAYS
+            )));
+        }
+        if self.rest_days.len() >= 7 {
+            return Err(RescheduleError::InvalidOptions("At least one day must be available".into()));
+        }
+        Ok(())
+    }
+
+    /// The horizon from `today`, skipping rest days, with `busy` minutes
+    /// taken off the daily capacity.
+    pub fn days(&self, today: NaiveDate, busy: impl Fn(NaiveDate) -> u32) -> Vec<DayCapacity> {
+        today
+            .iter_days()
+            .take(self.horizon_days as usize)
+            .filter(|date| !self.rest_days.contains(&date.weekday()))
+            .map(|date| DayCapacity { date, minutes: self.daily_capacity_minutes.saturating_sub(busy(date)) })
+            .collect()
+    }
+}
+
+/// A proposed new due date. `apply_reschedule` takes the accepted changes
+/// back and refuses tasks whose due date moved since the preview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RescheduleChange {
+    pub task_id: u32,
+    pub title: String,
+    pub priority: TaskPriority,
+    pub minutes: u32,
+    pub old_due_date: Option<NaiveDateTime>,
+    pub new_due_date: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ReschedulePreview {
+    pub changes: Vec<RescheduleChange>,
+    /// Overdue tasks that did not fit in the horizon; their due dates stay.
+    pub unscheduled: Vec<u32>,
+}
+
+impl ReschedulePreview {
+    /// Places `tasks`, highest priority and most overdue first, on the
+    /// earliest day with room for them. A task longer than a whole day takes
+    /// the first day nothing else was placed on. `due_on` picks the new due
+    /// date on the chosen day.
+    pub fn new(
+        tasks: &[Task],
+        days: &[DayCapacity],
+        default_minutes: u32,
+        due_on: impl Fn(&Task, NaiveDate) -> NaiveDateTime,
+    ) -> Self {
+        let mut tasks: Vec<&Task> = tasks.iter().collect();
+        tasks.sort_by_key(|task| (Reverse(task.priority), task.due_date, task.id));
+        let mut room: Vec<u32> = days.iter().map(|day| day.minutes).collect();
+        let mut untouched = vec![true; days.len()];
+        let mut preview = Self::default();
+
+        for task in tasks {
+            let Some(task_id) = task.id else { continue };
+            let minutes = task.estimated_minutes.unwrap_or(default_minutes);
+            let day = (0..days.len())
+                .find(|&i| room[i] >= minutes)
+                .or_else(|| (0..days.len()).find(|&i| untouched[i] && room[i] > 0));
+            match day {
+                Some(i) => {
+                    room[i] = room[i].saturating_sub(minutes);
+                    untouched[i] = false;
+                    preview.changes.push(RescheduleChange {
+                        task_id,
+                        title: task.title.clone(),
+                        priority: task.priority,
+                        minutes,
+                        old_due_date: task.due_date,
+                        new_due_date: due_on(task, days[i].date),
+                    });
+                }
+                None => preview.unscheduled.push(task_id),
+            }
+        }
+        preview
+    }
+}
+
+/// Moves `due` to the local `date`, keeping its local time of day unless
+/// that has already passed, in which case the task is due at the end of the
+/// day.
+pub fn move_due_to(due: NaiveDateTime, date: NaiveDate, now: NaiveDateTime, calendar: &Calendar) -> NaiveDateTime {
+    let moved = calendar.to_utc(date.and_time(calendar.to_local(due).time()));
+    if moved > now {
+        return moved;
+    }
+    calendar.to_utc(date.and_time(default_due_time())).max(now + Duration::minutes(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserSettings;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn task(id: u32, priority: TaskPriority, due: &str, minutes: Option<u32>) -> Task {
+        let mut task = Task::new(format!("Task {}", id), 1, None, Some(priority)).unwrap();
+        task.id = Some(id);
+        task.due_date = Some(at(due));
+        task.estimated_minutes = minutes;
+        task
+    }
+
+    fn days(minutes: &[u32]) -> Vec<DayCapacity> {
+        date("2025-03-10").iter_days().zip(minutes).map(|(date, minutes)| DayCapacity { date, minutes: *minutes }).collect()
+    }
+
+    fn placed(preview: &ReschedulePreview) -> Vec<(u32, u32)> {
+        preview.changes.iter().map(|change| (change.task_id, change.new_due_date.day())).collect()
+    }
+
+    #[test]
+    fn test_high_priority_work_goes_first() {
+        let tasks = [
+            task(1, TaskPriority::Low, "2025-03-01 10:00", Some(60)),
+            task(2, TaskPriority::High, "2025-03-05 10:00", Some(60)),
+            task(3, TaskPriority::High, "2025-03-04 10:00", None),
+            task(4, TaskPriority::Medium, "2025-03-02 10:00", Some(90)),
+        ];
+
+        let preview = ReschedulePreview::new(&tasks, &days(&[120, 30, 120]), 30, |_, date| date.and_hms_opt(9, 0, 0).unwrap());
+
+        assert_eq!(placed(&preview), [(3, 10), (2, 10), (4, 12), (1, 11)]);
+        assert_eq!(preview.changes[0].minutes, 30);
+        assert_eq!(preview.changes[3].old_due_date, Some(at("2025-03-01 10:00")));
+        assert!(preview.unscheduled.is_empty());
+    }
+
+    #[test]
+    fn test_long_tasks_take_a_free_day_and_the_rest_waits() {
+        let tasks = [
+            task(1, TaskPriority::High, "2025-03-01 10:00", Some(300)),
+            task(2, TaskPriority::High, "2025-03-02 10:00", Some(60)),
+            task(3, TaskPriority::Low, "2025-03-03 10:00", Some(100)),
+        ];
+
+        let preview = ReschedulePreview::new(&tasks, &days(&[120, 60]), 30, |_, date| date.and_hms_opt(9, 0, 0).unwrap());
+
+        assert_eq!(placed(&preview), [(1, 10), (2, 11)]);
+        assert_eq!(preview.unscheduled, [3]);
+    }
+
+    #[test]
+    fn test_move_due_keeps_time_unless_past() {
+        let calendar = UserSettings { utc_offset_minutes: Some(60), ..UserSettings::new(1) }.calendar();
+        let now = at("2025-03-10 15:00");
+        let moved = |due, day| move_due_to(at(due), date(day), now, &calendar);
+
+        assert_eq!(moved("2025-03-01 17:00", "2025-03-10"), at("2025-03-10 17:00"));
+        assert_eq!(moved("2025-03-01 08:00", "2025-03-10"), at("2025-03-10 22:59"));
+        assert_eq!(moved("2025-03-01 08:00", "2025-03-11"), at("2025-03-11 08:00"));
+    }
+
+    #[test]
+    fn test_validate_options() {
+        assert!(RescheduleOptions::default().validate().is_ok());
+        let zero = RescheduleOptions { daily_capacity_minutes: 0, ..Default::default() };
+        let long = RescheduleOptions { horizon_days: MAX_RESCHEDULE_DAYS + 1, ..Default::default() };
+        assert!(zero.validate().is_err());
+        assert!(long.validate().is_err());
+    }
+}
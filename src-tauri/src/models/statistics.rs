@@ -0,0 +1,347 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::StatisticsError;
+use crate::models::task_priority::TaskPriority;
+use crate::models::Calendar;
+
+/// Label used in `time_per_tag` for time logged on tasks without tags.
+pub const UNTAGGED: &str = "Untagged";
+
+/// Width of the buckets along a chart's x axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsGrouping {
+    Day,
+    Week,
+}
+
+impl StatsGrouping {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+
+    /// First day of the bucket containing `date`.
+    pub fn bucket(&self, date: NaiveDate, calendar: &Calendar) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => calendar.week_start_of(date),
+        }
+    }
+
+    fn step(&self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::days(7),
+        }
+    }
+}
+
+impl FromStr for StatsGrouping {
+    type Err = StatisticsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "day" | "daily" => Ok(Self::Day),
+            "week" | "weekly" => Ok(Self::Week),
+            _ => Err(StatisticsError::InvalidGrouping(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for StatsGrouping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A task in a completed status. Times are UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedTask {
+    pub priority: TaskPriority,
+    pub created_at: NaiveDateTime,
+    pub completed_at: NaiveDateTime,
+    pub due_date: Option<NaiveDateTime>,
+}
+
+impl CompletedTask {
+    /// `None` for tasks without a due date.
+    pub fn on_time(&self) -> Option<bool> {
+        self.due_date.map(|due| self.completed_at <= due)
+    }
+
+    fn cycle_hours(&self) -> f64 {
+        (self.completed_at - self.created_at).num_minutes().max(0) as f64 / 60.0
+    }
+}
+
+/// A finished time log with the names of its task's tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedMinutes {
+    pub started_at: NaiveDateTime,
+    pub minutes: u32,
+    pub tags: Vec<String>,
+}
+
+/// One bar of a categorical chart.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryValue {
+    pub label: String,
+    pub value: f64,
+    /// How many tasks or logs went into `value`.
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklySummary {
+    pub week_start: NaiveDate,
+    pub tasks_completed: u32,
+    /// Tasks without a due date are neither on time nor late.
+    pub on_time: u32,
+    pub late: u32,
+    pub on_time_rate: Option<f64>,
+    pub minutes_logged: u32,
+    pub average_cycle_hours: Option<f64>,
+    pub top_tag: Option<String>,
+}
+
+/// Chart-ready statistics for `from..=to` in the user's calendar. The
+/// per-bucket vectors line up with `buckets`, which includes empty ones.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Statistics {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub grouping: StatsGrouping,
+    pub buckets: Vec<NaiveDate>,
+    pub completed: Vec<u32>,
+    /// Tasks without a due date count as completed only.
+    pub on_time: Vec<u32>,
+    pub late: Vec<u32>,
+    /// `None` for buckets where nothing was completed.
+    pub on_time_rate: Vec<Option<f64>>,
+    pub minutes_logged: Vec<u32>,
+    /// Average hours from creation to completion, by priority.
+    pub cycle_time: Vec<CategoryValue>,
+    /// Minutes per tag, most first. A log counts towards each of its task's tags.
+    pub time_per_tag: Vec<CategoryValue>,
+    pub weekly: Vec<WeeklySummary>,
+}
+
+impl Statistics {
+    pub fn new(
+        from: NaiveDate,
+        to: NaiveDate,
+        grouping: StatsGrouping,
+        completed: &[CompletedTask],
+        logged: &[LoggedMinutes],
+        calendar: &Calendar,
+    ) -> Self {
+        let in_range = |at: &NaiveDateTime| {
+            let date = calendar.local_date(*at);
+            (from..=to).contains(&date).then_some(date)
+        };
+        let completed: Vec<(NaiveDate, &CompletedTask)> = completed
+            .iter()
+            .filter_map(|task| in_range(&task.completed_at).map(|date| (date, task)))
+            .collect();
+        let logged: Vec<(NaiveDate, &LoggedMinutes)> = logged
+            .iter()
+            .filter_map(|log| in_range(&log.started_at).map(|date| (date, log)))
+            .collect();
+
+        let buckets = bucket_starts(grouping, from, to, calendar);
+        let index = |date: NaiveDate| {
+            let bucket = grouping.bucket(date, calendar);
+            buckets.iter().position(|b| *b == bucket).unwrap_or(0)
+        };
+        let mut counts = vec![0; buckets.len()];
+        let mut on_time = vec![0; buckets.len()];
+        let mut late = vec![0; buckets.len()];
+        let mut minutes_logged = vec![0; buckets.len()];
+        for (date, task) in &completed {
+            counts[index(*date)] += 1;
+            match task.on_time() {
+                Some(true) => on_time[index(*date)] += 1,
+                Some(false) => late[index(*date)] += 1,
+                None => {}
+            }
+        }
+        for (date, log) in &logged {
+            minutes_logged[index(*date)] += log.minutes;
+        }
+
+        let weekly = bucket_starts(StatsGrouping::Week, from, to, calendar)
+            .into_iter()
+            .map(|week_start| {
+                let week_end = week_start + Duration::days(6);
+                let in_week = |date: &NaiveDate| (week_start..=week_end).contains(date);
+                let tasks: Vec<&CompletedTask> =
+                    completed.iter().filter(|(date, _)| in_week(date)).map(|(_, task)| *task).collect();
+                let logs: Vec<&LoggedMinutes> =
+                    logged.iter().filter(|(date, _)| in_week(date)).map(|(_, log)| *log).collect();
+                WeeklySummary::new(week_start, &tasks, &logs)
+            })
+            .collect();
+
+        let all_tasks: Vec<&CompletedTask> = completed.iter().map(|(_, task)| *task).collect();
+        let all_logs: Vec<&LoggedMinutes> = logged.iter().map(|(_, log)| *log).collect();
+        Self {
+            from,
+            to,
+            grouping,
+            completed: counts,
+            on_time_rate: on_time.iter().zip(&late).map(|(a, b)| rate(*a, *b)).collect(),
+            buckets,
+            on_time,
+            late,
+            minutes_logged,
+            cycle_time: cycle_time(&all_tasks),
+            time_per_tag: time_per_tag(&all_logs),
+            weekly,
+        }
+    }
+}
+
+impl WeeklySummary {
+    fn new(week_start: NaiveDate, tasks: &[&CompletedTask], logs: &[&LoggedMinutes]) -> Self {
+        let on_time = tasks.iter().filter(|task| task.on_time() == Some(true)).count() as u32;
+        let late = tasks.iter().filter(|task| task.on_time() == Some(false)).count() as u32;
+        Self {
+            week_start,
+            tasks_completed: tasks.len() as u32,
+            on_time,
+            late,
+            on_time_rate: rate(on_time, late),
+            minutes_logged: logs.iter().map(|log| log.minutes).sum(),
+            average_cycle_hours: (!tasks.is_empty())
+                .then(|| tasks.iter().map(|task| task.cycle_hours()).sum::<f64>() / tasks.len() as f64),
+            top_tag: time_per_tag(logs)
+                .into_iter()
+                .find(|tag| tag.label != UNTAGGED)
+                .map(|tag| tag.label),
+        }
+    }
+}
+
+/// First days of the buckets covering `from..=to`.
+fn bucket_starts(grouping: StatsGrouping, from: NaiveDate, to: NaiveDate, calendar: &Calendar) -> Vec<NaiveDate> {
+    let mut buckets = Vec::new();
+    let mut bucket = grouping.bucket(from, calendar);
+    while bucket <= to {
+        buckets.push(bucket);
+        bucket += grouping.step();
+    }
+    buckets
+}
+
+fn rate(on_time: u32, late: u32) -> Option<f64> {
+    let total = on_time + late;
+    (total > 0).then(|| on_time as f64 / total as f64)
+}
+
+fn cycle_time(tasks: &[&CompletedTask]) -> Vec<CategoryValue> {
+    let mut by_priority: BTreeMap<TaskPriority, (f64, u32)> = BTreeMap::new();
+    for task in tasks {
+        let entry = by_priority.entry(task.priority).or_insert((0.0, 0));
+        entry.0 += task.cycle_hours();
+        entry.1 += 1;
+    }
+    by_priority
+        .into_iter()
+        .map(|(priority, (hours, count))| CategoryValue {
+            label: priority.as_str().to_string(),
+            value: hours / count as f64,
+            count,
+        })
+        .collect()
+}
+
+fn time_per_tag(logs: &[&LoggedMinutes]) -> Vec<CategoryValue> {
+    let mut by_tag: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for log in logs {
+        let tags: Vec<&str> = match log.tags.is_empty() {
+            true => vec![UNTAGGED],
+            false => log.tags.iter().map(String::as_str).collect(),
+        };
+        for tag in tags {
+            let entry = by_tag.entry(tag).or_insert((0, 0));
+            entry.0 += log.minutes;
+            entry.1 += 1;
+        }
+    }
+    let mut values: Vec<CategoryValue> = by_tag
+        .into_iter()
+        .map(|(tag, (minutes, count))| CategoryValue { label: tag.to_string(), value: minutes as f64, count })
+        .collect();
+    values.sort_by(|a, b| b.value.total_cmp(&a.value));
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UserSettings;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn calendar() -> Calendar {
+        UserSettings { utc_offset_minutes: Some(0), ..UserSettings::new(1) }.calendar()
+    }
+
+    fn task(priority: TaskPriority, created: &str, completed: &str, due: &str) -> CompletedTask {
+        CompletedTask { priority, created_at: at(created), completed_at: at(completed), due_date: Some(at(due)) }
+    }
+
+    #[test]
+    fn test_daily_series_include_empty_buckets() {
+        let tasks = [
+            task(TaskPriority::High, "2025-03-01 10:00", "2025-03-03 10:00", "2025-03-04 23:59"),
+            task(TaskPriority::High, "2025-03-01 10:00", "2025-03-05 10:00", "2025-03-04 23:59"),
+            task(TaskPriority::Low, "2025-03-01 10:00", "2025-03-01 16:00", "2025-03-01 23:59"),
+            task(TaskPriority::Low, "2025-02-01 10:00", "2025-02-02 10:00", "2025-02-03 23:59"),
+            CompletedTask { due_date: None, ..task(TaskPriority::Low, "2025-03-05 08:00", "2025-03-05 09:00", "2025-03-05 23:59") },
+        ];
+        let stats = Statistics::new(date("2025-03-03"), date("2025-03-05"), StatsGrouping::Day, &tasks, &[], &calendar());
+
+        assert_eq!(stats.buckets, vec![date("2025-03-03"), date("2025-03-04"), date("2025-03-05")]);
+        assert_eq!(stats.completed, vec![1, 0, 2]);
+        assert_eq!(stats.late, vec![0, 0, 1]);
+        assert_eq!(stats.on_time_rate, vec![Some(1.0), None, Some(0.0)]);
+        let high = stats.cycle_time.iter().find(|value| value.label == "high").unwrap();
+        assert_eq!((high.value, high.count), (72.0, 2));
+        assert_eq!(stats.cycle_time.len(), 2);
+    }
+
+    #[test]
+    fn test_weekly_grouping_and_tags() {
+        let logs = [
+            LoggedMinutes { started_at: at("2025-03-03 09:00"), minutes: 30, tags: vec!["math".into(), "exam".into()] },
+            LoggedMinutes { started_at: at("2025-03-04 09:00"), minutes: 45, tags: vec!["math".into()] },
+            LoggedMinutes { started_at: at("2025-03-10 09:00"), minutes: 100, tags: vec![] },
+        ];
+        let stats = Statistics::new(date("2025-03-05"), date("2025-03-12"), StatsGrouping::Week, &[], &logs, &calendar());
+
+        assert_eq!(stats.buckets, vec![date("2025-03-03"), date("2025-03-10")]);
+        assert_eq!(stats.minutes_logged, vec![0, 100]);
+        assert_eq!(stats.time_per_tag[0].label, UNTAGGED);
+        assert_eq!(stats.weekly[1].top_tag, None);
+
+        let stats = Statistics::new(date("2025-03-03"), date("2025-03-09"), StatsGrouping::Day, &[], &logs, &calendar());
+        let tags: Vec<(&str, f64)> = stats.time_per_tag.iter().map(|t| (t.label.as_str(), t.value)).collect();
+        assert_eq!(tags, vec![("math", 75.0), ("exam", 30.0)]);
+        assert_eq!(stats.weekly[0].minutes_logged, 75);
+        assert_eq!(stats.weekly[0].top_tag.as_deref(), Some("math"));
+    }
+}
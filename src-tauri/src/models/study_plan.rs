@@ -0,0 +1,322 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::StudyPlanError;
+use crate::models::Task;
+use crate::utils::SqliteDate;
+
+pub const DEFAULT_DAILY_CAPACITY_MINUTES: u32 = 120;
+pub const DEFAULT_BLOCK_MINUTES: u32 = 50;
+
+/// A plan to cover `topics` before `exam_date`, split into study-block tasks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StudyPlan {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub subject_id: Option<u32>,
+    pub title: String,
+    pub exam_date: NaiveDate,
+    /// Most study minutes per day, before classes and other tasks.
+    pub daily_capacity_minutes: u32,
+    /// Longest single block; longer work is split.
+    pub block_minutes: u32,
+    pub rest_days: Vec<Weekday>,
+}
+
+impl StudyPlan {
+    pub fn new(user_id: u32, title: String, exam_date: NaiveDate) -> Result<Self, StudyPlanError> {
+        let plan = Self {
+            id: None,
+            user_id,
+            subject_id: None,
+            title,
+            exam_date,
+            daily_capacity_minutes: DEFAULT_DAILY_CAPACITY_MINUTES,
+            block_minutes: DEFAULT_BLOCK_MINUTES,
+            rest_days: Vec::new(),
+        };
+        plan.validate()?;
+        Ok(plan)
+    }
+
+    pub fn validate(&self) -> Result<(), StudyPlanError> {
+        if self.title.trim().is_empty() {
+            return Err(StudyPlanError::InvalidPlan("Plan title cannot be empty".into()));
+        }
+        if self.daily_capacity_minutes == 0 || self.block_minutes == 0 {
+            return Err(StudyPlanError::InvalidPlan("Capacity and block length must be positive".into()));
+        }
+        if self.rest_days.len() >= 7 {
+            return Err(StudyPlanError::InvalidPlan("At least one day a week must be available".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Rest days are stored as weekday numbers (0 = Monday), comma separated.
+pub fn format_rest_days(days: &[Weekday]) -> String {
+    days.iter()
+        .map(|day| day.num_days_from_monday().to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_rest_days(value: &str) -> Result<Vec<Weekday>, String> {
+    value
+        .split(',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<u8>()
+                .ok()
+                .and_then(|day| Weekday::try_from(day).ok())
+                .ok_or_else(|| format!("Invalid rest day: {}", part))
+        })
+        .collect()
+}
+
+impl<'a> TryFrom<&Row<'a>> for StudyPlan {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        let rest_days: String = row.get("rest_days")?;
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            subject_id: row.get("subject_id")?,
+            title: row.get("title")?,
+            exam_date: row.get::<_, SqliteDate>("exam_date")?.into(),
+            daily_capacity_minutes: row.get("daily_capacity_minutes")?,
+            block_minutes: row.get("block_minutes")?,
+            rest_days: parse_rest_days(&rest_days).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+            })?,
+        })
+    }
+}
+
+/// A topic to study and the minutes it still needs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicEstimate {
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub name: String,
+    pub minutes: u32,
+}
+
+/// Study minutes free on one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayCapacity {
+    pub date: NaiveDate,
+    pub minutes: u32,
+}
+
+/// Days from `start` up to the day before `exam_date`, skipping rest days,
+/// with `busy` minutes (classes, other tasks) taken off the daily capacity.
+pub fn available_days(plan: &StudyPlan, start: NaiveDate, busy: impl Fn(NaiveDate) -> u32) -> Vec<DayCapacity> {
+    std::iter::successors(Some(start), |date| Some(*date + Duration::days(1)))
+        .take_while(|date| *date < plan.exam_date)
+        .filter(|date| !plan.rest_days.contains(&date.weekday()))
+        .map(|date| DayCapacity {
+            date,
+            minutes: plan.daily_capacity_minutes.saturating_sub(busy(date)),
+        })
+        .collect()
+}
+
+/// One study session of a topic on a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StudyBlock {
+    pub date: NaiveDate,
+    /// Index into the topics passed to [`plan_study`].
+    pub topic: usize,
+    pub minutes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct PlannedStudy {
+    pub blocks: Vec<StudyBlock>,
+    /// Minutes per topic index that did not fit before the exam.
+    pub unscheduled: Vec<(usize, u32)>,
+}
+
+/// Spreads the topics, in order, over `days` as evenly as capacity allows.
+///
+/// Each day is filled up to a common level (or its capacity if lower), the
+/// smallest level at which everything fits, so the load is balanced instead
+/// of front-loaded. Topics are cut into blocks of at most `block_minutes`.
+/// The result depends only on the inputs.
+pub fn plan_study(topics: &[TopicEstimate], days: &[DayCapacity], block_minutes: u32) -> PlannedStudy {
+    let total: u32 = topics.iter().map(|topic| topic.minutes).sum();
+    let level = balanced_level(days, total);
+
+    let mut remaining: Vec<u32> = topics.iter().map(|topic| topic.minutes).collect();
+    let mut topic = 0;
+    let mut blocks = Vec::new();
+
+    for day in days {
+        let mut quota = day.minutes.min(level);
+        while quota > 0 {
+            while topic < remaining.len() && remaining[topic] == 0 {
+                topic += 1;
+            }
+            if topic == remaining.len() {
+                break;
+            }
+            let minutes = quota.min(remaining[topic]).min(block_minutes.max(1));
+            blocks.push(StudyBlock { date: day.date, topic, minutes });
+            remaining[topic] -= minutes;
+            quota -= minutes;
+        }
+    }
+
+    PlannedStudy {
+        blocks,
+        unscheduled: remaining
+            .into_iter()
+            .enumerate()
+            .filter(|(_, minutes)| *minutes > 0)
+            .collect(),
+    }
+}
+
+/// Smallest per-day level whose capped sum covers `total`, or the largest
+/// capacity when nothing would be enough.
+fn balanced_level(days: &[DayCapacity], total: u32) -> u32 {
+    let fits = |level: u32| days.iter().map(|day| day.minutes.min(level)).sum::<u32>() >= total;
+    let (mut low, mut high) = (0, days.iter().map(|day| day.minutes).max().unwrap_or(0));
+    if !fits(high) {
+        return high;
+    }
+    while low < high {
+        let mid = (low + high) / 2;
+        if fits(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    high
+}
+
+/// Progress of one topic across completed and pending blocks.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicProgress {
+    pub topic: TopicEstimate,
+    pub done_minutes: u32,
+    pub scheduled_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StudyPlanView {
+    pub plan: StudyPlan,
+    pub topics: Vec<TopicProgress>,
+    pub tasks: Vec<Task>,
+    /// Minutes that did not fit before the exam at the last (re)planning.
+    pub unscheduled_minutes: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn topic(name: &str, minutes: u32) -> TopicEstimate {
+        TopicEstimate { id: None, name: name.to_string(), minutes }
+    }
+
+    fn plan() -> StudyPlan {
+        // Exam on Monday 2025-03-17; Saturdays and Sundays off.
+        let mut plan = StudyPlan::new(1, "Physics midterm".to_string(), date("2025-03-17")).unwrap();
+        plan.rest_days = vec![Weekday::Sat, Weekday::Sun];
+        plan
+    }
+
+    fn minutes_per_day(planned: &PlannedStudy) -> Vec<(NaiveDate, u32)> {
+        let mut days: Vec<(NaiveDate, u32)> = Vec::new();
+        for block in &planned.blocks {
+            match days.last_mut() {
+                Some((date, minutes)) if *date == block.date => *minutes += block.minutes,
+                _ => days.push((block.date, block.minutes)),
+            }
+        }
+        days
+    }
+
+    #[test]
+    fn test_available_days_skip_rest_days_and_busy_time() {
+        let days = available_days(&plan(), date("2025-03-13"), |day| if day == date("2025-03-14") { 90 } else { 0 });
+        assert_eq!(
+            days,
+            [
+                DayCapacity { date: date("2025-03-13"), minutes: 120 },
+                DayCapacity { date: date("2025-03-14"), minutes: 30 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_balances_load_across_days() {
+        let days = available_days(&plan(), date("2025-03-10"), |_| 0);
+        let planned = plan_study(&[topic("Kinematics", 150), topic("Dynamics", 150)], &days, 50);
+
+        assert_eq!(
+            minutes_per_day(&planned),
+            [
+                (date("2025-03-10"), 60),
+                (date("2025-03-11"), 60),
+                (date("2025-03-12"), 60),
+                (date("2025-03-13"), 60),
+                (date("2025-03-14"), 60),
+            ]
+        );
+        assert!(planned.blocks.iter().all(|block| block.minutes <= 50));
+        assert!(planned.unscheduled.is_empty());
+        // Topics stay in order.
+        assert_eq!(planned.blocks.first().unwrap().topic, 0);
+        assert_eq!(planned.blocks.last().unwrap().topic, 1);
+    }
+
+    #[test]
+    fn test_busy_days_get_less() {
+        let days = [
+            DayCapacity { date: date("2025-03-10"), minutes: 20 },
+            DayCapacity { date: date("2025-03-11"), minutes: 120 },
+            DayCapacity { date: date("2025-03-12"), minutes: 120 },
+        ];
+        let planned = plan_study(&[topic("Optics", 180)], &days, 60);
+
+        assert_eq!(
+            minutes_per_day(&planned),
+            [(date("2025-03-10"), 20), (date("2025-03-11"), 80), (date("2025-03-12"), 80)]
+        );
+    }
+
+    #[test]
+    fn test_reports_what_does_not_fit() {
+        let days = [DayCapacity { date: date("2025-03-10"), minutes: 60 }];
+        let planned = plan_study(&[topic("Optics", 45), topic("Waves", 45)], &days, 60);
+
+        assert_eq!(planned.blocks.iter().map(|block| block.minutes).sum::<u32>(), 60);
+        assert_eq!(planned.unscheduled, [(1, 30)]);
+    }
+
+    #[test]
+    fn test_is_deterministic() {
+        let days = available_days(&plan(), date("2025-03-03"), |day| day.day() % 3 * 20);
+        let topics = [topic("A", 95), topic("B", 230), topic("C", 40)];
+
+        assert_eq!(plan_study(&topics, &days, 45), plan_study(&topics, &days, 45));
+    }
+
+    #[test]
+    fn test_rest_days_round_trip() {
+        assert_eq!(format_rest_days(&[Weekday::Sat, Weekday::Sun]), "5,6");
+        assert_eq!(parse_rest_days("5,6").unwrap(), [Weekday::Sat, Weekday::Sun]);
+        assert_eq!(parse_rest_days("").unwrap(), []);
+        assert!(parse_rest_days("9").is_err());
+    }
+}
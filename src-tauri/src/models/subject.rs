@@ -0,0 +1,132 @@
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SubjectError;
+use crate::models::{tag::is_valid_color, GradingScheme, Task, DEFAULT_PASSING_GRADE};
+
+/// A school subject or course. Tasks (and through them, logged study time)
+/// belong to at most one subject.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subject {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub name: String,
+    pub teacher: Option<String>,
+    pub color: String,
+    /// Free-form label such as "2025/1".
+    pub term: Option<String>,
+    pub credit_hours: Option<u32>,
+    pub weekly_goal_minutes: Option<u32>,
+    #[serde(default)]
+    pub grading_scheme: GradingScheme,
+    /// Fraction of the maximum grade needed to pass; see `passing_grade()`.
+    #[serde(default)]
+    pub passing_grade: Option<f64>,
+}
+
+impl Subject {
+    pub fn new(user_id: u32, name: String, color: String) -> Result<Self, SubjectError> {
+        let subject = Self {
+            id: None,
+            user_id,
+            name,
+            teacher: None,
+            color,
+            term: None,
+            credit_hours: None,
+            weekly_goal_minutes: None,
+            grading_scheme: GradingScheme::default(),
+            passing_grade: None,
+        };
+        subject.validate()?;
+        Ok(subject)
+    }
+
+    pub fn validate(&self) -> Result<(), SubjectError> {
+        if self.name.trim().is_empty() {
+            return Err(SubjectError::InvalidName("Subject name cannot be empty".into()));
+        }
+        if !is_valid_color(&self.color) {
+            return Err(SubjectError::InvalidColor(format!("Subject color '{}' is invalid", self.color)));
+        }
+        if self.credit_hours == Some(0) {
+            return Err(SubjectError::InvalidGoal("Credit hours must be positive".into()));
+        }
+        if self.weekly_goal_minutes == Some(0) {
+            return Err(SubjectError::InvalidGoal("Weekly goal must be positive".into()));
+        }
+        if self.passing_grade.is_some_and(|grade| !(0.0..=1.0).contains(&grade)) {
+            return Err(SubjectError::InvalidGoal("Passing grade must be between 0 and 1".into()));
+        }
+        Ok(())
+    }
+
+    pub fn passing_grade(&self) -> f64 {
+        self.passing_grade.unwrap_or(DEFAULT_PASSING_GRADE)
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for Subject {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            name: row.get("name")?,
+            teacher: row.get("teacher")?,
+            color: row.get("color")?,
+            term: row.get("term")?,
+            credit_hours: row.get("credit_hours")?,
+            weekly_goal_minutes: row.get("weekly_goal_minutes")?,
+            grading_scheme: row.get("grading_scheme")?,
+            passing_grade: row.get("passing_grade")?,
+        })
+    }
+}
+
+/// Overview of one subject: what is left to do and how this week's study
+/// time compares with the goal.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubjectDashboard {
+    pub subject: Subject,
+    pub pending_tasks: Vec<Task>,
+    pub upcoming_exams: Vec<Task>,
+    pub minutes_this_week: u32,
+    /// `minutes_this_week / weekly_goal_minutes`, when a goal is set.
+    pub goal_progress: Option<f64>,
+}
+
+impl SubjectDashboard {
+    pub fn goal_progress(minutes: u32, goal: Option<u32>) -> Option<f64> {
+        goal.map(|goal| minutes as f64 / goal as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_validates() {
+        assert!(Subject::new(1, "Physics".to_string(), "#336699".to_string()).is_ok());
+        assert!(matches!(
+            Subject::new(1, " ".to_string(), "red".to_string()),
+            Err(SubjectError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Subject::new(1, "Physics".to_string(), "not-a-color".to_string()),
+            Err(SubjectError::InvalidColor(_))
+        ));
+
+        let mut subject = Subject::new(1, "Physics".to_string(), "red".to_string()).unwrap();
+        subject.weekly_goal_minutes = Some(0);
+        assert!(matches!(subject.validate(), Err(SubjectError::InvalidGoal(_))));
+    }
+
+    #[test]
+    fn test_goal_progress() {
+        assert_eq!(SubjectDashboard::goal_progress(90, Some(180)), Some(0.5));
+        assert_eq!(SubjectDashboard::goal_progress(90, None), None);
+    }
+}
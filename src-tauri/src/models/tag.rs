@@ -28,12 +28,11 @@ impl Tag {
     }
 }
 
-fn is_valid_color(color: &str) -> bool {
+pub(crate) fn is_valid_color(color: &str) -> bool {
     let trimmed = color.trim();
     
-    if trimmed.starts_with('#') {
-        let hex = &trimmed[1..];
-        return (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_digit(16));
+    if let Some(hex) = trimmed.strip_prefix('#') {
+        return (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
     }
     
     let allowed_names = [
@@ -1,34 +1,77 @@
-use chrono::{NaiveDateTime, Utc};
-use rusqlite::{Connection, Row, types::{FromSql, FromSqlResult, ValueRef, FromSqlError}};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{Connection, Row, ToSql, types::{FromSql, FromSqlResult, ToSqlOutput, ValueRef, FromSqlError}};
 use serde::{Serialize, Deserialize};
 use std::fmt;
 use std::str::FromStr;
 
-use crate::{errors::task_errors::TaskError, utils::{truncate_to_minute, SqliteDateTime}};
+use crate::{errors::task_errors::TaskError, utils::{default_due_time, truncate_to_minute, SqliteDateTime}};
 use crate::models::tag::Tag;
 
 pub mod task_status {
     use super::*;
 
-    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    /// A workflow status key. The four built-in statuses keep their variants
+    /// so existing data and callers work unchanged; statuses users define
+    /// themselves are carried as `Custom` with their key (see `WorkflowStatus`).
+    /// Deserializing goes through `from_key`, so both the built-in names and
+    /// their keys (`"done"`, `"in_progress"`) give the built-in variants.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
     pub enum TaskStatus {
         Todo,
         InProgress,
         Done,
         Backlog,
+        #[serde(untagged)]
+        Custom(String),
     }
 
     impl TaskStatus {
-        pub fn as_str(&self) -> &'static str {
+        pub fn as_str(&self) -> &str {
             match self {
                 Self::Todo => "todo",
                 Self::InProgress => "in_progress",
                 Self::Done => "done",
                 Self::Backlog => "backlog",
+                Self::Custom(key) => key,
+            }
+        }
+
+        /// Resolves a stored or user-supplied key: built-in names as accepted
+        /// by `FromStr`, otherwise a custom key of lowercase letters, digits
+        /// and underscores.
+        pub fn from_key(key: &str) -> Result<Self, TaskError> {
+            if let Ok(status) = key.parse() {
+                return Ok(status);
+            }
+            if is_valid_key(key) {
+                Ok(Self::Custom(key.to_string()))
+            } else {
+                Err(TaskError::InvalidStatus(key.to_string()))
+            }
+        }
+
+        /// Derives a key from a display name, e.g. "Waiting for feedback"
+        /// becomes `waiting_for_feedback`.
+        pub fn key_from_name(name: &str) -> String {
+            let mut key = String::new();
+            for c in name.trim().to_lowercase().chars() {
+                if c.is_alphanumeric() {
+                    key.push(c);
+                } else if !key.is_empty() && !key.ends_with('_') {
+                    key.push('_');
+                }
             }
+            key.trim_end_matches('_').to_string()
         }
     }
 
+    fn is_valid_key(key: &str) -> bool {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c == '_' || (c.is_alphanumeric() && !c.is_uppercase()))
+    }
+
     impl FromStr for TaskStatus {
         type Err = TaskError;
     
@@ -44,11 +87,16 @@ pub mod task_status {
     }
     
 
+    impl<'de> Deserialize<'de> for TaskStatus {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let key = String::deserialize(deserializer)?;
+            TaskStatus::from_key(&key).map_err(serde::de::Error::custom)
+        }
+    }
+
     impl FromSql for TaskStatus {
         fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-            value
-                .as_str()?
-                .parse()
+            TaskStatus::from_key(value.as_str()?)
                 .map_err(|e: TaskError| FromSqlError::Other(Box::new(e)))
         }
     }
@@ -63,31 +111,67 @@ pub mod task_status {
 pub mod task_priority {
     use super::*;
 
-    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    /// Importance on a 1–5 scale. `Low`, `Medium` and `High` keep their
+    /// names and map to 2, 3 and 4, so older data and clients still work.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
     pub enum TaskPriority {
+        Lowest,
         Low,
         Medium,
         High,
+        Highest,
     }
 
     impl TaskPriority {
         pub fn as_str(&self) -> &'static str {
             match self {
+                Self::Lowest => "lowest",
                 Self::Low => "low",
                 Self::Medium => "medium",
                 Self::High => "high",
+                Self::Highest => "highest",
+            }
+        }
+
+        pub fn level(&self) -> u8 {
+            match self {
+                Self::Lowest => 1,
+                Self::Low => 2,
+                Self::Medium => 3,
+                Self::High => 4,
+                Self::Highest => 5,
             }
         }
+
+        pub fn from_level(level: u8) -> Result<Self, TaskError> {
+            match level {
+                1 => Ok(Self::Lowest),
+                2 => Ok(Self::Low),
+                3 => Ok(Self::Medium),
+                4 => Ok(Self::High),
+                5 => Ok(Self::Highest),
+                _ => Err(TaskError::InvalidPriority(format!(
+                    "Priority must be between 1 and 5, got {}", level
+                ))),
+            }
+        }
+
+        /// Counts as "important" in the Eisenhower matrix.
+        pub fn is_important(&self) -> bool {
+            *self >= Self::High
+        }
     }
 
     impl FromStr for TaskPriority {
         type Err = TaskError;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s.to_lowercase().as_str() {
-                "low" => Ok(Self::Low),
-                "medium" => Ok(Self::Medium),
-                "high" => Ok(Self::High),
+            match s.trim().to_lowercase().as_str() {
+                "lowest" | "1" => Ok(Self::Lowest),
+                "low" | "2" => Ok(Self::Low),
+                "medium" | "3" => Ok(Self::Medium),
+                "high" | "4" => Ok(Self::High),
+                "highest" | "5" => Ok(Self::Highest),
                 _ => Err(TaskError::InvalidPriority(s.to_string())),
             }
         }
@@ -95,10 +179,19 @@ pub mod task_priority {
 
     impl FromSql for TaskPriority {
         fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-            value
-                .as_str()?
-                .parse()
-                .map_err(|e: TaskError| FromSqlError::Other(Box::new(e)))
+            let priority = match value {
+                ValueRef::Integer(level) => u8::try_from(level)
+                    .map_err(|_| TaskError::InvalidPriority(level.to_string()))
+                    .and_then(TaskPriority::from_level),
+                _ => value.as_str()?.parse(),
+            };
+            priority.map_err(|e| FromSqlError::Other(Box::new(e)))
+        }
+    }
+
+    impl ToSql for TaskPriority {
+        fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::from(self.level()))
         }
     }
 
@@ -109,6 +202,58 @@ pub mod task_priority {
     }
 }
 
+pub mod task_kind {
+    use super::*;
+
+    /// Exams are tasks too, so they show up on the board and in the agenda,
+    /// but subject dashboards list them separately.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum TaskKind {
+        #[default]
+        Task,
+        Exam,
+    }
+
+    impl TaskKind {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Self::Task => "task",
+                Self::Exam => "exam",
+            }
+        }
+    }
+
+    impl FromStr for TaskKind {
+        type Err = TaskError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.trim().to_lowercase().as_str() {
+                "task" => Ok(Self::Task),
+                "exam" => Ok(Self::Exam),
+                _ => Err(TaskError::InvalidKind(s.to_string())),
+            }
+        }
+    }
+
+    impl FromSql for TaskKind {
+        fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+            value.as_str()?.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+        }
+    }
+
+    impl ToSql for TaskKind {
+        fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::from(self.as_str()))
+        }
+    }
+
+    impl fmt::Display for TaskKind {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.as_str())
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: Option<u32>,
@@ -119,7 +264,30 @@ pub struct Task {
     pub priority: task_priority::TaskPriority,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
-    pub due_date: NaiveDateTime,
+    #[serde(default)]
+    pub due_date: Option<NaiveDateTime>,
+    /// Due on a whole day rather than at a time; `due_date` is then the end
+    /// of that day in the user's time zone.
+    #[serde(default)]
+    pub all_day: bool,
+    /// Kept out of day-to-day lists until this time ("don't show until").
+    #[serde(default)]
+    pub start_date: Option<NaiveDateTime>,
+    /// Position within the task's status column; see `utils::rank`.
+    #[serde(default)]
+    pub rank: String,
+    #[serde(default)]
+    pub estimated_minutes: Option<u32>,
+    #[serde(default)]
+    pub subject_id: Option<u32>,
+    #[serde(default)]
+    pub kind: task_kind::TaskKind,
+    /// When the task last entered a completed status; cleared when it leaves.
+    #[serde(default)]
+    pub completed_at: Option<NaiveDateTime>,
+    /// Open and past its due date, as of the query that loaded it.
+    #[serde(default)]
+    pub overdue: bool,
     pub tags: Vec<Tag>,
 }
 
@@ -142,13 +310,21 @@ impl<'a> TryFrom<(&Connection, &Row<'a>)> for Task {
             priority: row.get("priority")?,
             created_at: row.get::<_, SqliteDateTime>("created_at")?.into(),
             updated_at: row.get::<_, SqliteDateTime>("updated_at")?.into(),
-            due_date: row.get::<_, SqliteDateTime>("due_date")?.into(),
+            due_date: row.get::<_, Option<SqliteDateTime>>("due_date")?.map(Into::into),
+            all_day: row.get("all_day")?,
+            start_date: row.get::<_, Option<SqliteDateTime>>("start_date")?.map(Into::into),
+            rank: row.get("rank")?,
+            estimated_minutes: row.get("estimated_minutes")?,
+            subject_id: row.get("subject_id")?,
+            kind: row.get("kind")?,
+            completed_at: row.get::<_, Option<SqliteDateTime>>("completed_at")?.map(Into::into),
+            overdue: row.get("overdue")?,
             tags,
         })
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct FrontendTag {
     pub id: Option<u32>,
     pub name: String,
@@ -176,10 +352,44 @@ impl Task {
             priority: priority.unwrap_or(task_priority::TaskPriority::Medium),
             created_at: now,
             updated_at: now,
-            due_date: now,
+            due_date: None,
+            all_day: false,
+            start_date: None,
+            rank: String::new(),
+            estimated_minutes: None,
+            subject_id: None,
+            kind: task_kind::TaskKind::Task,
+            completed_at: None,
+            overdue: false,
             tags: Vec::new(),
         })
     }
+
+    /// Makes the task due on the local `date` as a whole, ending at
+    /// `default_due_time`, which `to_utc` converts to stored time.
+    pub fn set_due_day(&mut self, date: NaiveDate, to_utc: impl Fn(NaiveDateTime) -> NaiveDateTime) {
+        self.due_date = Some(to_utc(date.and_time(default_due_time())));
+        self.all_day = true;
+    }
+
+    /// A start date may not come after the due date, and an all-day task
+    /// needs a due date.
+    pub fn check_dates(&self) -> Result<(), TaskError> {
+        if self.all_day && self.due_date.is_none() {
+            return Err(TaskError::InvalidDate("An all-day task needs a due date".to_string()));
+        }
+        if let (Some(start), Some(due)) = (self.start_date, self.due_date) {
+            if start > due {
+                return Err(TaskError::InvalidDate("Start date is after the due date".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open and due before `now`.
+    pub fn is_overdue(&self, now: NaiveDateTime) -> bool {
+        self.completed_at.is_none() && self.due_date.is_some_and(|due| due < now)
+    }
 }
 
 #[cfg(test)]
@@ -291,6 +501,59 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_task_status_from_key() {
+        assert_eq!(
+            task_status::TaskStatus::from_key("in_progress").unwrap(),
+            task_status::TaskStatus::InProgress
+        );
+        assert_eq!(
+            task_status::TaskStatus::from_key("waiting_for_feedback").unwrap(),
+            task_status::TaskStatus::Custom("waiting_for_feedback".to_string())
+        );
+        assert!(task_status::TaskStatus::from_key("Waiting for feedback").is_err());
+        assert!(task_status::TaskStatus::from_key("").is_err());
+    }
+
+    #[test]
+    fn test_task_status_key_from_name() {
+        assert_eq!(
+            task_status::TaskStatus::key_from_name("  Waiting for feedback! "),
+            "waiting_for_feedback"
+        );
+        assert_eq!(task_status::TaskStatus::key_from_name("Revisão"), "revisão");
+        assert_eq!(task_status::TaskStatus::key_from_name("--"), "");
+    }
+
+    #[test]
+    fn test_task_status_serde_keeps_builtin_names() {
+        let statuses = vec![
+            task_status::TaskStatus::InProgress,
+            task_status::TaskStatus::Custom("reading".to_string()),
+        ];
+        let json = serde_json::to_string(&statuses).unwrap();
+        assert_eq!(json, r#"["InProgress","reading"]"#);
+
+        let parsed: Vec<task_status::TaskStatus> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, statuses);
+    }
+
+    #[test]
+    fn test_task_status_deserializes_builtin_keys() {
+        let parsed: Vec<task_status::TaskStatus> =
+            serde_json::from_str(r#"["done", "in_progress", "todo", "backlog"]"#).unwrap();
+        assert_eq!(
+            parsed,
+            [
+                task_status::TaskStatus::Done,
+                task_status::TaskStatus::InProgress,
+                task_status::TaskStatus::Todo,
+                task_status::TaskStatus::Backlog,
+            ]
+        );
+        assert!(serde_json::from_str::<task_status::TaskStatus>(r#""Not a key""#).is_err());
+    }
+
     #[test]
     fn test_task_priority_from_str() {
         assert_eq!(
@@ -306,4 +569,19 @@ mod tests {
             Err(TaskError::InvalidPriority(_))
         ));
     }
+
+    #[test]
+    fn test_task_priority_levels() {
+        assert_eq!(
+            "5".parse::<task_priority::TaskPriority>().unwrap(),
+            task_priority::TaskPriority::Highest
+        );
+        assert_eq!(task_priority::TaskPriority::Low.level(), 2);
+        assert_eq!(task_priority::TaskPriority::Medium.level(), 3);
+        assert_eq!(task_priority::TaskPriority::High.level(), 4);
+        assert!(task_priority::TaskPriority::from_level(0).is_err());
+        assert!(task_priority::TaskPriority::from_level(6).is_err());
+        assert!(task_priority::TaskPriority::High.is_important());
+        assert!(!task_priority::TaskPriority::Medium.is_important());
+    }
 }
\ No newline at end of file
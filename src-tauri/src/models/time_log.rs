@@ -0,0 +1,206 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use rusqlite::Row;
+use serde::Serialize;
+
+use crate::utils::SqliteDateTime;
+
+/// Time spent on a task: a manual entry, a finished timer, or (with no
+/// `ended_at`) a timer that is still running.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeLog {
+    pub id: Option<u32>,
+    pub task_id: u32,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+    pub minutes: Option<u32>,
+    pub note: Option<String>,
+}
+
+impl TimeLog {
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for TimeLog {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            task_id: row.get("task_id")?,
+            started_at: row.get::<_, SqliteDateTime>("started_at")?.into(),
+            ended_at: row.get::<_, Option<SqliteDateTime>>("ended_at")?.map(Into::into),
+            minutes: row.get("minutes")?,
+            note: row.get("note")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskEffort {
+    pub task_id: u32,
+    pub title: String,
+    pub estimated_minutes: Option<u32>,
+    pub actual_minutes: u32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagEffort {
+    pub tag_id: u32,
+    pub tag_name: String,
+    pub estimated_minutes: u32,
+    pub actual_minutes: u32,
+}
+
+/// Minutes logged during a week against the estimates of tasks due that week.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WeekEffort {
+    pub week_start: NaiveDate,
+    pub estimated_minutes: u32,
+    pub actual_minutes: u32,
+}
+
+/// How well estimates matched reality on completed tasks that have both an
+/// estimate and logged time.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EstimationAccuracy {
+    pub tasks_compared: u32,
+    pub total_estimated_minutes: u32,
+    pub total_actual_minutes: u32,
+    /// Mean of `min(estimate, actual) / max(estimate, actual)`: 1.0 is perfect.
+    pub accuracy: Option<f64>,
+    /// Total actual over total estimated: above 1.0 means work takes longer
+    /// than planned.
+    pub bias: Option<f64>,
+}
+
+impl EstimationAccuracy {
+    pub fn from_tasks(tasks: &[TaskEffort]) -> Self {
+        let compared: Vec<(u32, u32)> = tasks
+            .iter()
+            .filter(|task| task.completed && task.actual_minutes > 0)
+            .filter_map(|task| Some((task.estimated_minutes?, task.actual_minutes)))
+            .collect();
+
+        let total_estimated: u32 = compared.iter().map(|(estimated, _)| estimated).sum();
+        let total_actual: u32 = compared.iter().map(|(_, actual)| actual).sum();
+        let ratios: f64 = compared
+            .iter()
+            .map(|&(estimated, actual)| estimated.min(actual) as f64 / estimated.max(actual) as f64)
+            .sum();
+
+        Self {
+            tasks_compared: compared.len() as u32,
+            total_estimated_minutes: total_estimated,
+            total_actual_minutes: total_actual,
+            accuracy: (!compared.is_empty()).then(|| ratios / compared.len() as f64),
+            bias: (total_estimated > 0).then(|| total_actual as f64 / total_estimated as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EffortReport {
+    pub tasks: Vec<TaskEffort>,
+    pub tags: Vec<TagEffort>,
+    pub weeks: Vec<WeekEffort>,
+    pub accuracy: EstimationAccuracy,
+}
+
+/// Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Buckets logged minutes (by start) and estimates (by due date) into weeks,
+/// oldest first.
+pub fn weekly_effort(
+    logged: &[(NaiveDateTime, u32)],
+    estimated: &[(NaiveDateTime, u32)],
+) -> Vec<WeekEffort> {
+    fn week_of(weeks: &mut Vec<WeekEffort>, at: &NaiveDateTime) -> usize {
+        let start = week_start(at.date());
+        weeks
+            .iter()
+            .position(|week| week.week_start == start)
+            .unwrap_or_else(|| {
+                weeks.push(WeekEffort { week_start: start, estimated_minutes: 0, actual_minutes: 0 });
+                weeks.len() - 1
+            })
+    }
+
+    let mut weeks: Vec<WeekEffort> = Vec::new();
+    for (at, minutes) in logged {
+        let index = week_of(&mut weeks, at);
+        weeks[index].actual_minutes += minutes;
+    }
+    for (at, minutes) in estimated {
+        let index = week_of(&mut weeks, at);
+        weeks[index].estimated_minutes += minutes;
+    }
+    weeks.sort_by_key(|week| week.week_start);
+    weeks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn effort(estimated: Option<u32>, actual: u32, completed: bool) -> TaskEffort {
+        TaskEffort {
+            task_id: 1,
+            title: "Task".to_string(),
+            estimated_minutes: estimated,
+            actual_minutes: actual,
+            completed,
+        }
+    }
+
+    #[test]
+    fn test_accuracy_uses_completed_tasks_with_both_values() {
+        let accuracy = EstimationAccuracy::from_tasks(&[
+            effort(Some(60), 120, true),
+            effort(Some(30), 30, true),
+            effort(Some(60), 10, false),
+            effort(None, 45, true),
+            effort(Some(20), 0, true),
+        ]);
+
+        assert_eq!(accuracy.tasks_compared, 2);
+        assert_eq!(accuracy.total_estimated_minutes, 90);
+        assert_eq!(accuracy.total_actual_minutes, 150);
+        assert_eq!(accuracy.accuracy, Some(0.75));
+        assert!((accuracy.bias.unwrap() - 150.0 / 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_accuracy_without_data() {
+        let accuracy = EstimationAccuracy::from_tasks(&[effort(None, 10, true)]);
+        assert_eq!(accuracy.tasks_compared, 0);
+        assert_eq!(accuracy.accuracy, None);
+        assert_eq!(accuracy.bias, None);
+    }
+
+    #[test]
+    fn test_weekly_effort_buckets_by_monday() {
+        let weeks = weekly_effort(
+            &[(at("2025-03-09 22:00"), 30), (at("2025-03-10 08:00"), 45), (at("2025-03-16 23:59"), 15)],
+            &[(at("2025-03-12 09:00"), 90), (at("2025-03-03 09:00"), 20)],
+        );
+
+        let monday = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(
+            weeks,
+            [
+                WeekEffort { week_start: monday("2025-03-03"), estimated_minutes: 20, actual_minutes: 30 },
+                WeekEffort { week_start: monday("2025-03-10"), estimated_minutes: 90, actual_minutes: 60 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rusqlite::Row;
+use serde::Serialize;
+
+use crate::errors::TimetableError;
+use crate::utils::{SqliteDate, SqliteTime};
+
+/// A semester or other academic period; classes only happen inside it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Term {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl Term {
+    pub fn new(user_id: u32, name: String, start_date: NaiveDate, end_date: NaiveDate) -> Result<Self, TimetableError> {
+        if name.trim().is_empty() {
+            return Err(TimetableError::InvalidName("Term name cannot be empty".into()));
+        }
+        if end_date < start_date {
+            return Err(TimetableError::InvalidRange("Term ends before it starts".into()));
+        }
+        Ok(Self { id: None, user_id, name, start_date, end_date })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+}
+
+/// A weekly class in a term's timetable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassSlot {
+    pub id: Option<u32>,
+    pub term_id: u32,
+    pub subject_id: u32,
+    pub weekday: Weekday,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub room: Option<String>,
+}
+
+impl ClassSlot {
+    pub fn new(
+        term_id: u32,
+        subject_id: u32,
+        weekday: Weekday,
+        start_time: NaiveTime,
+        end_time: NaiveTime,
+        room: Option<String>,
+    ) -> Result<Self, TimetableError> {
+        if end_time <= start_time {
+            return Err(TimetableError::InvalidRange("Class ends before it starts".into()));
+        }
+        Ok(Self { id: None, term_id, subject_id, weekday, start_time, end_time, room })
+    }
+}
+
+/// Days without classes: a holiday for the whole term, or a single class
+/// cancelled when `class_id` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TermException {
+    pub id: Option<u32>,
+    pub term_id: u32,
+    pub class_id: Option<u32>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub description: String,
+}
+
+impl TermException {
+    pub fn new(
+        term_id: u32,
+        class_id: Option<u32>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        description: String,
+    ) -> Result<Self, TimetableError> {
+        if end_date < start_date {
+            return Err(TimetableError::InvalidRange("Exception ends before it starts".into()));
+        }
+        Ok(Self { id: None, term_id, class_id, start_date, end_date, description })
+    }
+
+    pub fn cancels(&self, class_id: Option<u32>, date: NaiveDate) -> bool {
+        (self.class_id.is_none() || self.class_id == class_id)
+            && self.start_date <= date
+            && date <= self.end_date
+    }
+}
+
+/// Dates in `from..=to` on which `slot` meets, skipping days outside the term
+/// and days cancelled by an exception.
+pub fn class_dates(
+    term: &Term,
+    slot: &ClassSlot,
+    exceptions: &[TermException],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<NaiveDate> {
+    let from = from.max(term.start_date);
+    let to = to.min(term.end_date);
+    let ahead = (slot.weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+
+    std::iter::successors(Some(from + Duration::days(ahead as i64)), |date| Some(*date + Duration::days(7)))
+        .take_while(|date| *date <= to)
+        .filter(|date| !exceptions.iter().any(|exception| exception.cancels(slot.id, *date)))
+        .collect()
+}
+
+/// One meeting of a class on a given day, in local wall-clock time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduledClass {
+    pub class_id: u32,
+    pub term_id: u32,
+    pub subject_id: u32,
+    pub subject_name: String,
+    pub color: String,
+    pub room: Option<String>,
+    pub date: NaiveDate,
+    pub start_at: NaiveDateTime,
+    pub end_at: NaiveDateTime,
+}
+
+/// A column of the week grid.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimetableDay {
+    pub date: NaiveDate,
+    pub classes: Vec<ScheduledClass>,
+}
+
+impl<'a> TryFrom<&Row<'a>> for Term {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            name: row.get("name")?,
+            start_date: row.get::<_, SqliteDate>("start_date")?.into(),
+            end_date: row.get::<_, SqliteDate>("end_date")?.into(),
+        })
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for ClassSlot {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        let weekday: u8 = row.get("weekday")?;
+        Ok(Self {
+            id: Some(row.get("id")?),
+            term_id: row.get("term_id")?,
+            subject_id: row.get("subject_id")?,
+            weekday: Weekday::try_from(weekday).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Integer, Box::new(e))
+            })?,
+            start_time: row.get::<_, SqliteTime>("start_time")?.into(),
+            end_time: row.get::<_, SqliteTime>("end_time")?.into(),
+            room: row.get("room")?,
+        })
+    }
+}
+
+impl<'a> TryFrom<&Row<'a>> for TermException {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            term_id: row.get("term_id")?,
+            class_id: row.get("class_id")?,
+            start_date: row.get::<_, SqliteDate>("start_date")?.into(),
+            end_date: row.get::<_, SqliteDate>("end_date")?.into(),
+            description: row.get("description")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn term() -> Term {
+        Term::new(1, "2025/1".to_string(), date("2025-03-03"), date("2025-03-31")).unwrap()
+    }
+
+    fn slot(id: u32, weekday: Weekday) -> ClassSlot {
+        let mut slot = ClassSlot::new(1, 1, weekday, time("08:00"), time("09:40"), None).unwrap();
+        slot.id = Some(id);
+        slot
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(matches!(
+            Term::new(1, "2025/1".to_string(), date("2025-03-03"), date("2025-03-02")),
+            Err(TimetableError::InvalidRange(_))
+        ));
+        assert!(matches!(
+            ClassSlot::new(1, 1, Weekday::Mon, time("10:00"), time("10:00"), None),
+            Err(TimetableError::InvalidRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_class_dates_stay_inside_term() {
+        let dates = class_dates(&term(), &slot(1, Weekday::Mon), &[], date("2025-02-01"), date("2025-04-30"));
+        assert_eq!(
+            dates,
+            [date("2025-03-03"), date("2025-03-10"), date("2025-03-17"), date("2025-03-24"), date("2025-03-31")]
+        );
+    }
+
+    #[test]
+    fn test_exceptions_cancel_classes() {
+        let holiday = TermException::new(1, None, date("2025-03-03"), date("2025-03-05"), "Carnival".to_string()).unwrap();
+        let cancelled = TermException::new(1, Some(2), date("2025-03-12"), date("2025-03-12"), "Sick".to_string()).unwrap();
+        let exceptions = [holiday, cancelled];
+
+        let wednesdays = class_dates(&term(), &slot(2, Weekday::Wed), &exceptions, date("2025-03-01"), date("2025-03-19"));
+        assert_eq!(wednesdays, [date("2025-03-19")]);
+
+        let other = class_dates(&term(), &slot(3, Weekday::Wed), &exceptions, date("2025-03-01"), date("2025-03-19"));
+        assert_eq!(other, [date("2025-03-12"), date("2025-03-19")]);
+    }
+}
@@ -0,0 +1,202 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use rusqlite::Row;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::WorkflowStatusError;
+use crate::models::{is_valid_color, task_status::TaskStatus};
+
+/// What a status means to the rest of the app, whatever the user calls it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusCategory {
+    NotStarted,
+    Active,
+    Completed,
+}
+
+impl StatusCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotStarted => "not_started",
+            Self::Active => "active",
+            Self::Completed => "completed",
+        }
+    }
+}
+
+impl FromStr for StatusCategory {
+    type Err = WorkflowStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['_', ' '], "").as_str() {
+            "notstarted" => Ok(Self::NotStarted),
+            "active" => Ok(Self::Active),
+            "completed" => Ok(Self::Completed),
+            _ => Err(WorkflowStatusError::InvalidCategory(s.to_string())),
+        }
+    }
+}
+
+impl FromSql for StatusCategory {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .parse()
+            .map_err(|e: WorkflowStatusError| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl fmt::Display for StatusCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A user's definition of one workflow status (a Kanban column).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowStatus {
+    pub id: Option<u32>,
+    pub user_id: u32,
+    pub status: TaskStatus,
+    pub name: String,
+    pub category: StatusCategory,
+    pub position: u32,
+    pub color: String,
+}
+
+/// Definitions every user starts with; they map the original four statuses.
+pub const DEFAULT_STATUSES: [(&str, &str, StatusCategory, &str); 4] = [
+    ("backlog", "Backlog", StatusCategory::NotStarted, "#9E9E9E"),
+    ("todo", "To do", StatusCategory::NotStarted, "#2196F3"),
+    ("in_progress", "In progress", StatusCategory::Active, "#FF9800"),
+    ("done", "Done", StatusCategory::Completed, "#4CAF50"),
+];
+
+impl WorkflowStatus {
+    /// A new definition whose key is derived from `name`; the repository
+    /// makes the key unique and assigns the position.
+    pub fn new(
+        user_id: u32,
+        name: String,
+        category: StatusCategory,
+        color: String,
+    ) -> Result<Self, WorkflowStatusError> {
+        validate_name(&name)?;
+        validate_color(&color)?;
+
+        let key = TaskStatus::key_from_name(&name);
+        let status = TaskStatus::from_key(&key)
+            .map_err(|_| WorkflowStatusError::InvalidName(format!("'{}' has no usable characters", name)))?;
+
+        Ok(Self {
+            id: None,
+            user_id,
+            status,
+            name,
+            category,
+            position: 0,
+            color,
+        })
+    }
+
+    pub fn defaults(user_id: u32) -> Vec<Self> {
+        DEFAULT_STATUSES
+            .iter()
+            .enumerate()
+            .map(|(position, (key, name, category, color))| Self {
+                id: None,
+                user_id,
+                status: TaskStatus::from_key(key).expect("default keys are valid"),
+                name: name.to_string(),
+                category: *category,
+                position: position as u32,
+                color: color.to_string(),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn validate_name(name: &str) -> Result<(), WorkflowStatusError> {
+    if name.trim().is_empty() {
+        return Err(WorkflowStatusError::InvalidName("Status name cannot be empty".into()));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_color(color: &str) -> Result<(), WorkflowStatusError> {
+    if !is_valid_color(color) {
+        return Err(WorkflowStatusError::InvalidColor(format!("Status color '{}' is invalid", color)));
+    }
+    Ok(())
+}
+
+impl<'a> TryFrom<&Row<'a>> for WorkflowStatus {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            user_id: row.get("user_id")?,
+            status: row.get("key")?,
+            name: row.get("name")?,
+            category: row.get("category")?,
+            position: row.get("position")?,
+            color: row.get("color")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_derives_key_from_name() {
+        let status = WorkflowStatus::new(
+            1,
+            "Waiting for feedback".to_string(),
+            StatusCategory::Active,
+            "#FFAA00".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(status.status, TaskStatus::Custom("waiting_for_feedback".to_string()));
+        assert!(status.id.is_none());
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_input() {
+        let new = |name: &str, color: &str| {
+            WorkflowStatus::new(1, name.to_string(), StatusCategory::Active, color.to_string())
+        };
+
+        assert!(matches!(new(" ", "red"), Err(WorkflowStatusError::InvalidName(_))));
+        assert!(matches!(new("!!", "red"), Err(WorkflowStatusError::InvalidName(_))));
+        assert!(matches!(new("Reading", "not-a-color"), Err(WorkflowStatusError::InvalidColor(_))));
+    }
+
+    #[test]
+    fn test_defaults_map_builtin_statuses() {
+        let defaults = WorkflowStatus::defaults(7);
+        let statuses: Vec<_> = defaults.iter().map(|d| (d.status.clone(), d.category)).collect();
+
+        assert_eq!(
+            statuses,
+            [
+                (TaskStatus::Backlog, StatusCategory::NotStarted),
+                (TaskStatus::Todo, StatusCategory::NotStarted),
+                (TaskStatus::InProgress, StatusCategory::Active),
+                (TaskStatus::Done, StatusCategory::Completed),
+            ]
+        );
+        assert!(defaults.iter().all(|d| d.user_id == 7));
+    }
+
+    #[test]
+    fn test_category_from_str() {
+        assert_eq!("not started".parse::<StatusCategory>().unwrap(), StatusCategory::NotStarted);
+        assert_eq!("Completed".parse::<StatusCategory>().unwrap(), StatusCategory::Completed);
+        assert!("finished".parse::<StatusCategory>().is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+use crate::errors::AgendaError;
+use crate::models::{Agenda, AgendaStudyBlock};
+use crate::repository::{SettingsRepository, TaskRepository, TimetableRepository};
+
+/// Longest range, in days, that `agenda` accepts.
+pub const MAX_AGENDA_DAYS: i64 = 366;
+
+fn database_error(e: impl ToString) -> AgendaError {
+    AgendaError::DatabaseError(e.to_string())
+}
+
+pub struct AgendaRepository;
+
+impl AgendaRepository {
+    /// Tasks, study blocks and class meetings on the user's local days
+    /// `from..=to`. Weekly class slots are the only recurring items and are
+    /// expanded into one meeting per matching day; tasks do not recur.
+    pub fn agenda(conn: &Connection, user_id: u32, from: NaiveDate, to: NaiveDate) -> Result<Agenda, AgendaError> {
+        if from > to {
+            return Err(AgendaError::InvalidRange(format!("{} is after {}", from, to)));
+        }
+        if (to - from).num_days() >= MAX_AGENDA_DAYS {
+            return Err(AgendaError::InvalidRange(format!("Ranges are limited to {} days", MAX_AGENDA_DAYS)));
+        }
+
+        let calendar = SettingsRepository::get(conn, user_id)?.calendar();
+        let tasks = TaskRepository::get_tasks_in_range(
+            conn,
+            user_id,
+            calendar.day_start(from),
+            calendar.day_start(to + Duration::days(1)),
+        )
+        .map_err(database_error)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT b.task_id, b.plan_id, p.title, b.minutes FROM study_plan_blocks b
+             JOIN study_plans p ON p.id = b.plan_id
+             WHERE p.user_id = ?1",
+        )?;
+        let mut blocks: HashMap<u32, (u32, String, u32)> = stmt
+            .query_map(params![user_id], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))?
+            .collect::<Result<_, _>>()?;
+
+        let mut study_blocks = Vec::new();
+        let mut others = Vec::new();
+        for task in tasks {
+            match task.id.and_then(|id| blocks.remove(&id)) {
+                Some((plan_id, plan_title, minutes)) => study_blocks.push(AgendaStudyBlock { plan_id, plan_title, minutes, task }),
+                None => others.push(task),
+            }
+        }
+
+        let classes = TimetableRepository::classes_between(conn, user_id, from, to).map_err(database_error)?;
+        Ok(Agenda::new(from, to, others, study_blocks, classes, &calendar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ClassSlot, StudyPlan, Subject, Task, Term, TopicEstimate, UserSettings};
+    use crate::repository::{StudyPlanRepository, SubjectRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+    use chrono::{NaiveDateTime, NaiveTime, Weekday};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        SettingsRepository::save(&conn, &UserSettings { utc_offset_minutes: Some(0), ..UserSettings::new(1) }).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_agenda_combines_tasks_blocks_and_classes() {
+        let conn = setup_db();
+        let mut physics = Subject::new(1, "Physics".to_string(), "blue".to_string()).unwrap();
+        SubjectRepository::create(&conn, &mut physics).unwrap();
+        let mut term = Term::new(1, "2025/1".to_string(), date("2025-03-03"), date("2025-03-31")).unwrap();
+        TimetableRepository::create_term(&conn, &mut term).unwrap();
+        let mut class =
+            ClassSlot::new(term.id.unwrap(), physics.id.unwrap(), Weekday::Mon, time("08:00"), time("09:40"), None).unwrap();
+        TimetableRepository::add_class(&conn, &mut class).unwrap();
+
+        let mut plan = StudyPlan::new(1, "Midterm".to_string(), date("2025-03-12")).unwrap();
+        plan.daily_capacity_minutes = 300;
+        plan.block_minutes = 60;
+        let topics = [TopicEstimate { id: None, name: "Optics".to_string(), minutes: 60 }];
+        UnitOfWork::run(&conn, |uow| StudyPlanRepository::create(uow, &mut plan, &topics, date("2025-03-10"), |dt| dt))
+            .unwrap();
+
+        let mut essay = Task::new("Essay".to_string(), 1, None, None).unwrap();
+        essay.due_date = Some(NaiveDateTime::parse_from_str("2025-03-11 17:00", "%Y-%m-%d %H:%M").unwrap());
+        TaskRepository::insert_task(&conn, &mut essay).unwrap();
+
+        let agenda = AgendaRepository::agenda(&conn, 1, date("2025-03-10"), date("2025-03-16")).unwrap();
+
+        assert_eq!(agenda.days.len(), 7);
+        let monday = &agenda.days[0];
+        assert_eq!(monday.classes.len(), 1);
+        assert_eq!(monday.study_blocks[0].plan_title, "Midterm");
+        assert_eq!(monday.study_blocks[0].task.title, "Midterm: Optics");
+        assert!(monday.tasks.is_empty());
+        assert_eq!(agenda.days[1].tasks[0].title, "Essay");
+        assert!(agenda.days[3..].iter().all(|day| day.tasks.is_empty() && day.study_blocks.is_empty()));
+    }
+
+    #[test]
+    fn test_rejects_bad_ranges() {
+        let conn = setup_db();
+
+        let reversed = AgendaRepository::agenda(&conn, 1, date("2025-03-02"), date("2025-03-01"));
+        let too_long = AgendaRepository::agenda(&conn, 1, date("2024-01-01"), date("2025-01-01"));
+        assert!(matches!(reversed, Err(AgendaError::InvalidRange(_))));
+        assert!(matches!(too_long, Err(AgendaError::InvalidRange(_))));
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::AssessmentError;
+use crate::models::{task_kind::TaskKind, Assessment, GradeSummary, Task};
+use crate::repository::{SubjectRepository, TaskRepository, UnitOfWork, WorkflowStatusRepository};
+use crate::utils::truncate_to_minute;
+
+const ASSESSMENT_COLUMNS: &str = "id, subject_id, title, weight, date, max_score, score, is_exam, task_id";
+
+/// Assessments and the exam tasks that mirror dated exams.
+pub struct AssessmentRepository;
+
+impl AssessmentRepository {
+    pub fn add(uow: &UnitOfWork, assessment: &mut Assessment) -> Result<(), AssessmentError> {
+        assessment.validate()?;
+        SubjectRepository::find(uow, assessment.subject_id)?;
+
+        uow.execute(
+            &format!(
+                "INSERT INTO assessments ({}) VALUES (NULL, ?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL)",
+                ASSESSMENT_COLUMNS
+            ),
+            params![
+                assessment.subject_id,
+                assessment.title,
+                assessment.weight,
+                assessment.date.map(|date| truncate_to_minute(date).format("%Y-%m-%dT%H:%M").to_string()),
+                assessment.max_score,
+                assessment.score,
+                assessment.is_exam,
+            ],
+        )?;
+        assessment.id = Some(uow.last_insert_rowid() as u32);
+        AssessmentRepository::sync_exam_task(uow, assessment)
+    }
+
+    /// Saves every field and brings the exam task in line: created when an
+    /// exam gets a date, updated when it moves, removed otherwise.
+    pub fn update(uow: &UnitOfWork, assessment: &mut Assessment) -> Result<(), AssessmentError> {
+        let id = assessment.id.ok_or_else(|| AssessmentError::DatabaseError("Assessment id missing".into()))?;
+        assessment.validate()?;
+        assessment.task_id = AssessmentRepository::find(uow, id)?.task_id;
+
+        uow.execute(
+            "UPDATE assessments SET title = ?2, weight = ?3, date = ?4, max_score = ?5, score = ?6, is_exam = ?7
+             WHERE id = ?1",
+            params![
+                id,
+                assessment.title,
+                assessment.weight,
+                assessment.date.map(|date| truncate_to_minute(date).format("%Y-%m-%dT%H:%M").to_string()),
+                assessment.max_score,
+                assessment.score,
+                assessment.is_exam,
+            ],
+        )?;
+        AssessmentRepository::sync_exam_task(uow, assessment)
+    }
+
+    pub fn record_score(conn: &Connection, assessment_id: u32, score: Option<f64>) -> Result<Assessment, AssessmentError> {
+        let mut assessment = AssessmentRepository::find(conn, assessment_id)?;
+        assessment.score = score;
+        assessment.validate()?;
+
+        conn.execute(
+            "UPDATE assessments SET score = ?1 WHERE id = ?2",
+            params![score, assessment_id],
+        )?;
+        Ok(assessment)
+    }
+
+    /// Deletes the assessment and its exam task.
+    pub fn delete(uow: &UnitOfWork, assessment_id: u32) -> Result<bool, AssessmentError> {
+        let assessment = match AssessmentRepository::find(uow, assessment_id) {
+            Ok(assessment) => assessment,
+            Err(AssessmentError::NotFound(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        uow.execute("DELETE FROM assessments WHERE id = ?1", params![assessment_id])?;
+        if let Some(task_id) = assessment.task_id {
+            TaskRepository::delete_task(uow, task_id).map_err(|e| AssessmentError::DatabaseError(e.to_string()))?;
+        }
+        Ok(true)
+    }
+
+    pub fn find(conn: &Connection, assessment_id: u32) -> Result<Assessment, AssessmentError> {
+        conn.query_row(
+            &format!("SELECT {} FROM assessments WHERE id = ?1", ASSESSMENT_COLUMNS),
+            params![assessment_id],
+            |row| Assessment::try_from(row),
+        )
+        .optional()?
+        .ok_or(AssessmentError::NotFound(assessment_id))
+    }
+
+    /// A subject's assessments, dated ones first in date order.
+    pub fn list_for_subject(conn: &Connection, subject_id: u32) -> Result<Vec<Assessment>, AssessmentError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM assessments WHERE subject_id = ?1 ORDER BY date IS NULL, date, id",
+            ASSESSMENT_COLUMNS
+        ))?;
+        let assessments = stmt
+            .query_map(params![subject_id], |row| Assessment::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(assessments)
+    }
+
+    /// Grade summary against `target` (a fraction), defaulting to the
+    /// subject's passing grade.
+    pub fn summary(conn: &Connection, subject_id: u32, target: Option<f64>) -> Result<GradeSummary, AssessmentError> {
+        let subject = SubjectRepository::find(conn, subject_id)?;
+        let assessments = AssessmentRepository::list_for_subject(conn, subject_id)?;
+        Ok(GradeSummary::new(
+            &assessments,
+            subject.grading_scheme,
+            target.unwrap_or_else(|| subject.passing_grade()),
+        ))
+    }
+
+    fn sync_exam_task(uow: &UnitOfWork, assessment: &mut Assessment) -> Result<(), AssessmentError> {
+        let id = assessment.id.unwrap_or_default();
+        let existing = match assessment.task_id {
+            Some(task_id) => TaskRepository::get_task_by_id(uow, task_id).ok(),
+            None => None,
+        };
+
+        match (assessment.is_exam, assessment.date, existing) {
+            (true, Some(date), Some(mut task)) => {
+                task.title = assessment.title.clone();
+                task.due_date = Some(date);
+                task.updated_at = truncate_to_minute(Utc::now().naive_utc());
+                TaskRepository::restore_task(uow, &task).map_err(|e| AssessmentError::DatabaseError(e.to_string()))?;
+            }
+            (true, Some(date), None) => {
+                let subject = SubjectRepository::find(uow, assessment.subject_id)?;
+                let mut task = Task::new(assessment.title.clone(), subject.user_id, None, None)
+                    .map_err(|e| AssessmentError::InvalidTitle(e.to_string()))?;
+                task.status = WorkflowStatusRepository::initial_status(uow, subject.user_id)
+                    .map_err(|e| AssessmentError::DatabaseError(e.to_string()))?;
+                task.kind = TaskKind::Exam;
+                task.subject_id = Some(assessment.subject_id);
+                task.due_date = Some(date);
+                TaskRepository::insert_task(uow, &mut task).map_err(|e| AssessmentError::DatabaseError(e.to_string()))?;
+                assessment.task_id = task.id;
+            }
+            (_, _, Some(task)) => {
+                TaskRepository::delete_task(uow, task.id.unwrap_or_default())
+                    .map_err(|e| AssessmentError::DatabaseError(e.to_string()))?;
+                assessment.task_id = None;
+            }
+            (_, _, None) => assessment.task_id = None,
+        }
+
+        uow.execute(
+            "UPDATE assessments SET task_id = ?1 WHERE id = ?2",
+            params![assessment.task_id, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Subject;
+    use crate::utils::initialize_database;
+    use chrono::NaiveDateTime;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> (Connection, u32) {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        let mut subject = Subject::new(1, "Physics".to_string(), "blue".to_string()).unwrap();
+        SubjectRepository::create(&conn, &mut subject).unwrap();
+        (conn, subject.id.unwrap())
+    }
+
+    fn add(conn: &Connection, assessment: &mut Assessment) {
+        UnitOfWork::run(conn, |uow| AssessmentRepository::add(uow, assessment)).unwrap();
+    }
+
+    #[test]
+    fn test_exam_dates_become_exam_tasks() {
+        let (conn, subject_id) = setup_db();
+        let mut exam = Assessment::new(subject_id, "Midterm".to_string(), 4.0, 10.0).unwrap();
+        exam.is_exam = true;
+        exam.date = Some(at("2025-04-10 08:00"));
+        add(&conn, &mut exam);
+
+        let task = TaskRepository::get_task_by_id(&conn, exam.task_id.unwrap()).unwrap();
+        assert_eq!(task.kind, TaskKind::Exam);
+        assert_eq!(task.subject_id, Some(subject_id));
+        assert_eq!(task.due_date, Some(at("2025-04-10 08:00")));
+
+        exam.date = Some(at("2025-04-17 08:00"));
+        UnitOfWork::run(&conn, |uow| AssessmentRepository::update(uow, &mut exam)).unwrap();
+        let moved = TaskRepository::get_task_by_id(&conn, exam.task_id.unwrap()).unwrap();
+        assert_eq!(moved.id, task.id);
+        assert_eq!(moved.due_date, Some(at("2025-04-17 08:00")));
+
+        assert!(UnitOfWork::run(&conn, |uow| AssessmentRepository::delete(uow, exam.id.unwrap())).unwrap());
+        assert!(TaskRepository::get_task_by_id(&conn, task.id.unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_undated_work_has_no_task() {
+        let (conn, subject_id) = setup_db();
+        let mut homework = Assessment::new(subject_id, "Homework".to_string(), 1.0, 10.0).unwrap();
+        add(&conn, &mut homework);
+        assert_eq!(homework.task_id, None);
+
+        assert!(matches!(
+            UnitOfWork::run(&conn, |uow| AssessmentRepository::add(
+                uow,
+                &mut Assessment::new(99, "Quiz".to_string(), 1.0, 10.0).unwrap()
+            )),
+            Err(AssessmentError::SubjectNotFound(99))
+        ));
+    }
+
+    #[test]
+    fn test_summary_uses_subject_passing_grade() {
+        let (conn, subject_id) = setup_db();
+        let mut subject = SubjectRepository::find(&conn, subject_id).unwrap();
+        subject.passing_grade = Some(0.7);
+        SubjectRepository::update(&conn, &subject).unwrap();
+
+        let mut first = Assessment::new(subject_id, "P1".to_string(), 1.0, 10.0).unwrap();
+        add(&conn, &mut first);
+        add(&conn, &mut Assessment::new(subject_id, "P2".to_string(), 1.0, 10.0).unwrap());
+        AssessmentRepository::record_score(&conn, first.id.unwrap(), Some(6.0)).unwrap();
+        assert!(matches!(
+            AssessmentRepository::record_score(&conn, first.id.unwrap(), Some(11.0)),
+            Err(AssessmentError::InvalidScore(_))
+        ));
+
+        let summary = AssessmentRepository::summary(&conn, subject_id, None).unwrap();
+        assert_eq!(summary.target_grade, "7.0");
+        assert_eq!(summary.required[0].score, 8.0);
+    }
+}
@@ -0,0 +1,298 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::errors::AttachmentError;
+use crate::models::{Attachment, IntegrityReport};
+use crate::repository::UnitOfWork;
+use crate::utils::{AttachmentStore, BlobStatus};
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, user_id, task_id, note_id, hash, original_name, mime_type, size, created_at";
+
+pub struct AttachmentRepository;
+
+impl AttachmentRepository {
+    /// Copies `source` into the store and attaches it to a task or note of
+    /// the user. If saving the metadata fails, a newly stored blob is removed
+    /// again.
+    pub fn import(
+        conn: &Connection,
+        store: &AttachmentStore,
+        user_id: u32,
+        task_id: Option<u32>,
+        note_id: Option<u32>,
+        source: &Path,
+    ) -> Result<Attachment, AttachmentError> {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        AttachmentRepository::ensure_owner(conn, user_id, task_id, note_id)?;
+
+        let blob = store.import(source)?;
+        let mut attachment = Attachment::new(user_id, task_id, note_id, name, &blob)?;
+        let saved = UnitOfWork::run(conn, |uow| AttachmentRepository::insert(uow, &mut attachment));
+        if let Err(e) = saved {
+            if !AttachmentRepository::blob_known(conn, &blob.hash)? {
+                store.remove(&blob.hash)?;
+            }
+            return Err(e);
+        }
+        Ok(attachment)
+    }
+
+    pub fn find(conn: &Connection, attachment_id: u32) -> Result<Attachment, AttachmentError> {
+        conn.query_row(
+            &format!("SELECT {} FROM attachments WHERE id = ?1", ATTACHMENT_COLUMNS),
+            params![attachment_id],
+            |row| Attachment::try_from(row),
+        )
+        .optional()?
+        .ok_or(AttachmentError::NotFound(attachment_id))
+    }
+
+    pub fn list_for_task(conn: &Connection, task_id: u32) -> Result<Vec<Attachment>, AttachmentError> {
+        AttachmentRepository::query(conn, "task_id = ?1", task_id)
+    }
+
+    pub fn list_for_note(conn: &Connection, note_id: u32) -> Result<Vec<Attachment>, AttachmentError> {
+        AttachmentRepository::query(conn, "note_id = ?1", note_id)
+    }
+
+    /// Deletes the attachment and collects its blob if nothing else uses it.
+    pub fn delete(conn: &Connection, store: &AttachmentStore, attachment_id: u32) -> Result<bool, AttachmentError> {
+        let deleted = conn.execute("DELETE FROM attachments WHERE id = ?1", params![attachment_id])? > 0;
+        AttachmentRepository::collect_garbage(conn, store)?;
+        Ok(deleted)
+    }
+
+    /// Removes every blob no attachment references any more, returning how
+    /// many were removed. A row is only dropped once its file is gone, so a
+    /// failed removal is retried by the next sweep.
+    pub fn collect_garbage(conn: &Connection, store: &AttachmentStore) -> Result<u32, AttachmentError> {
+        let mut stmt = conn.prepare("SELECT hash FROM attachment_blobs WHERE ref_count = 0")?;
+        let hashes = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for hash in &hashes {
+            store.remove(hash)?;
+            conn.execute(
+                "DELETE FROM attachment_blobs WHERE hash = ?1 AND ref_count = 0",
+                params![hash],
+            )?;
+        }
+        Ok(hashes.len() as u32)
+    }
+
+    /// Re-hashes the blobs behind the user's attachments, reading each
+    /// shared blob once.
+    pub fn verify(conn: &Connection, store: &AttachmentStore, user_id: u32) -> Result<IntegrityReport, AttachmentError> {
+        let mut report = IntegrityReport::default();
+        let mut checked: HashMap<String, BlobStatus> = HashMap::new();
+
+        for attachment in AttachmentRepository::query(conn, "user_id = ?1", user_id)? {
+            let status = match checked.get(&attachment.hash) {
+                Some(status) => *status,
+                None => {
+                    let status = store.check(&attachment.hash, attachment.size)?;
+                    checked.insert(attachment.hash.clone(), status);
+                    status
+                }
+            };
+            report.checked += 1;
+            match status {
+                BlobStatus::Intact => {}
+                BlobStatus::Missing => report.missing.push(attachment),
+                BlobStatus::Corrupted => report.corrupted.push(attachment),
+            }
+        }
+        Ok(report)
+    }
+
+    fn insert(uow: &UnitOfWork, attachment: &mut Attachment) -> Result<(), AttachmentError> {
+        uow.execute(
+            "INSERT INTO attachment_blobs (hash, size) VALUES (?1, ?2) ON CONFLICT (hash) DO NOTHING",
+            params![attachment.hash, attachment.size],
+        )?;
+        uow.execute(
+            "INSERT INTO attachments (user_id, task_id, note_id, hash, original_name, mime_type, size, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                attachment.user_id,
+                attachment.task_id,
+                attachment.note_id,
+                attachment.hash,
+                attachment.original_name,
+                attachment.mime_type,
+                attachment.size,
+                attachment.created_at.format("%Y-%m-%dT%H:%M").to_string(),
+            ],
+        )?;
+        attachment.id = Some(uow.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    fn query(conn: &Connection, filter: &str, id: u32) -> Result<Vec<Attachment>, AttachmentError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM attachments WHERE {} ORDER BY original_name COLLATE NOCASE, id",
+            ATTACHMENT_COLUMNS, filter
+        ))?;
+        let attachments = stmt
+            .query_map(params![id], |row| Attachment::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(attachments)
+    }
+
+    fn ensure_owner(
+        conn: &Connection,
+        user_id: u32,
+        task_id: Option<u32>,
+        note_id: Option<u32>,
+    ) -> Result<(), AttachmentError> {
+        let (table, label, id) = match (task_id, note_id) {
+            (Some(id), None) => ("tasks", "Task", id),
+            (None, Some(id)) => ("notes", "Note", id),
+            _ => {
+                return Err(AttachmentError::InvalidAttachment(
+                    "An attachment belongs to exactly one task or note".into(),
+                ))
+            }
+        };
+        let exists: bool = conn.query_row(
+            &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1 AND user_id = ?2)", table),
+            params![id, user_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AttachmentError::OwnerNotFound(format!("{} {}", label, id)));
+        }
+        Ok(())
+    }
+
+    fn blob_known(conn: &Connection, hash: &str) -> Result<bool, AttachmentError> {
+        Ok(conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM attachment_blobs WHERE hash = ?1)",
+            params![hash],
+            |row| row.get(0),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Note, Task};
+    use crate::repository::{NoteRepository, TaskRepository};
+    use crate::utils::{initialize_database, ATTACHMENTS_DIR};
+    use std::fs;
+    use std::path::PathBuf;
+
+    struct Fixture {
+        conn: Connection,
+        store: AttachmentStore,
+        dir: PathBuf,
+        task_id: u32,
+        note_id: u32,
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn setup(name: &str) -> Fixture {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        let mut task = Task::new("Lab report".to_string(), 1, None, None).unwrap();
+        TaskRepository::insert_task(&conn, &mut task).unwrap();
+        let mut note = Note::new(1, "Optics".to_string(), String::new()).unwrap();
+        UnitOfWork::run(&conn, |uow| NoteRepository::create(uow, &mut note)).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("attachment-repo-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Fixture {
+            conn,
+            store: AttachmentStore::new(dir.join(ATTACHMENTS_DIR)),
+            task_id: task.id.unwrap(),
+            note_id: note.id.unwrap(),
+            dir,
+        }
+    }
+
+    fn file(fixture: &Fixture, name: &str, contents: &str) -> PathBuf {
+        let path = fixture.dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_shared_blob_lives_until_last_reference() {
+        let f = setup("refcount");
+        let slides = file(&f, "slides.pdf", "same bytes");
+        let copy = file(&f, "copy.pdf", "same bytes");
+
+        let a = AttachmentRepository::import(&f.conn, &f.store, 1, Some(f.task_id), None, &slides).unwrap();
+        let b = AttachmentRepository::import(&f.conn, &f.store, 1, None, Some(f.note_id), &copy).unwrap();
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(a.mime_type, "application/pdf");
+        assert_eq!(AttachmentRepository::list_for_task(&f.conn, f.task_id).unwrap(), vec![a.clone()]);
+
+        AttachmentRepository::delete(&f.conn, &f.store, a.id.unwrap()).unwrap();
+        assert!(f.store.path(&a.hash).exists());
+
+        // Deleting the note cascades to its attachment; the sweep then
+        // removes the blob.
+        NoteRepository::delete(&f.conn, f.note_id).unwrap();
+        assert_eq!(AttachmentRepository::collect_garbage(&f.conn, &f.store).unwrap(), 1);
+        assert!(!f.store.path(&a.hash).exists());
+    }
+
+    #[test]
+    fn test_deleting_task_releases_its_blobs() {
+        let f = setup("task");
+        let path = file(&f, "notes.txt", "text");
+        let attachment = AttachmentRepository::import(&f.conn, &f.store, 1, Some(f.task_id), None, &path).unwrap();
+
+        UnitOfWork::run(&f.conn, |uow| TaskRepository::delete_task(uow, f.task_id)).unwrap();
+        AttachmentRepository::collect_garbage(&f.conn, &f.store).unwrap();
+
+        assert!(!f.store.path(&attachment.hash).exists());
+    }
+
+    #[test]
+    fn test_import_requires_an_owner_of_the_user() {
+        let f = setup("owner");
+        let path = file(&f, "a.txt", "text");
+
+        let result = AttachmentRepository::import(&f.conn, &f.store, 1, Some(999), None, &path);
+        assert!(matches!(result, Err(AttachmentError::OwnerNotFound(_))));
+        let result = AttachmentRepository::import(&f.conn, &f.store, 2, Some(f.task_id), None, &path);
+        assert!(matches!(result, Err(AttachmentError::OwnerNotFound(_))));
+        assert!(!f.dir.join(ATTACHMENTS_DIR).exists());
+    }
+
+    #[test]
+    fn test_verify_reports_missing_and_corrupted_blobs() {
+        let f = setup("verify");
+        let a = file(&f, "a.txt", "first");
+        let b = file(&f, "b.txt", "second");
+        let c = file(&f, "c.txt", "third");
+        let missing = AttachmentRepository::import(&f.conn, &f.store, 1, Some(f.task_id), None, &a).unwrap();
+        let corrupted = AttachmentRepository::import(&f.conn, &f.store, 1, Some(f.task_id), None, &b).unwrap();
+        AttachmentRepository::import(&f.conn, &f.store, 1, Some(f.task_id), None, &c).unwrap();
+
+        fs::remove_file(f.store.path(&missing.hash)).unwrap();
+        fs::write(f.store.path(&corrupted.hash), "tampered").unwrap();
+        let report = AttachmentRepository::verify(&f.conn, &f.store, 1).unwrap();
+
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.missing, vec![missing]);
+        assert_eq!(report.corrupted, vec![corrupted]);
+    }
+}
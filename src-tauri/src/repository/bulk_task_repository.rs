@@ -0,0 +1,520 @@
+use chrono::Duration;
+
+use crate::errors::TaskError;
+use crate::models::{BulkSummary, BulkTaskChanges, Tag, Task, TaskSelection};
+use crate::repository::{TagStore, TaskStore};
+
+/// Bulk task operations built on the store traits. They issue one store call
+/// per task, so callers wrap them in a [`UnitOfWork`](crate::repository::UnitOfWork)
+/// to make the whole batch atomic.
+pub struct BulkTaskRepository;
+
+impl BulkTaskRepository {
+    /// Resolves a selection to the user's tasks, reporting requested IDs
+    /// that do not exist or belong to someone else.
+    pub fn select<S: TaskStore>(
+        store: &S,
+        user_id: u32,
+        selection: &TaskSelection,
+    ) -> Result<(Vec<Task>, Vec<u32>), TaskError> {
+        let tasks: Vec<Task> = store.list_tasks()?.into_iter().filter(|task| task.user_id == user_id).collect();
+
+        match selection {
+            TaskSelection::Ids(ids) => {
+                let mut ids = ids.clone();
+                ids.sort_unstable();
+                ids.dedup();
+
+                let missing = ids
+                    .iter()
+                    .copied()
+                    .filter(|id| !tasks.iter().any(|task| task.id == Some(*id)))
+                    .collect();
+                let selected = tasks
+                    .into_iter()
+                    .filter(|task| task.id.is_some_and(|id| ids.binary_search(&id).is_ok()))
+                    .collect();
+                Ok((selected, missing))
+            }
+            TaskSelection::Filter(filter) => Ok((
+                tasks.into_iter().filter(|task| filter.matches(task)).collect(),
+                Vec::new(),
+            )),
+        }
+    }
+
+    /// Applies `changes` to every selected task. Returns the summary and the
+    /// affected tasks as they were before the change, for the undo log.
+    pub fn update<S: TaskStore + TagStore>(
+        store: &mut S,
+        user_id: u32,
+        selection: &TaskSelection,
+        changes: &BulkTaskChanges,
+    ) -> Result<(BulkSummary, Vec<Task>), TaskError> {
+        if changes.is_empty() {
+            return Err(TaskError::InvalidStatus("Nenhum campo para atualizar".to_owned()));
+        }
+
+        let (tasks, missing_ids) = Self::select(store, user_id, selection)?;
+        let mut summary = BulkSummary {
+            matched: tasks.len() as u32,
+            missing_ids,
+            ..Default::default()
+        };
+        let mut snapshot = Vec::new();
+
+        for task in tasks {
+            let task_id = task.id.ok_or_else(|| TaskError::DatabaseError("Task without id".to_owned()))?;
+
+            let status = changes.status.clone().filter(|status| status != &task.status);
+            let priority = changes.priority.filter(|priority| priority != &task.priority);
+            let due_date = changes
+                .shift_due_minutes
+                .filter(|minutes| *minutes != 0)
+                .and_then(|minutes| task.due_date.map(|due| due + Duration::minutes(minutes)));
+            let tags = Self::apply_tag_changes(&task.tags, changes);
+
+            if status.is_none() && priority.is_none() && due_date.is_none() && tags.is_none() {
+                continue;
+            }
+
+            if status.is_some() || priority.is_some() || due_date.is_some() {
+                store.update_task(task_id, None, None, status, priority, due_date, None)?;
+            }
+            if let Some(tags) = tags {
+                store
+                    .set_task_tags(task_id, &tags)
+                    .map_err(|e| TaskError::InvalidTag(e.to_string()))?;
+            }
+
+            summary.affected += 1;
+            snapshot.push(task);
+        }
+
+        Ok((summary, snapshot))
+    }
+
+    /// Deletes every selected task. Returns the summary and the deleted tasks.
+    pub fn delete<S: TaskStore>(
+        store: &mut S,
+        user_id: u32,
+        selection: &TaskSelection,
+    ) -> Result<(BulkSummary, Vec<Task>), TaskError> {
+        let (tasks, missing_ids) = Self::select(store, user_id, selection)?;
+
+        for task in &tasks {
+            if let Some(task_id) = task.id {
+                store.delete_task(task_id)?;
+            }
+        }
+
+        let summary = BulkSummary {
+            matched: tasks.len() as u32,
+            affected: tasks.len() as u32,
+            missing_ids,
+            undo_id: None,
+        };
+        Ok((summary, tasks))
+    }
+
+    /// New tag list for a task, or `None` when the changes leave it as is.
+    fn apply_tag_changes(current: &[Tag], changes: &BulkTaskChanges) -> Option<Vec<Tag>> {
+        let mut tags: Vec<Tag> = current
+            .iter()
+            .filter(|tag| !changes.remove_tags.contains(&tag.name))
+            .cloned()
+            .collect();
+
+        for added in &changes.add_tags {
+            if !tags.iter().any(|tag| tag.name == added.name) {
+                tags.push(Tag {
+                    id: added.id,
+                    name: added.name.clone(),
+                    color: added.color.clone(),
+                });
+            }
+        }
+
+        let unchanged = tags.len() == current.len()
+            && tags.iter().all(|tag| current.iter().any(|c| c.name == tag.name));
+        (!unchanged).then_some(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use rusqlite::Connection;
+
+    use crate::models::{
+        task_priority::TaskPriority,
+        task_status::TaskStatus,
+        FrontendTag, TaskFilter, User,
+    };
+    use crate::repository::{InMemoryStore, SqliteStore, UndoRepository, UnitOfWork, UserStore, MAX_UNDO_ENTRIES};
+    use crate::utils::initialize_database;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        let mut user = User::new("Alice".to_string()).unwrap();
+        SqliteStore::new(&conn).create_user(&mut user).unwrap();
+        conn
+    }
+
+    fn add_task(store: &mut impl TaskStore, title: &str, due: &str, tags: &[&str]) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.due_date = Some(at(due));
+        let tags: Vec<Tag> = tags
+            .iter()
+            .map(|name| Tag::new(name.to_string(), "red".to_string()).unwrap())
+            .collect();
+        store.insert_task(&mut task, &tags).unwrap();
+        task.id.unwrap()
+    }
+
+    fn tag_names(task: &Task) -> Vec<&str> {
+        task.tags.iter().map(|tag| tag.name.as_str()).collect()
+    }
+
+    fn frontend_tag(name: &str) -> FrontendTag {
+        FrontendTag { id: None, name: name.to_string(), color: "blue".to_string() }
+    }
+
+    /// What the `bulk_update_tasks` command runs.
+    fn bulk_update(
+        conn: &Connection,
+        selection: &TaskSelection,
+        changes: &BulkTaskChanges,
+    ) -> Result<BulkSummary, TaskError> {
+        UnitOfWork::run(conn, |uow| {
+            let mut store = SqliteStore::new(uow);
+            let (mut summary, snapshot) = BulkTaskRepository::update(&mut store, 1, selection, changes)?;
+            if !snapshot.is_empty() {
+                summary.undo_id = Some(UndoRepository::record(uow, 1, "bulk_update_tasks", &snapshot)?);
+            }
+            Ok(summary)
+        })
+    }
+
+    #[test]
+    fn test_select_by_ids_reports_missing() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &[]);
+        add_task(&mut store, "Second", "2025-03-10 09:00", &[]);
+
+        let (tasks, missing) =
+            BulkTaskRepository::select(&store, 1, &TaskSelection::Ids(vec![first, 99, first])).unwrap();
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, Some(first));
+        assert_eq!(missing, vec![99]);
+    }
+
+    #[test]
+    fn test_update_by_filter_sets_fields_and_shifts_dates() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let physics = add_task(&mut store, "Physics", "2025-03-10 09:00", &["Semester"]);
+        let other = add_task(&mut store, "Other", "2025-03-10 09:00", &[]);
+
+        let selection = TaskSelection::Filter(TaskFilter {
+            tag: Some("Semester".to_string()),
+            ..Default::default()
+        });
+        let changes = BulkTaskChanges {
+            status: Some(TaskStatus::Done),
+            priority: Some(TaskPriority::Low),
+            shift_due_minutes: Some(7 * 24 * 60),
+            add_tags: vec![frontend_tag("Archived")],
+            remove_tags: vec!["Semester".to_string()],
+        };
+
+        let summary = bulk_update(&conn, &selection, &changes).unwrap();
+
+        assert_eq!(summary.matched, 1);
+        assert_eq!(summary.affected, 1);
+        assert!(summary.undo_id.is_some());
+
+        let task = store.get_task(physics).unwrap();
+        assert_eq!(task.status, TaskStatus::Done);
+        assert_eq!(task.priority, TaskPriority::Low);
+        assert_eq!(task.due_date, Some(at("2025-03-17 09:00")));
+        assert_eq!(tag_names(&task), ["Archived"]);
+
+        let untouched = store.get_task(other).unwrap();
+        assert_eq!(untouched.status, TaskStatus::Todo);
+    }
+
+    #[test]
+    fn test_update_skips_tasks_already_in_target_state() {
+        let mut store = InMemoryStore::new();
+        store.create_user(&mut User::new("Alice".to_string()).unwrap()).unwrap();
+        let done = add_task(&mut store, "Done", "2025-03-10 09:00", &[]);
+        add_task(&mut store, "Todo", "2025-03-10 09:00", &[]);
+        store
+            .update_task(done, None, None, Some(TaskStatus::Done), None, None, None)
+            .unwrap();
+
+        let changes = BulkTaskChanges { status: Some(TaskStatus::Done), ..Default::default() };
+        let (summary, snapshot) = BulkTaskRepository::update(
+            &mut store,
+            1,
+            &TaskSelection::Filter(TaskFilter::default()),
+            &changes,
+        )
+        .unwrap();
+
+        assert_eq!(summary.matched, 2);
+        assert_eq!(summary.affected, 1);
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].title, "Todo");
+    }
+
+    #[test]
+    fn test_update_without_changes_is_rejected() {
+        let mut store = InMemoryStore::new();
+        let result = BulkTaskRepository::update(
+            &mut store,
+            1,
+            &TaskSelection::Ids(vec![1]),
+            &BulkTaskChanges::default(),
+        );
+        assert!(matches!(result, Err(TaskError::InvalidStatus(_))));
+    }
+
+    #[test]
+    fn test_update_failure_rolls_back_whole_batch() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &[]);
+        let second = add_task(&mut store, "Second", "2025-03-10 09:00", &[]);
+        conn.execute_batch(&format!(
+            "CREATE TRIGGER fail_second BEFORE UPDATE ON tasks WHEN NEW.id = {second}
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END;"
+        ))
+        .unwrap();
+
+        let changes = BulkTaskChanges { status: Some(TaskStatus::Done), ..Default::default() };
+        let result = bulk_update(&conn, &TaskSelection::Ids(vec![first, second]), &changes);
+
+        assert!(result.is_err());
+        assert_eq!(store.get_task(first).unwrap().status, TaskStatus::Todo);
+        let entries: u32 = conn
+            .query_row("SELECT COUNT(*) FROM undo_entries", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(entries, 0);
+    }
+
+    #[test]
+    fn test_undo_reverts_bulk_update_in_one_step() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &["Physics"]);
+        let second = add_task(&mut store, "Second", "2025-03-11 09:00", &[]);
+
+        let changes = BulkTaskChanges {
+            status: Some(TaskStatus::Done),
+            shift_due_minutes: Some(-60),
+            remove_tags: vec!["Physics".to_string()],
+            ..Default::default()
+        };
+        bulk_update(&conn, &TaskSelection::Ids(vec![first, second]), &changes).unwrap();
+
+        let undone = UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap().unwrap();
+        assert_eq!(undone.label, "bulk_update_tasks");
+        assert_eq!(undone.restored, 2);
+
+        let task = store.get_task(first).unwrap();
+        assert_eq!(task.status, TaskStatus::Todo);
+        assert_eq!(task.due_date, Some(at("2025-03-10 09:00")));
+        assert_eq!(tag_names(&task), ["Physics"]);
+        assert_eq!(store.get_task(second).unwrap().due_date, Some(at("2025-03-11 09:00")));
+
+        assert!(UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_is_per_user_and_skips_later_edits() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        store.create_user(&mut User::new("Bob".to_string()).unwrap()).unwrap();
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &[]);
+        let second = add_task(&mut store, "Second", "2025-03-11 09:00", &[]);
+
+        let changes = BulkTaskChanges { status: Some(TaskStatus::Done), ..Default::default() };
+        let selection = TaskSelection::Ids(vec![first, second]);
+        assert_eq!(
+            UnitOfWork::run(&conn, |uow| BulkTaskRepository::update(&mut SqliteStore::new(uow), 2, &selection, &changes))
+                .unwrap()
+                .0
+                .missing_ids,
+            vec![first, second]
+        );
+        bulk_update(&conn, &selection, &changes).unwrap();
+        conn.execute("UPDATE tasks SET title = 'Edited', updated_at = '2999-01-01T00:00' WHERE id = ?1", [second])
+            .unwrap();
+
+        assert!(UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 2)).unwrap().is_none());
+        let undone = UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap().unwrap();
+
+        assert_eq!(undone.restored, 1);
+        assert_eq!(undone.skipped, vec![second]);
+        assert_eq!(store.get_task(first).unwrap().status, TaskStatus::Todo);
+        let edited = store.get_task(second).unwrap();
+        assert_eq!((edited.title.as_str(), edited.status), ("Edited", TaskStatus::Done));
+    }
+
+    #[test]
+    fn test_bulk_delete_and_undo() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &["Physics"]);
+        let kept = add_task(&mut store, "Kept", "2025-03-10 09:00", &[]);
+
+        conn.execute(
+            "INSERT INTO time_logs (task_id, started_at, ended_at, minutes) VALUES (?1, '2025-03-09T10:00', '2025-03-09T10:30', 30)",
+            [first],
+        )
+        .unwrap();
+        conn.execute("INSERT INTO reminders (task_id, minutes_before_due) VALUES (?1, 60)", [first]).unwrap();
+        conn.execute("INSERT INTO attachment_blobs (hash, size, ref_count) VALUES ('abc', 3, 0)", []).unwrap();
+        conn.execute(
+            "INSERT INTO attachments (user_id, task_id, hash, original_name, mime_type, size, created_at)
+             VALUES (1, ?1, 'abc', 'notes.txt', 'text/plain', 3, '2025-03-09T10:00')",
+            [first],
+        )
+        .unwrap();
+        let dependents = |conn: &Connection| -> (u32, u32, u32) {
+            conn.query_row(
+                "SELECT (SELECT COUNT(*) FROM time_logs), (SELECT COUNT(*) FROM reminders),
+                    (SELECT ref_count FROM attachment_blobs WHERE hash = 'abc')",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+        };
+
+        let summary = UnitOfWork::run(&conn, |uow| {
+            let mut store = SqliteStore::new(uow);
+            let selection = TaskSelection::Ids(vec![first, 42]);
+            let captured = UndoRepository::capture_dependents(uow, &[first])?;
+            let (mut summary, deleted) = BulkTaskRepository::delete(&mut store, 1, &selection)?;
+            summary.undo_id = Some(UndoRepository::record_delete(uow, 1, "bulk_delete_tasks", &deleted, &captured)?);
+            Ok::<_, TaskError>(summary)
+        })
+        .unwrap();
+
+        assert_eq!(summary.affected, 1);
+        assert_eq!(summary.missing_ids, vec![42]);
+        assert_eq!(store.list_tasks().unwrap().len(), 1);
+        // The undo entry keeps the blob referenced.
+        assert_eq!(dependents(&conn), (0, 0, 1));
+
+        UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap();
+
+        let restored = store.get_task(first).unwrap();
+        assert_eq!(restored.title, "First");
+        assert_eq!(tag_names(&restored), ["Physics"]);
+        assert!(store.get_task(kept).is_ok());
+        assert_eq!(dependents(&conn), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_undo_drops_rows_whose_parents_were_deleted() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &["Physics"]);
+
+        conn.execute(
+            "INSERT INTO notes (id, user_id, title, created_at, updated_at) VALUES (7, 1, 'Optics', '2025-03-09T10:00', '2025-03-09T10:00')",
+            [],
+        )
+        .unwrap();
+        conn.execute("INSERT INTO note_links (note_id, target_task_id) VALUES (7, ?1)", [first]).unwrap();
+        conn.execute("INSERT INTO reminders (task_id, minutes_before_due) VALUES (?1, 60)", [first]).unwrap();
+
+        UnitOfWork::run(&conn, |uow| {
+            let mut store = SqliteStore::new(uow);
+            let captured = UndoRepository::capture_dependents(uow, &[first])?;
+            let (_, deleted) = BulkTaskRepository::delete(&mut store, 1, &TaskSelection::Ids(vec![first]))?;
+            UndoRepository::record_delete(uow, 1, "bulk_delete_tasks", &deleted, &captured)
+        })
+        .unwrap();
+        conn.execute("DELETE FROM notes WHERE id = 7", []).unwrap();
+        conn.execute("DELETE FROM tags WHERE tag_name = 'Physics'", []).unwrap();
+
+        let result = UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap().unwrap();
+
+        assert_eq!(result.restored, 1);
+        let restored = store.get_task(first).unwrap();
+        assert!(restored.tags.is_empty());
+        let counts: (u32, u32) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM note_links), (SELECT COUNT(*) FROM reminders)",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(counts, (0, 1));
+    }
+
+    #[test]
+    fn test_old_undo_entries_are_pruned_and_release_blobs() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &[]);
+        conn.execute("INSERT INTO attachment_blobs (hash, size, ref_count) VALUES ('abc', 3, 0)", []).unwrap();
+        conn.execute(
+            "INSERT INTO attachments (user_id, task_id, hash, original_name, mime_type, size, created_at)
+             VALUES (1, ?1, 'abc', 'notes.txt', 'text/plain', 3, '2025-03-09T10:00')",
+            [first],
+        )
+        .unwrap();
+
+        UnitOfWork::run(&conn, |uow| {
+            let captured = UndoRepository::capture_dependents(uow, &[first])?;
+            let (_, deleted) = BulkTaskRepository::delete(&mut SqliteStore::new(uow), 1, &TaskSelection::Ids(vec![first]))?;
+            UndoRepository::record_delete(uow, 1, "bulk_delete_tasks", &deleted, &captured)
+        })
+        .unwrap();
+        for _ in 0..MAX_UNDO_ENTRIES {
+            UndoRepository::record(&conn, 1, "bulk_update_tasks", &[]).unwrap();
+        }
+
+        let (entries, ref_count): (u32, u32) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM undo_entries WHERE user_id = 1),
+                    (SELECT ref_count FROM attachment_blobs WHERE hash = 'abc')",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((entries, ref_count), (MAX_UNDO_ENTRIES, 0));
+    }
+
+    #[test]
+    fn test_undo_skips_edits_made_in_the_same_minute() {
+        let conn = setup_db();
+        let mut store = SqliteStore::new(&conn);
+        let first = add_task(&mut store, "First", "2025-03-10 09:00", &[]);
+        let second = add_task(&mut store, "Second", "2025-03-11 09:00", &[]);
+
+        let changes = BulkTaskChanges { status: Some(TaskStatus::Done), ..Default::default() };
+        bulk_update(&conn, &TaskSelection::Ids(vec![first, second]), &changes).unwrap();
+        // Same minute as the operation, so updated_at alone cannot tell the edit apart.
+        conn.execute("UPDATE tasks SET title = 'Edited' WHERE id = ?1", [second]).unwrap();
+
+        let undone = UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap().unwrap();
+
+        assert_eq!(undone.skipped, vec![second]);
+        assert_eq!(store.get_task(first).unwrap().status, TaskStatus::Todo);
+        assert_eq!(store.get_task(second).unwrap().title, "Edited");
+    }
+}
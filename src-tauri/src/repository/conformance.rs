@@ -0,0 +1,475 @@
+//! Behaviour every repository backend must share. Each check runs against
+//! SQLite on the real `initialize_database` schema and against `InMemoryStore`.
+
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+
+use crate::errors::TaskError;
+use crate::models::{
+    task_kind::TaskKind,
+    task_priority::TaskPriority,
+    task_status::TaskStatus,
+    Tag, Task, User, UserStatus,
+};
+use crate::repository::{InMemoryStore, SqliteStore, TagStore, TaskStore, UserStore};
+use crate::utils::initialize_database;
+
+trait Store: UserStore + TagStore + TaskStore {}
+impl<S: UserStore + TagStore + TaskStore> Store for S {}
+
+fn with_sqlite(check: impl FnOnce(&mut SqliteStore)) {
+    let conn = Connection::open_in_memory().unwrap();
+    initialize_database(&conn).unwrap();
+    check(&mut SqliteStore::new(&conn));
+}
+
+fn with_memory(check: impl FnOnce(&mut InMemoryStore)) {
+    check(&mut InMemoryStore::new());
+}
+
+fn at(s: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+}
+
+fn tag(name: &str, color: &str) -> Tag {
+    Tag::new(name.to_string(), color.to_string()).unwrap()
+}
+
+fn user_id(store: &mut impl Store) -> u32 {
+    if let Some(id) = store.find_active_user_id().unwrap() {
+        return id;
+    }
+    let mut user = User::new("Alice".to_string()).unwrap();
+    store.create_user(&mut user).unwrap();
+    user.id.unwrap()
+}
+
+fn new_task(store: &mut impl Store, title: &str, due: &str, tags: &[Tag]) -> Task {
+    let user_id = user_id(store);
+    let mut task = Task::new(title.to_string(), user_id, None, None).unwrap();
+    task.due_date = Some(at(due));
+    store.insert_task(&mut task, tags).unwrap();
+    task
+}
+
+fn names(tags: &[Tag]) -> Vec<&str> {
+    tags.iter().map(|tag| tag.name.as_str()).collect()
+}
+
+fn users_are_counted_by_status(store: &mut impl Store) {
+    assert_eq!(store.find_active_user_id().unwrap(), None);
+
+    let mut inactive = User::new("Bob".to_string()).unwrap();
+    inactive.status = UserStatus::Inactive;
+    store.create_user(&mut inactive).unwrap();
+    let mut alice = User::new("Alice".to_string()).unwrap();
+    store.create_user(&mut alice).unwrap();
+    let mut carol = User::new("Carol".to_string()).unwrap();
+    store.create_user(&mut carol).unwrap();
+
+    assert!(alice.id.is_some());
+    assert_ne!(alice.id, carol.id);
+    assert_eq!(store.count_active_users().unwrap(), 2);
+    assert_eq!(store.find_active_user_id().unwrap(), alice.id);
+}
+
+fn tags_round_trip(store: &mut impl Store) {
+    let mut physics = tag("Physics", "#FF0000");
+    let mut math = tag("Math", "blue");
+    store.create_tag(&mut physics).unwrap();
+    store.create_tag(&mut math).unwrap();
+
+    let found = store.find_tag(physics.id.unwrap()).unwrap().unwrap();
+    assert_eq!(found.name, "Physics");
+    assert_eq!(found.color, "#FF0000");
+    assert!(store.find_tag(999).unwrap().is_none());
+    assert_eq!(names(&store.list_tags().unwrap()), ["Physics", "Math"]);
+
+    store.rename_tag(math.id.unwrap(), "Calculus".to_string()).unwrap();
+    assert_eq!(store.find_tag(math.id.unwrap()).unwrap().unwrap().name, "Calculus");
+}
+
+fn deleting_a_tag_unlinks_it(store: &mut impl Store) {
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[tag("Physics", "red"), tag("Math", "blue")]);
+    let physics_id = task.tags[0].id.unwrap();
+
+    assert!(store.delete_tag(physics_id).unwrap());
+    assert!(!store.delete_tag(physics_id).unwrap());
+
+    assert_eq!(names(&store.get_task_tags(task.id.unwrap()).unwrap()), ["Math"]);
+    assert_eq!(names(&store.get_task(task.id.unwrap()).unwrap().tags), ["Math"]);
+}
+
+fn task_tags_are_matched_by_name(store: &mut impl Store) {
+    let mut physics = tag("Physics", "red");
+    store.create_tag(&mut physics).unwrap();
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[]);
+    let task_id = task.id.unwrap();
+
+    store
+        .set_task_tags(task_id, &[tag("Physics", "blue"), tag("Chemistry", "green")])
+        .unwrap();
+
+    let linked = store.get_task_tags(task_id).unwrap();
+    assert_eq!(names(&linked), ["Physics", "Chemistry"]);
+    assert_eq!(linked[0].id, physics.id);
+    assert_eq!(linked[0].color, "red");
+    assert_eq!(store.list_tags().unwrap().len(), 2);
+
+    store.set_task_tags(task_id, &[]).unwrap();
+    assert!(store.get_task_tags(task_id).unwrap().is_empty());
+}
+
+fn duplicate_task_tags_roll_back(store: &mut impl Store) {
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[tag("Physics", "red")]);
+    let task_id = task.id.unwrap();
+
+    let result = store.set_task_tags(task_id, &[tag("Math", "blue"), tag("Math", "blue")]);
+
+    assert!(result.is_err());
+    assert_eq!(names(&store.get_task_tags(task_id).unwrap()), ["Physics"]);
+    assert_eq!(store.list_tags().unwrap().len(), 1);
+}
+
+fn tasks_round_trip(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let mut task = Task::new(
+        "Read chapter 4".to_string(),
+        user_id,
+        Some("Physics".to_string()),
+        Some(TaskPriority::High),
+    )
+    .unwrap();
+    task.due_date = Some(at("2025-03-10 18:30"));
+    task.estimated_minutes = Some(45);
+    task.kind = TaskKind::Exam;
+    store.insert_task(&mut task, &[tag("Physics", "red")]).unwrap();
+
+    let stored = store.get_task(task.id.unwrap()).unwrap();
+    assert_eq!(stored.title, "Read chapter 4");
+    assert_eq!(stored.user_id, user_id);
+    assert_eq!(stored.description.as_deref(), Some("Physics"));
+    assert_eq!(stored.status, TaskStatus::Todo);
+    assert_eq!(stored.priority, TaskPriority::High);
+    assert_eq!(stored.due_date, Some(at("2025-03-10 18:30")));
+    assert_eq!(stored.estimated_minutes, Some(45));
+    assert_eq!(stored.kind, TaskKind::Exam);
+    assert_eq!(stored.created_at, task.created_at);
+    assert_eq!(names(&stored.tags), ["Physics"]);
+    assert_eq!(names(&task.tags), ["Physics"]);
+}
+
+fn missing_task_is_an_error(store: &mut impl Store) {
+    assert!(matches!(store.get_task(42), Err(TaskError::DatabaseError(_))));
+}
+
+fn task_references_are_enforced(store: &mut impl Store) {
+    let mut orphan = Task::new("Orphan".to_string(), 42, None, None).unwrap();
+    assert!(store.insert_task(&mut orphan, &[]).is_err());
+    assert!(store.list_tasks().unwrap().is_empty());
+
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[]);
+    let unknown_tag = Tag { id: Some(42), name: "Ghost".to_string(), color: "red".to_string() };
+    let result = store.update_task(
+        task.id.unwrap(),
+        Some("Review".to_string()),
+        None,
+        None,
+        None,
+        None,
+        Some(vec![unknown_tag]),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(store.get_task(task.id.unwrap()).unwrap().title, "Study");
+}
+
+fn tasks_are_listed_in_insertion_order(store: &mut impl Store) {
+    let first = new_task(store, "First", "2025-03-12 09:00", &[]);
+    let second = new_task(store, "Second", "2025-03-10 09:00", &[]);
+
+    let ids: Vec<_> = store.list_tasks().unwrap().iter().map(|task| task.id).collect();
+    assert_eq!(ids, [first.id, second.id]);
+}
+
+fn tasks_are_updated(store: &mut impl Store) {
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[tag("Physics", "red")]);
+    let task_id = task.id.unwrap();
+    let mut math = tag("Math", "blue");
+    store.create_tag(&mut math).unwrap();
+
+    store
+        .update_task(
+            task_id,
+            Some("Review".to_string()),
+            Some("Notes".to_string()),
+            Some(TaskStatus::InProgress),
+            Some(TaskPriority::Low),
+            Some(at("2025-03-11 14:00")),
+            Some(vec![math]),
+        )
+        .unwrap();
+
+    let stored = store.get_task(task_id).unwrap();
+    assert_eq!(stored.title, "Review");
+    assert_eq!(stored.description.as_deref(), Some("Notes"));
+    assert_eq!(stored.status, TaskStatus::InProgress);
+    assert_eq!(stored.priority, TaskPriority::Low);
+    assert_eq!(stored.due_date, Some(at("2025-03-11 14:00")));
+    assert_eq!(names(&stored.tags), ["Math"]);
+}
+
+fn due_date_updates_keep_dates_consistent(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let mut task = Task::new("Essay".to_string(), user_id, None, None).unwrap();
+    task.due_date = Some(at("2025-03-10 23:59"));
+    task.all_day = true;
+    task.start_date = Some(at("2025-03-09 10:00"));
+    store.insert_task(&mut task, &[]).unwrap();
+    let task_id = task.id.unwrap();
+
+    let early = store.update_task(task_id, None, None, None, None, Some(at("2025-03-08 10:00")), None);
+    assert!(matches!(early, Err(TaskError::InvalidDate(_))));
+    let stored = store.get_task(task_id).unwrap();
+    assert_eq!((stored.due_date, stored.all_day), (Some(at("2025-03-10 23:59")), true));
+
+    store.update_task(task_id, None, None, None, None, Some(at("2025-03-12 14:00")), None).unwrap();
+    let stored = store.get_task(task_id).unwrap();
+    assert_eq!((stored.due_date, stored.all_day), (Some(at("2025-03-12 14:00")), false));
+}
+
+fn update_with_unsaved_tag_changes_nothing(store: &mut impl Store) {
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[tag("Physics", "red")]);
+    let task_id = task.id.unwrap();
+
+    let result = store.update_task(
+        task_id,
+        Some("Review".to_string()),
+        None,
+        None,
+        None,
+        None,
+        Some(vec![tag("Math", "blue")]),
+    );
+
+    assert!(matches!(result, Err(TaskError::InvalidTag(_))));
+    let stored = store.get_task(task_id).unwrap();
+    assert_eq!(stored.title, "Study");
+    assert_eq!(names(&stored.tags), ["Physics"]);
+}
+
+fn deleting_a_task_keeps_its_tags(store: &mut impl Store) {
+    let task = new_task(store, "Study", "2025-03-10 09:00", &[tag("Physics", "red")]);
+    let task_id = task.id.unwrap();
+
+    store.delete_task(task_id).unwrap();
+
+    assert!(store.get_task(task_id).is_err());
+    assert!(store.list_tasks().unwrap().is_empty());
+    assert!(store.get_task_tags(task_id).unwrap().is_empty());
+    assert_eq!(store.list_tags().unwrap().len(), 1);
+}
+
+fn due_range_is_inclusive_and_ordered(store: &mut impl Store) {
+    let late = new_task(store, "Late", "2025-03-10 23:59", &[]);
+    let early = new_task(store, "Early", "2025-03-10 00:00", &[]);
+    new_task(store, "Yesterday", "2025-03-09 23:59", &[]);
+    new_task(store, "Tomorrow", "2025-03-11 00:00", &[]);
+
+    let due = store
+        .get_tasks_due_between(
+            at("2025-03-10 00:00"),
+            NaiveDateTime::parse_from_str("2025-03-10 23:59:59", "%Y-%m-%d %H:%M:%S").unwrap(),
+        )
+        .unwrap();
+
+    let ids: Vec<_> = due.iter().map(|task| task.id).collect();
+    assert_eq!(ids, [early.id, late.id]);
+}
+
+fn titles(tasks: &[Task]) -> Vec<&str> {
+    tasks.iter().map(|task| task.title.as_str()).collect()
+}
+
+fn new_tasks_are_appended_to_their_column(store: &mut impl Store) {
+    let user_id = user_id(store);
+    new_task(store, "First", "2025-03-12 09:00", &[]);
+    new_task(store, "Second", "2025-03-10 09:00", &[]);
+    new_task(store, "Third", "2025-03-11 09:00", &[]);
+
+    let column = store.list_column(user_id, &TaskStatus::Todo).unwrap();
+    assert_eq!(titles(&column), ["First", "Second", "Third"]);
+    assert!(store.list_column(user_id, &TaskStatus::Done).unwrap().is_empty());
+}
+
+fn tasks_move_within_and_across_columns(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let first = new_task(store, "First", "2025-03-10 09:00", &[]);
+    new_task(store, "Second", "2025-03-10 09:00", &[]);
+    let third = new_task(store, "Third", "2025-03-10 09:00", &[]);
+
+    store.move_task(third.id.unwrap(), TaskStatus::Todo, Some(1)).unwrap();
+    assert_eq!(titles(&store.list_column(user_id, &TaskStatus::Todo).unwrap()), ["First", "Third", "Second"]);
+
+    let moved = store.move_task(first.id.unwrap(), TaskStatus::Done, None).unwrap();
+    assert_eq!(moved.status, TaskStatus::Done);
+    assert_eq!(titles(&store.list_column(user_id, &TaskStatus::Todo).unwrap()), ["Third", "Second"]);
+
+    store.move_task(third.id.unwrap(), TaskStatus::Done, Some(0)).unwrap();
+    assert_eq!(titles(&store.list_column(user_id, &TaskStatus::Done).unwrap()), ["Third", "First"]);
+
+    store.move_task(first.id.unwrap(), TaskStatus::Done, Some(99)).unwrap();
+    assert_eq!(titles(&store.list_column(user_id, &TaskStatus::Done).unwrap()), ["Third", "First"]);
+    assert!(store.move_task(999, TaskStatus::Done, None).is_err());
+}
+
+fn status_changes_append_to_the_new_column(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let first = new_task(store, "First", "2025-03-10 09:00", &[]);
+    let second = new_task(store, "Second", "2025-03-10 09:00", &[]);
+    for task in [&second, &first] {
+        store
+            .update_task(task.id.unwrap(), None, None, Some(TaskStatus::Done), None, None, None)
+            .unwrap();
+    }
+
+    assert_eq!(titles(&store.list_column(user_id, &TaskStatus::Done).unwrap()), ["Second", "First"]);
+}
+
+fn wip_limits_block_moves_into_full_columns(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let first = new_task(store, "First", "2025-03-10 09:00", &[]);
+    let second = new_task(store, "Second", "2025-03-10 09:00", &[]);
+    assert_eq!(store.wip_limit(user_id, &TaskStatus::InProgress).unwrap(), None);
+
+    store.set_wip_limit(user_id, &TaskStatus::InProgress, Some(1)).unwrap();
+    assert_eq!(store.wip_limit(user_id, &TaskStatus::InProgress).unwrap(), Some(1));
+    assert!(store.set_wip_limit(user_id, &TaskStatus::InProgress, Some(0)).is_err());
+
+    store.move_task(first.id.unwrap(), TaskStatus::InProgress, None).unwrap();
+    store.move_task(first.id.unwrap(), TaskStatus::InProgress, Some(0)).unwrap();
+
+    let moved = store.move_task(second.id.unwrap(), TaskStatus::InProgress, None);
+    assert!(matches!(moved, Err(TaskError::WipLimitReached(_))));
+    let updated = store.update_task(
+        second.id.unwrap(),
+        Some("Renamed".to_string()),
+        None,
+        Some(TaskStatus::InProgress),
+        None,
+        None,
+        None,
+    );
+    assert!(matches!(updated, Err(TaskError::WipLimitReached(_))));
+    let stored = store.get_task(second.id.unwrap()).unwrap();
+    assert_eq!((stored.title.as_str(), stored.status), ("Second", TaskStatus::Todo));
+
+    store.set_wip_limit(user_id, &TaskStatus::InProgress, None).unwrap();
+    store.move_task(second.id.unwrap(), TaskStatus::InProgress, None).unwrap();
+    assert_eq!(store.list_column(user_id, &TaskStatus::InProgress).unwrap().len(), 2);
+}
+
+fn columns_belong_to_their_user(store: &mut impl Store) {
+    let alice = new_task(store, "Alice's", "2025-03-10 09:00", &[]);
+    let mut bob = User::new("Bob".to_string()).unwrap();
+    store.create_user(&mut bob).unwrap();
+    let mut bobs = Task::new("Bob's".to_string(), bob.id.unwrap(), None, None).unwrap();
+    store.insert_task(&mut bobs, &[]).unwrap();
+
+    assert_eq!(titles(&store.list_column(alice.user_id, &TaskStatus::Todo).unwrap()), ["Alice's"]);
+    assert_eq!(bobs.rank, alice.rank);
+
+    store.set_wip_limit(alice.user_id, &TaskStatus::InProgress, Some(1)).unwrap();
+    store.set_wip_limit(bob.id.unwrap(), &TaskStatus::InProgress, Some(1)).unwrap();
+    store.move_task(alice.id.unwrap(), TaskStatus::InProgress, None).unwrap();
+    store.move_task(bobs.id.unwrap(), TaskStatus::InProgress, Some(0)).unwrap();
+    assert_eq!(titles(&store.list_column(bob.id.unwrap(), &TaskStatus::InProgress).unwrap()), ["Bob's"]);
+    assert_eq!(store.get_task(alice.id.unwrap()).unwrap().rank, alice.rank);
+
+    store.set_wip_limit(alice.user_id, &TaskStatus::InProgress, None).unwrap();
+    assert_eq!(store.wip_limit(bob.id.unwrap(), &TaskStatus::InProgress).unwrap(), Some(1));
+    let unknown = TaskStatus::Custom("reading".to_string());
+    assert!(matches!(store.set_wip_limit(alice.user_id, &unknown, Some(2)), Err(TaskError::InvalidStatus(_))));
+}
+
+fn statuses_must_be_defined_for_the_user(store: &mut impl Store) {
+    let user_id = user_id(store);
+    let reading = TaskStatus::Custom("reading".to_string());
+
+    let mut task = Task::new("Novel".to_string(), user_id, None, None).unwrap();
+    task.status = reading.clone();
+    assert!(matches!(store.insert_task(&mut task, &[]), Err(TaskError::InvalidStatus(_))));
+    assert!(store.list_tasks().unwrap().is_empty());
+
+    let task = new_task(store, "Essay", "2025-03-10 09:00", &[]);
+    let task_id = task.id.unwrap();
+    let updated = store.update_task(task_id, None, None, Some(reading.clone()), None, None, None);
+    assert!(matches!(updated, Err(TaskError::InvalidStatus(_))));
+    assert!(matches!(store.move_task(task_id, reading, None), Err(TaskError::InvalidStatus(_))));
+    assert_eq!(store.get_task(task_id).unwrap().status, TaskStatus::Todo);
+}
+
+fn completion_is_stamped_and_cleared(store: &mut impl Store) {
+    let task = new_task(store, "Essay", "2025-03-10 09:00", &[]);
+    let task_id = task.id.unwrap();
+    assert_eq!(task.completed_at, None);
+    assert!(store.get_task(task_id).unwrap().overdue);
+
+    store.update_task(task_id, None, None, Some(TaskStatus::Done), None, None, None).unwrap();
+    let done = store.get_task(task_id).unwrap();
+    assert!(done.completed_at.is_some());
+    assert!(!done.overdue);
+
+    store.move_task(task_id, TaskStatus::Done, Some(0)).unwrap();
+    assert_eq!(store.get_task(task_id).unwrap().completed_at, done.completed_at);
+
+    let reopened = store.move_task(task_id, TaskStatus::InProgress, None).unwrap();
+    assert_eq!(reopened.completed_at, None);
+    assert!(reopened.overdue);
+}
+
+macro_rules! conformance {
+    ($($check:ident),* $(,)?) => {
+        mod sqlite {
+            $(
+                #[test]
+                fn $check() {
+                    super::with_sqlite(|store| super::$check(store));
+                }
+            )*
+        }
+
+        mod in_memory {
+            $(
+                #[test]
+                fn $check() {
+                    super::with_memory(|store| super::$check(store));
+                }
+            )*
+        }
+    };
+}
+
+conformance!(
+    users_are_counted_by_status,
+    tags_round_trip,
+    deleting_a_tag_unlinks_it,
+    task_tags_are_matched_by_name,
+    duplicate_task_tags_roll_back,
+    tasks_round_trip,
+    missing_task_is_an_error,
+    task_references_are_enforced,
+    tasks_are_listed_in_insertion_order,
+    tasks_are_updated,
+    due_date_updates_keep_dates_consistent,
+    update_with_unsaved_tag_changes_nothing,
+    deleting_a_task_keeps_its_tags,
+    due_range_is_inclusive_and_ordered,
+    new_tasks_are_appended_to_their_column,
+    tasks_move_within_and_across_columns,
+    status_changes_append_to_the_new_column,
+    wip_limits_block_moves_into_full_columns,
+    columns_belong_to_their_user,
+    statuses_must_be_defined_for_the_user,
+    completion_is_stamped_and_cleared,
+);
@@ -0,0 +1,311 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+
+use crate::errors::GoalError;
+use crate::models::{Calendar, Goal, GoalMetric, GoalProgress};
+use crate::repository::{SettingsRepository, SubjectRepository};
+use crate::utils::{SqliteDate, SqliteDateTime};
+
+const GOAL_COLUMNS: &str = "id, user_id, title, metric, period, target, subject_id, starts_on";
+
+pub struct GoalRepository;
+
+impl GoalRepository {
+    pub fn create(conn: &Connection, goal: &mut Goal) -> Result<(), GoalError> {
+        GoalRepository::check(conn, goal)?;
+        conn.execute(
+            "INSERT INTO goals (user_id, title, metric, period, target, subject_id, starts_on)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                goal.user_id,
+                goal.title.trim(),
+                goal.metric,
+                goal.period,
+                goal.target,
+                goal.subject_id,
+                goal.starts_on.format("%Y-%m-%d").to_string(),
+            ],
+        )?;
+        goal.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn update(conn: &Connection, goal: &Goal) -> Result<(), GoalError> {
+        let id = goal.id.ok_or_else(|| GoalError::DatabaseError("Goal id missing".into()))?;
+        GoalRepository::check(conn, goal)?;
+        let updated = conn.execute(
+            "UPDATE goals SET title = ?2, metric = ?3, period = ?4, target = ?5, subject_id = ?6, starts_on = ?7
+             WHERE id = ?1",
+            params![
+                id,
+                goal.title.trim(),
+                goal.metric,
+                goal.period,
+                goal.target,
+                goal.subject_id,
+                goal.starts_on.format("%Y-%m-%d").to_string(),
+            ],
+        )?;
+        if updated == 0 {
+            return Err(GoalError::NotFound(id));
+        }
+        Ok(())
+    }
+
+    pub fn find(conn: &Connection, goal_id: u32) -> Result<Goal, GoalError> {
+        conn.query_row(
+            &format!("SELECT {} FROM goals WHERE id = ?1", GOAL_COLUMNS),
+            params![goal_id],
+            |row| Goal::try_from(row),
+        )
+        .optional()?
+        .ok_or(GoalError::NotFound(goal_id))
+    }
+
+    pub fn list(conn: &Connection, user_id: u32) -> Result<Vec<Goal>, GoalError> {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM goals WHERE user_id = ?1 ORDER BY id", GOAL_COLUMNS))?;
+        let goals = stmt
+            .query_map(params![user_id], |row| Goal::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(goals)
+    }
+
+    pub fn delete(conn: &Connection, goal_id: u32) -> Result<bool, GoalError> {
+        Ok(conn.execute("DELETE FROM goals WHERE id = ?1", params![goal_id])? > 0)
+    }
+
+    /// Marks a local day as frozen for all of the user's goals.
+    pub fn freeze_day(conn: &Connection, user_id: u32, date: NaiveDate) -> Result<(), GoalError> {
+        conn.execute(
+            "INSERT OR IGNORE INTO streak_freezes (user_id, date) VALUES (?1, ?2)",
+            params![user_id, date.format("%Y-%m-%d").to_string()],
+        )?;
+        Ok(())
+    }
+
+    pub fn unfreeze_day(conn: &Connection, user_id: u32, date: NaiveDate) -> Result<bool, GoalError> {
+        Ok(conn.execute(
+            "DELETE FROM streak_freezes WHERE user_id = ?1 AND date = ?2",
+            params![user_id, date.format("%Y-%m-%d").to_string()],
+        )? > 0)
+    }
+
+    pub fn freezes(conn: &Connection, user_id: u32) -> Result<Vec<NaiveDate>, GoalError> {
+        let mut stmt = conn.prepare("SELECT date FROM streak_freezes WHERE user_id = ?1 ORDER BY date")?;
+        let dates = stmt
+            .query_map(params![user_id], |row| row.get::<_, SqliteDate>(0).map(Into::into))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(dates)
+    }
+
+    /// Progress and streaks as of `now` (UTC), in the user's calendar.
+    pub fn progress(conn: &Connection, goal_id: u32, now: NaiveDateTime) -> Result<GoalProgress, GoalError> {
+        let goal = GoalRepository::find(conn, goal_id)?;
+        let calendar = SettingsRepository::get(conn, goal.user_id)?.calendar();
+        let freezes: HashSet<NaiveDate> = GoalRepository::freezes(conn, goal.user_id)?.into_iter().collect();
+        GoalRepository::evaluate(conn, goal, &freezes, now, &calendar)
+    }
+
+    pub fn progress_for_user(conn: &Connection, user_id: u32, now: NaiveDateTime) -> Result<Vec<GoalProgress>, GoalError> {
+        let calendar = SettingsRepository::get(conn, user_id)?.calendar();
+        let freezes: HashSet<NaiveDate> = GoalRepository::freezes(conn, user_id)?.into_iter().collect();
+        GoalRepository::list(conn, user_id)?
+            .into_iter()
+            .map(|goal| GoalRepository::evaluate(conn, goal, &freezes, now, &calendar))
+            .collect()
+    }
+
+    fn evaluate(
+        conn: &Connection,
+        goal: Goal,
+        freezes: &HashSet<NaiveDate>,
+        now: NaiveDateTime,
+        calendar: &Calendar,
+    ) -> Result<GoalProgress, GoalError> {
+        let today = calendar.local_date(now);
+        let (from, _) = goal.period.bounds(goal.starts_on.min(today), calendar);
+        let (_, to) = goal.period.bounds(today, calendar);
+        let values = GoalRepository::daily_values(conn, &goal, calendar, from, to)?;
+        Ok(GoalProgress::new(goal, &values, freezes, today, calendar))
+    }
+
+    /// The goal's metric per local day from `from` to `to`, inclusive.
+    fn daily_values(
+        conn: &Connection,
+        goal: &Goal,
+        calendar: &Calendar,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<NaiveDate, u32>, GoalError> {
+        let mut values = HashMap::new();
+
+        // Reading sessions are kept per local day; the rest are UTC
+        // timestamps, bucketed into the user's local days.
+        let sql = match goal.metric {
+            GoalMetric::ReadingUnits => return GoalRepository::reading_values(conn, goal, from, to),
+            GoalMetric::StudyMinutes => {
+                "SELECT l.started_at, l.minutes FROM time_logs l JOIN tasks t ON t.id = l.task_id
+                 WHERE t.user_id = ?1 AND (?2 IS NULL OR t.subject_id = ?2) AND l.minutes IS NOT NULL
+                    AND l.started_at >= ?3 AND l.started_at < ?4"
+            }
+            GoalMetric::TasksCompleted => {
+                "SELECT t.completed_at, 1 FROM tasks t
+                 WHERE t.user_id = ?1 AND (?2 IS NULL OR t.subject_id = ?2)
+                    AND t.completed_at >= ?3 AND t.completed_at < ?4"
+            }
+            GoalMetric::QuestionsAnswered => {
+                "SELECT a.finished_at, COUNT(*) FROM quiz_attempts a JOIN quiz_answers qa ON qa.attempt_id = a.id
+                 WHERE a.user_id = ?1 AND ?2 IS NULL AND a.finished_at >= ?3 AND a.finished_at < ?4
+                 GROUP BY a.id"
+            }
+        };
+        let start = calendar.day_start(from).format("%Y-%m-%dT%H:%M").to_string();
+        let end = calendar.day_start(to + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params![goal.user_id, goal.subject_id, start, end], |row| {
+            Ok((row.get::<_, SqliteDateTime>(0)?.into(), row.get::<_, u32>(1)?))
+        })?;
+        for row in rows {
+            let (at, value): (NaiveDateTime, u32) = row?;
+            *values.entry(calendar.local_date(at)).or_insert(0) += value;
+        }
+        Ok(values)
+    }
+
+    fn reading_values(
+        conn: &Connection,
+        goal: &Goal,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<NaiveDate, u32>, GoalError> {
+        let mut values = HashMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT r.read_on, r.units FROM reading_sessions r JOIN books b ON b.id = r.book_id
+             WHERE b.user_id = ?1 AND r.read_on >= ?2 AND r.read_on <= ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![goal.user_id, from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string()],
+            |row| Ok((row.get::<_, SqliteDate>(0)?.into(), row.get::<_, u32>(1)?)),
+        )?;
+        for row in rows {
+            let (date, units): (NaiveDate, u32) = row?;
+            *values.entry(date).or_insert(0) += units;
+        }
+        Ok(values)
+    }
+
+    fn check(conn: &Connection, goal: &Goal) -> Result<(), GoalError> {
+        goal.validate()?;
+        if let Some(subject_id) = goal.subject_id {
+            SubjectRepository::find(conn, subject_id).map_err(|_| GoalError::SubjectNotFound(subject_id))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, GoalPeriod, Task, UserSettings};
+    use crate::repository::{TaskRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+    use chrono::Weekday;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_goal(conn: &Connection, metric: GoalMetric, period: GoalPeriod, target: u32) -> u32 {
+        let mut goal = Goal {
+            id: None,
+            user_id: 1,
+            title: "Goal".to_string(),
+            metric,
+            period,
+            target,
+            subject_id: None,
+            starts_on: date("2025-03-01"),
+        };
+        GoalRepository::create(conn, &mut goal).unwrap();
+        goal.id.unwrap()
+    }
+
+    fn log_minutes(conn: &Connection, task_id: u32, started_at: &str, minutes: u32) {
+        conn.execute(
+            "INSERT INTO time_logs (task_id, started_at, ended_at, minutes) VALUES (?1, ?2, ?2, ?3)",
+            params![task_id, at(started_at).format("%Y-%m-%dT%H:%M").to_string(), minutes],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_study_minutes_use_the_users_time_zone() {
+        let conn = setup_db();
+        let mut task = Task::new("Physics".to_string(), 1, None, None).unwrap();
+        TaskRepository::insert_task(&conn, &mut task).unwrap();
+        // 01:30 UTC on the 11th is still the 10th at UTC-3.
+        log_minutes(&conn, task.id.unwrap(), "2025-03-11 01:30", 60);
+        let goal_id = create_goal(&conn, GoalMetric::StudyMinutes, GoalPeriod::Daily, 60);
+
+        let utc = GoalRepository::progress(&conn, goal_id, at("2025-03-11 12:00")).unwrap();
+        assert!(utc.met);
+
+        let settings = UserSettings { user_id: 1, week_start: Weekday::Mon, utc_offset_minutes: Some(-180) };
+        SettingsRepository::save(&conn, &settings).unwrap();
+        let brazil = GoalRepository::progress(&conn, goal_id, at("2025-03-11 12:00")).unwrap();
+        assert_eq!((brazil.value, brazil.current_streak), (0, 1));
+    }
+
+    #[test]
+    fn test_tasks_completed_and_freezes() {
+        let conn = setup_db();
+        SettingsRepository::save(&conn, &UserSettings { user_id: 1, week_start: Weekday::Mon, utc_offset_minutes: Some(0) })
+            .unwrap();
+        let mut task = Task::new("Essay".to_string(), 1, None, None).unwrap();
+        TaskRepository::insert_task(&conn, &mut task).unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, task.id.unwrap(), None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+        conn.execute("UPDATE tasks SET completed_at = '2025-03-09T10:00'", []).unwrap();
+        let goal_id = create_goal(&conn, GoalMetric::TasksCompleted, GoalPeriod::Daily, 1);
+
+        GoalRepository::freeze_day(&conn, 1, date("2025-03-10")).unwrap();
+        let progress = GoalRepository::progress(&conn, goal_id, at("2025-03-11 08:00")).unwrap();
+        assert_eq!(progress.current_streak, 1);
+
+        GoalRepository::unfreeze_day(&conn, 1, date("2025-03-10")).unwrap();
+        let progress = GoalRepository::progress(&conn, goal_id, at("2025-03-11 08:00")).unwrap();
+        assert_eq!((progress.current_streak, progress.best_streak), (0, 1));
+    }
+
+    #[test]
+    fn test_subject_filter_only_for_supported_metrics() {
+        let conn = setup_db();
+        let mut goal = Goal {
+            id: None,
+            user_id: 1,
+            title: "Read".to_string(),
+            metric: GoalMetric::ReadingUnits,
+            period: GoalPeriod::Daily,
+            target: 10,
+            subject_id: Some(1),
+            starts_on: date("2025-03-01"),
+        };
+
+        assert!(matches!(GoalRepository::create(&conn, &mut goal), Err(GoalError::InvalidGoal(_))));
+    }
+}
@@ -0,0 +1,446 @@
+use chrono::{NaiveDateTime, Utc};
+
+use crate::errors::{TagError, TaskError, UserError};
+use crate::models::{
+    task_priority::TaskPriority,
+    task_status::TaskStatus,
+    StatusCategory, Tag, Task, User, UserStatus, WorkflowStatus,
+};
+use crate::repository::{TagStore, TaskStore, UserStore};
+use crate::utils::truncate_to_minute;
+use crate::utils::rank::{initial_ranks, rank_between};
+
+const FOREIGN_KEY_FAILED: &str = "FOREIGN KEY constraint failed";
+
+/// Pure in-memory backend for the repository traits, mirroring the SQLite
+/// behaviour (including foreign keys) closely enough to pass the same
+/// conformance suite.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStore {
+    users: Vec<User>,
+    tags: Vec<Tag>,
+    tasks: Vec<Task>,
+    task_tags: Vec<(u32, u32)>,
+    wip_limits: Vec<(u32, TaskStatus, u32)>,
+    statuses: Vec<WorkflowStatus>,
+    next_user_id: u32,
+    next_tag_id: u32,
+    next_task_id: u32,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `f` to a copy of the store and keeps it only on success.
+    fn atomically<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        let mut draft = self.clone();
+        let value = f(&mut draft)?;
+        *self = draft;
+        Ok(value)
+    }
+
+    fn link(&mut self, task_id: u32, tag_id: u32) -> Result<(), String> {
+        let task_exists = self.tasks.iter().any(|task| task.id == Some(task_id));
+        let tag_exists = self.tags.iter().any(|tag| tag.id == Some(tag_id));
+        if !task_exists || !tag_exists {
+            return Err(FOREIGN_KEY_FAILED.to_string());
+        }
+        if self.task_tags.contains(&(task_id, tag_id)) {
+            return Err("UNIQUE constraint failed: task_tags.task_id, task_tags.tag_id".to_string());
+        }
+        self.task_tags.push((task_id, tag_id));
+        Ok(())
+    }
+
+    /// Tasks of one of the user's columns other than `excluding`, in rank order.
+    fn column(&self, user_id: u32, status: &TaskStatus, excluding: Option<u32>) -> Vec<&Task> {
+        let mut column: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| task.user_id == user_id && &task.status == status && task.id != excluding)
+            .collect();
+        column.sort_by(|a, b| (&a.rank, a.id).cmp(&(&b.rank, b.id)));
+        column
+    }
+
+    fn next_rank(&self, user_id: u32, status: &TaskStatus) -> Result<String, TaskError> {
+        let last = self
+            .column(user_id, status, None)
+            .into_iter()
+            .map(|task| task.rank.as_str())
+            .rfind(|rank| !rank.is_empty());
+        rank_between(last, None).map_err(TaskError::DatabaseError)
+    }
+
+    fn check_status_defined(&self, user_id: u32, status: &TaskStatus) -> Result<(), TaskError> {
+        if !self
+            .statuses
+            .iter()
+            .any(|definition| definition.user_id == user_id && &definition.status == status)
+        {
+            return Err(TaskError::InvalidStatus(format!(
+                "'{}' is not one of user {}'s statuses", status, user_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_wip_limit(&self, user_id: u32, status: &TaskStatus, task_id: u32) -> Result<(), TaskError> {
+        let Some(limit) = self.wip_limit(user_id, status)? else {
+            return Ok(());
+        };
+        let count = self.column(user_id, status, Some(task_id)).len() as u32;
+        if count >= limit {
+            return Err(TaskError::WipLimitReached(format!(
+                "column '{}' already has {} of {} tasks", status, count, limit
+            )));
+        }
+        Ok(())
+    }
+
+    fn rank_at(&self, user_id: u32, status: &TaskStatus, task_id: u32, index: usize) -> Result<String, String> {
+        let column = self.column(user_id, status, Some(task_id));
+        let before = index.checked_sub(1).map(|i| column[i].rank.as_str());
+        rank_between(before, column.get(index).map(|task| task.rank.as_str()))
+    }
+
+    fn renumber_column(&mut self, user_id: u32, status: &TaskStatus, task_id: u32) {
+        let ids: Vec<Option<u32>> = self.column(user_id, status, Some(task_id)).iter().map(|task| task.id).collect();
+        for (id, rank) in ids.iter().zip(initial_ranks(ids.len())) {
+            if let Some(task) = self.tasks.iter_mut().find(|task| &task.id == id) {
+                task.rank = rank;
+            }
+        }
+    }
+
+    fn with_tags(&self, task: &Task) -> Task {
+        let mut task = task.clone();
+        task.tags = task.id.map(|id| self.linked_tags(id)).unwrap_or_default();
+        task.overdue = task.is_overdue(truncate_to_minute(Utc::now().naive_utc()));
+        task
+    }
+
+    /// Same rule as `TaskRepository::sync_completed_at`.
+    fn sync_completed_at(&mut self, task_id: u32, now: NaiveDateTime) {
+        let statuses = &self.statuses;
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.id == Some(task_id)) {
+            let completed = statuses.iter().any(|status| {
+                status.user_id == task.user_id
+                    && status.status == task.status
+                    && status.category == StatusCategory::Completed
+            });
+            task.completed_at = match completed {
+                true => Some(task.completed_at.unwrap_or(now)),
+                false => None,
+            };
+        }
+    }
+
+    fn linked_tags(&self, task_id: u32) -> Vec<Tag> {
+        self.tags
+            .iter()
+            .filter(|tag| {
+                tag.id
+                    .is_some_and(|tag_id| self.task_tags.contains(&(task_id, tag_id)))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl UserStore for InMemoryStore {
+    fn create_user(&mut self, user: &mut User) -> Result<(), UserError> {
+        self.next_user_id += 1;
+        user.id = Some(self.next_user_id);
+        self.users.push(user.clone());
+        self.statuses.extend(WorkflowStatus::defaults(self.next_user_id));
+        Ok(())
+    }
+
+    fn count_active_users(&self) -> Result<u32, UserError> {
+        Ok(self
+            .users
+            .iter()
+            .filter(|user| user.status == UserStatus::Active)
+            .count() as u32)
+    }
+
+    fn find_active_user_id(&self) -> Result<Option<u32>, UserError> {
+        Ok(self
+            .users
+            .iter()
+            .find(|user| user.status == UserStatus::Active)
+            .and_then(|user| user.id))
+    }
+}
+
+impl TagStore for InMemoryStore {
+    fn create_tag(&mut self, tag: &mut Tag) -> Result<(), TagError> {
+        self.next_tag_id += 1;
+        tag.id = Some(self.next_tag_id);
+        self.tags.push(tag.clone());
+        Ok(())
+    }
+
+    fn rename_tag(&mut self, id: u32, tag_name: String) -> Result<(), TagError> {
+        if let Some(tag) = self.tags.iter_mut().find(|tag| tag.id == Some(id)) {
+            tag.name = tag_name;
+        }
+        Ok(())
+    }
+
+    fn find_tag(&self, id: u32) -> Result<Option<Tag>, TagError> {
+        Ok(self.tags.iter().find(|tag| tag.id == Some(id)).cloned())
+    }
+
+    fn list_tags(&self) -> Result<Vec<Tag>, TagError> {
+        Ok(self.tags.clone())
+    }
+
+    fn delete_tag(&mut self, id: u32) -> Result<bool, TagError> {
+        self.task_tags.retain(|&(_, tag_id)| tag_id != id);
+        let before = self.tags.len();
+        self.tags.retain(|tag| tag.id != Some(id));
+        Ok(self.tags.len() < before)
+    }
+
+    fn get_task_tags(&self, task_id: u32) -> Result<Vec<Tag>, TagError> {
+        Ok(self.linked_tags(task_id))
+    }
+
+    fn set_task_tags(&mut self, task_id: u32, tags: &[Tag]) -> Result<(), TagError> {
+        self.atomically(|store| {
+            store.task_tags.retain(|&(linked_task, _)| linked_task != task_id);
+
+            for tag in tags {
+                let existing = store
+                    .tags
+                    .iter()
+                    .find(|stored| stored.name == tag.name)
+                    .and_then(|stored| stored.id);
+
+                let tag_id = match existing {
+                    Some(id) => id,
+                    None => {
+                        let mut created = Tag {
+                            id: None,
+                            name: tag.name.clone(),
+                            color: tag.color.clone(),
+                        };
+                        store.create_tag(&mut created)?;
+                        store.next_tag_id
+                    }
+                };
+
+                store.link(task_id, tag_id).map_err(TagError::DatabaseError)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl TaskStore for InMemoryStore {
+    fn insert_task(&mut self, task: &mut Task, tags: &[Tag]) -> Result<(), TaskError> {
+        if !self.users.iter().any(|user| user.id == Some(task.user_id)) {
+            return Err(TaskError::DatabaseError(FOREIGN_KEY_FAILED.to_string()));
+        }
+        self.check_status_defined(task.user_id, &task.status)?;
+        task.check_dates()?;
+
+        let inserted = self.atomically(|store| {
+            let mut stored = task.clone();
+            store.next_task_id += 1;
+            stored.id = Some(store.next_task_id);
+            stored.created_at = truncate_to_minute(stored.created_at);
+            stored.updated_at = truncate_to_minute(stored.updated_at);
+            stored.due_date = stored.due_date.map(truncate_to_minute);
+            stored.start_date = stored.start_date.map(truncate_to_minute);
+            stored.rank = store.next_rank(stored.user_id, &stored.status)?;
+            stored.completed_at = stored.completed_at.map(truncate_to_minute);
+            stored.tags = Vec::new();
+            store.tasks.push(stored.clone());
+            store.sync_completed_at(store.next_task_id, stored.updated_at);
+            let stored = store.get_task(store.next_task_id)?;
+
+            store
+                .set_task_tags(store.next_task_id, tags)
+                .map_err(|e| TaskError::DatabaseError(format!("Failed to associate tags: {}", e)))?;
+            Ok::<_, TaskError>(store.with_tags(&stored))
+        })?;
+
+        task.id = inserted.id;
+        task.rank = inserted.rank;
+        task.completed_at = inserted.completed_at;
+        task.tags = inserted.tags;
+        Ok(())
+    }
+
+    fn get_task(&self, task_id: u32) -> Result<Task, TaskError> {
+        self.tasks
+            .iter()
+            .find(|task| task.id == Some(task_id))
+            .map(|task| self.with_tags(task))
+            .ok_or_else(|| TaskError::DatabaseError("Query returned no rows".to_string()))
+    }
+
+    fn list_tasks(&self) -> Result<Vec<Task>, TaskError> {
+        Ok(self.tasks.iter().map(|task| self.with_tags(task)).collect())
+    }
+
+    fn update_task(
+        &mut self,
+        task_id: u32,
+        title: Option<String>,
+        description: Option<String>,
+        status: Option<TaskStatus>,
+        priority: Option<TaskPriority>,
+        due_date: Option<NaiveDateTime>,
+        tags: Option<Vec<Tag>>,
+    ) -> Result<(), TaskError> {
+        if let Some(tags) = &tags {
+            if let Some(tag) = tags.iter().find(|tag| tag.id.is_none()) {
+                return Err(TaskError::InvalidTag(format!(
+                    "A tag '{}' não tem um ID associado", tag.name
+                )));
+            }
+        }
+
+        let status_changed = status.is_some();
+        self.atomically(|store| {
+            let moved_to = status.clone().and_then(|status| {
+                store
+                    .tasks
+                    .iter()
+                    .find(|task| task.id == Some(task_id) && task.status != status)
+                    .map(|task| (task.user_id, status))
+            });
+            let rank = match &moved_to {
+                Some((user_id, status)) => {
+                    store.check_status_defined(*user_id, status)?;
+                    store.check_wip_limit(*user_id, status, task_id)?;
+                    Some(store.next_rank(*user_id, status)?)
+                }
+                None => None,
+            };
+
+            if let Some(task) = store.tasks.iter_mut().find(|task| task.id == Some(task_id)) {
+                if let Some(rank) = rank {
+                    task.rank = rank;
+                }
+                if let Some(title) = title {
+                    task.title = title;
+                }
+                if let Some(description) = description {
+                    task.description = Some(description);
+                }
+                if let Some(status) = status {
+                    task.status = status;
+                }
+                if let Some(priority) = priority {
+                    task.priority = priority;
+                }
+                if let Some(due_date) = due_date {
+                    task.due_date = Some(truncate_to_minute(due_date));
+                    task.all_day = false;
+                    task.check_dates()?;
+                }
+                task.updated_at = truncate_to_minute(Utc::now().naive_utc());
+            }
+            if status_changed {
+                store.sync_completed_at(task_id, truncate_to_minute(Utc::now().naive_utc()));
+            }
+
+            if let Some(tags) = tags {
+                store.task_tags.retain(|&(linked_task, _)| linked_task != task_id);
+                for tag in tags.iter().filter_map(|tag| tag.id) {
+                    store.link(task_id, tag).map_err(TaskError::DatabaseError)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_task(&mut self, task_id: u32) -> Result<(), TaskError> {
+        self.task_tags.retain(|&(linked_task, _)| linked_task != task_id);
+        self.tasks.retain(|task| task.id != Some(task_id));
+        Ok(())
+    }
+
+    fn list_column(&self, user_id: u32, status: &TaskStatus) -> Result<Vec<Task>, TaskError> {
+        Ok(self
+            .column(user_id, status, None)
+            .into_iter()
+            .map(|task| self.with_tags(task))
+            .collect())
+    }
+
+    fn move_task(&mut self, task_id: u32, status: TaskStatus, index: Option<usize>) -> Result<Task, TaskError> {
+        self.atomically(|store| {
+            let current = store.get_task(task_id)?;
+            if current.status != status {
+                store.check_status_defined(current.user_id, &status)?;
+                store.check_wip_limit(current.user_id, &status, task_id)?;
+            }
+
+            let user_id = current.user_id;
+            let len = store.column(user_id, &status, Some(task_id)).len();
+            let index = index.unwrap_or(len).min(len);
+            let rank = match store.rank_at(user_id, &status, task_id, index) {
+                Ok(rank) => rank,
+                Err(_) => {
+                    store.renumber_column(user_id, &status, task_id);
+                    store.rank_at(user_id, &status, task_id, index).map_err(TaskError::DatabaseError)?
+                }
+            };
+
+            if let Some(task) = store.tasks.iter_mut().find(|task| task.id == Some(task_id)) {
+                task.status = status;
+                task.rank = rank;
+                task.updated_at = truncate_to_minute(Utc::now().naive_utc());
+            }
+            store.sync_completed_at(task_id, truncate_to_minute(Utc::now().naive_utc()));
+            store.get_task(task_id)
+        })
+    }
+
+    fn wip_limit(&self, user_id: u32, status: &TaskStatus) -> Result<Option<u32>, TaskError> {
+        Ok(self
+            .wip_limits
+            .iter()
+            .find(|(owner, column, _)| *owner == user_id && column == status)
+            .map(|&(_, _, limit)| limit))
+    }
+
+    fn set_wip_limit(&mut self, user_id: u32, status: &TaskStatus, limit: Option<u32>) -> Result<(), TaskError> {
+        if limit == Some(0) {
+            return Err(TaskError::InvalidStatus("WIP limit must be greater than zero".to_owned()));
+        }
+        self.wip_limits.retain(|(owner, column, _)| *owner != user_id || column != status);
+        if let Some(limit) = limit {
+            self.check_status_defined(user_id, status)?;
+            self.wip_limits.push((user_id, status.clone(), limit));
+        }
+        Ok(())
+    }
+
+    fn get_tasks_due_between(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError> {
+        let (start, end) = (truncate_to_minute(start), truncate_to_minute(end));
+        let mut tasks: Vec<Task> = self
+            .tasks
+            .iter()
+            .filter(|task| task.due_date.is_some_and(|due| due >= start && due <= end))
+            .map(|task| self.with_tags(task))
+            .collect();
+        tasks.sort_by_key(|task| (task.due_date, task.id));
+        Ok(tasks)
+    }
+}
@@ -5,4 +5,73 @@ pub mod tag_repository;
 pub use tag_repository::*;
 
 pub mod task_repository;
-pub use task_repository::*;
\ No newline at end of file
+pub use task_repository::*;
+
+pub mod unit_of_work;
+pub use unit_of_work::*;
+
+pub mod traits;
+pub use traits::*;
+
+pub mod sqlite_store;
+pub use sqlite_store::*;
+
+pub mod in_memory_store;
+pub use in_memory_store::*;
+
+pub mod undo_repository;
+pub use undo_repository::*;
+
+pub mod bulk_task_repository;
+pub use bulk_task_repository::*;
+
+pub mod workflow_status_repository;
+pub use workflow_status_repository::*;
+
+pub mod time_log_repository;
+pub use time_log_repository::*;
+
+pub mod reminder_repository;
+pub use reminder_repository::*;
+
+pub mod subject_repository;
+pub use subject_repository::*;
+
+pub mod timetable_repository;
+pub use timetable_repository::*;
+
+pub mod assessment_repository;
+pub use assessment_repository::*;
+
+pub mod study_plan_repository;
+pub use study_plan_repository::*;
+
+pub mod note_repository;
+pub use note_repository::*;
+
+pub mod attachment_repository;
+pub use attachment_repository::*;
+
+pub mod reading_repository;
+pub use reading_repository::*;
+
+pub mod quiz_repository;
+pub use quiz_repository::*;
+
+pub mod settings_repository;
+pub use settings_repository::*;
+
+pub mod goal_repository;
+pub use goal_repository::*;
+
+pub mod statistics_repository;
+pub use statistics_repository::*;
+
+pub mod agenda_repository;
+pub use agenda_repository::*;
+
+pub mod reschedule_repository;
+pub use reschedule_repository::*;
+
+#[cfg(test)]
+mod conformance;
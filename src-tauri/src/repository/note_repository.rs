@@ -0,0 +1,356 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::NoteError;
+use crate::models::{Note, NoteLink, NoteLinks, NoteSearchHit};
+use crate::repository::{SubjectRepository, TaskRepository, UnitOfWork};
+
+const NOTE_COLUMNS: &str = "n.id, n.user_id, n.subject_id, n.title, n.body, n.created_at, n.updated_at";
+
+pub struct NoteRepository;
+
+impl NoteRepository {
+    pub fn create(uow: &UnitOfWork, note: &mut Note) -> Result<(), NoteError> {
+        NoteRepository::check(uow, note)?;
+
+        uow.execute(
+            "INSERT INTO notes (user_id, subject_id, title, body, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                note.user_id,
+                note.subject_id,
+                note.title.trim(),
+                note.body,
+                note.created_at.format("%Y-%m-%dT%H:%M").to_string(),
+                note.updated_at.format("%Y-%m-%dT%H:%M").to_string(),
+            ],
+        )?;
+        let note_id = uow.last_insert_rowid() as u32;
+        note.id = Some(note_id);
+        NoteRepository::sync_links(uow, note_id, note.user_id, &note.links())
+    }
+
+    /// Saves the note and rebuilds its outgoing links. Links from other
+    /// notes follow the title, so renaming a note leaves them dangling.
+    pub fn update(uow: &UnitOfWork, note: &Note) -> Result<(), NoteError> {
+        let id = note.id.ok_or_else(|| NoteError::DatabaseError("Note id missing".into()))?;
+        NoteRepository::check(uow, note)?;
+
+        let updated = uow.execute(
+            "UPDATE notes SET subject_id = ?2, title = ?3, body = ?4, updated_at = ?5 WHERE id = ?1",
+            params![
+                id,
+                note.subject_id,
+                note.title.trim(),
+                note.body,
+                note.updated_at.format("%Y-%m-%dT%H:%M").to_string(),
+            ],
+        )?;
+        if updated == 0 {
+            return Err(NoteError::NotFound(id));
+        }
+        NoteRepository::sync_links(uow, id, note.user_id, &note.links())
+    }
+
+    pub fn find(conn: &Connection, note_id: u32) -> Result<Note, NoteError> {
+        conn.query_row(
+            &format!("SELECT {} FROM notes n WHERE n.id = ?1", NOTE_COLUMNS),
+            params![note_id],
+            |row| Note::try_from(row),
+        )
+        .optional()?
+        .ok_or(NoteError::NotFound(note_id))
+    }
+
+    /// The user's notes, most recently edited first, optionally only those of
+    /// one subject.
+    pub fn list(conn: &Connection, user_id: u32, subject_id: Option<u32>) -> Result<Vec<Note>, NoteError> {
+        NoteRepository::query(
+            conn,
+            &format!(
+                "SELECT {} FROM notes n
+                 WHERE n.user_id = ?1 AND (?2 IS NULL OR n.subject_id = ?2)
+                 ORDER BY n.updated_at DESC, n.id DESC",
+                NOTE_COLUMNS
+            ),
+            params![user_id, subject_id],
+        )
+    }
+
+    pub fn delete(conn: &Connection, note_id: u32) -> Result<bool, NoteError> {
+        Ok(conn.execute("DELETE FROM notes WHERE id = ?1", params![note_id])? > 0)
+    }
+
+    /// Resolves the note's outgoing links.
+    pub fn links(conn: &Connection, note_id: u32) -> Result<NoteLinks, NoteError> {
+        let note = NoteRepository::find(conn, note_id)?;
+        let mut links = NoteLinks { notes: Vec::new(), tasks: Vec::new(), missing: Vec::new() };
+
+        for link in note.links() {
+            match link {
+                NoteLink::Note(title) => match NoteRepository::find_by_title(conn, note.user_id, &title)? {
+                    Some(target) => links.notes.push(target),
+                    None => links.missing.push(title),
+                },
+                NoteLink::Task(task_id) => {
+                    if let Ok(task) = TaskRepository::get_task_by_id(conn, task_id) {
+                        if task.user_id == note.user_id {
+                            links.tasks.push(task);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(links)
+    }
+
+    /// Other notes linking to this one by title.
+    pub fn backlinks(conn: &Connection, note_id: u32) -> Result<Vec<Note>, NoteError> {
+        let note = NoteRepository::find(conn, note_id)?;
+        NoteRepository::query(
+            conn,
+            &format!(
+                "SELECT DISTINCT {} FROM notes n
+                 JOIN note_links l ON l.note_id = n.id
+                 WHERE n.user_id = ?1 AND l.target_title = ?2 AND n.id != ?3
+                 ORDER BY n.title",
+                NOTE_COLUMNS
+            ),
+            params![note.user_id, note.title, note_id],
+        )
+    }
+
+    /// Notes attached to a task through `[[task:id]]` links.
+    pub fn task_notes(conn: &Connection, task_id: u32) -> Result<Vec<Note>, NoteError> {
+        NoteRepository::query(
+            conn,
+            &format!(
+                "SELECT DISTINCT {} FROM notes n
+                 JOIN note_links l ON l.note_id = n.id
+                 WHERE l.target_task_id = ?1
+                 ORDER BY n.title",
+                NOTE_COLUMNS
+            ),
+            params![task_id],
+        )
+    }
+
+    /// Notes no other note links to and that link to no existing note or
+    /// task.
+    pub fn orphans(conn: &Connection, user_id: u32) -> Result<Vec<Note>, NoteError> {
+        NoteRepository::query(
+            conn,
+            &format!(
+                "SELECT {} FROM notes n
+                 WHERE n.user_id = ?1
+                    AND NOT EXISTS (SELECT 1 FROM note_links l JOIN notes s ON s.id = l.note_id
+                        WHERE s.user_id = n.user_id AND s.id != n.id AND l.target_title = n.title)
+                    AND NOT EXISTS (SELECT 1 FROM note_links l
+                        WHERE l.note_id = n.id AND (l.target_task_id IS NOT NULL OR EXISTS (
+                            SELECT 1 FROM notes t
+                            WHERE t.user_id = n.user_id AND t.id != n.id AND t.title = l.target_title)))
+                 ORDER BY n.title",
+                NOTE_COLUMNS
+            ),
+            params![user_id],
+        )
+    }
+
+    /// Full-text search over titles and bodies, best matches first. Every
+    /// word must match, as a prefix and ignoring accents.
+    pub fn search(conn: &Connection, user_id: u32, query: &str, limit: u32) -> Result<Vec<NoteSearchHit>, NoteError> {
+        let Some(query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, snippet(notes_fts, -1, '<mark>', '</mark>', '…', 12) AS snippet
+             FROM notes_fts JOIN notes n ON n.id = notes_fts.rowid
+             WHERE notes_fts MATCH ?1 AND n.user_id = ?2
+             ORDER BY bm25(notes_fts, 10.0, 1.0), n.id
+             LIMIT ?3",
+            NOTE_COLUMNS
+        ))?;
+        let hits = stmt
+            .query_map(params![query, user_id, limit], |row| {
+                Ok(NoteSearchHit { note: Note::try_from(row)?, snippet: row.get("snippet")? })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(hits)
+    }
+
+    fn find_by_title(conn: &Connection, user_id: u32, title: &str) -> Result<Option<Note>, NoteError> {
+        Ok(conn
+            .query_row(
+                &format!("SELECT {} FROM notes n WHERE n.user_id = ?1 AND n.title = ?2", NOTE_COLUMNS),
+                params![user_id, title],
+                |row| Note::try_from(row),
+            )
+            .optional()?)
+    }
+
+    fn query(conn: &Connection, sql: &str, params: impl rusqlite::Params) -> Result<Vec<Note>, NoteError> {
+        let mut stmt = conn.prepare(sql)?;
+        let notes = stmt
+            .query_map(params, |row| Note::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    fn check(conn: &Connection, note: &Note) -> Result<(), NoteError> {
+        note.validate()?;
+        if let Some(subject_id) = note.subject_id {
+            SubjectRepository::find(conn, subject_id).map_err(|_| NoteError::SubjectNotFound(subject_id))?;
+        }
+        let taken: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM notes WHERE user_id = ?1 AND title = ?2 AND id != ?3)",
+            params![note.user_id, note.title.trim(), note.id.unwrap_or(0)],
+            |row| row.get(0),
+        )?;
+        if taken {
+            return Err(NoteError::DuplicateTitle(note.title.clone()));
+        }
+        Ok(())
+    }
+
+    /// Replaces the stored links of a note. Links to tasks that do not exist
+    /// or belong to someone else are kept in the body but not indexed.
+    fn sync_links(uow: &UnitOfWork, note_id: u32, user_id: u32, links: &[NoteLink]) -> Result<(), NoteError> {
+        uow.execute("DELETE FROM note_links WHERE note_id = ?1", params![note_id])?;
+        for link in links {
+            match link {
+                NoteLink::Note(title) => {
+                    uow.execute(
+                        "INSERT INTO note_links (note_id, target_title) VALUES (?1, ?2)",
+                        params![note_id, title],
+                    )?;
+                }
+                NoteLink::Task(task_id) => {
+                    uow.execute(
+                        "INSERT INTO note_links (note_id, target_task_id)
+                         SELECT ?1, id FROM tasks WHERE id = ?2 AND user_id = ?3",
+                        params![note_id, task_id, user_id],
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Turns free text into an FTS5 query of quoted prefix terms, so user input
+/// can never be a syntax error.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+    use crate::utils::initialize_database;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_note(conn: &Connection, title: &str, body: &str) -> Note {
+        let mut note = Note::new(1, title.to_string(), body.to_string()).unwrap();
+        UnitOfWork::run(conn, |uow| NoteRepository::create(uow, &mut note)).unwrap();
+        note
+    }
+
+    fn titles(notes: &[Note]) -> Vec<&str> {
+        notes.iter().map(|note| note.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_backlinks_resolve_by_title() {
+        let conn = setup_db();
+        let index = create_note(&conn, "Physics index", "Start with [[newton's laws]] and [[Optics]].");
+        let laws = create_note(&conn, "Newton's laws", "F = ma");
+
+        assert_eq!(titles(&NoteRepository::backlinks(&conn, laws.id.unwrap()).unwrap()), ["Physics index"]);
+        let links = NoteRepository::links(&conn, index.id.unwrap()).unwrap();
+        assert_eq!(titles(&links.notes), ["Newton's laws"]);
+        assert_eq!(links.missing, ["Optics"]);
+
+        let mut index = index;
+        index.body = "Only [[Optics]] now".to_string();
+        UnitOfWork::run(&conn, |uow| NoteRepository::update(uow, &index)).unwrap();
+        assert!(NoteRepository::backlinks(&conn, laws.id.unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_task_links_attach_notes() {
+        let conn = setup_db();
+        let mut task = Task::new("Lab report".to_string(), 1, None, None).unwrap();
+        TaskRepository::insert_task(&conn, &mut task).unwrap();
+        let task_id = task.id.unwrap();
+        create_note(&conn, "Lab notes", &format!("For [[task:{}]] and [[task:999]]", task_id));
+
+        assert_eq!(titles(&NoteRepository::task_notes(&conn, task_id).unwrap()), ["Lab notes"]);
+
+        UnitOfWork::run(&conn, |uow| TaskRepository::delete_task(uow, task_id)).unwrap();
+        assert!(NoteRepository::task_notes(&conn, task_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_orphans() {
+        let conn = setup_db();
+        create_note(&conn, "Hub", "[[Linked]] [[Nowhere]]");
+        create_note(&conn, "Linked", "");
+        create_note(&conn, "Lonely", "[[Nowhere]] [[Lonely]]");
+
+        assert_eq!(titles(&NoteRepository::orphans(&conn, 1).unwrap()), ["Lonely"]);
+    }
+
+    #[test]
+    fn test_duplicate_titles_are_rejected() {
+        let conn = setup_db();
+        create_note(&conn, "Optics", "");
+        let mut copy = Note::new(1, "OPTICS".to_string(), String::new()).unwrap();
+
+        let result = UnitOfWork::run(&conn, |uow| NoteRepository::create(uow, &mut copy));
+        assert!(matches!(result, Err(NoteError::DuplicateTitle(_))));
+    }
+
+    #[test]
+    fn test_search_ranks_and_ignores_accents() {
+        let conn = setup_db();
+        create_note(&conn, "Revolução Francesa", "Causas e consequências");
+        create_note(&conn, "História", "Notas sobre a revolução industrial");
+        create_note(&conn, "Química", "Tabela periódica");
+        conn.execute("INSERT INTO users (name, status) VALUES ('Bob', 'active')", []).unwrap();
+        let mut other = Note::new(2, "Revolucao".to_string(), String::new()).unwrap();
+        UnitOfWork::run(&conn, |uow| NoteRepository::create(uow, &mut other)).unwrap();
+
+        let hits = NoteRepository::search(&conn, 1, "revolu", 10).unwrap();
+        assert_eq!(hits.iter().map(|hit| hit.note.title.as_str()).collect::<Vec<_>>(), ["Revolução Francesa", "História"]);
+        assert!(hits[1].snippet.contains("<mark>revolução</mark>"));
+
+        assert!(NoteRepository::search(&conn, 1, "\"unbalanced (", 10).unwrap().is_empty());
+        assert!(NoteRepository::search(&conn, 1, "   ", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_follows_edits_and_deletes() {
+        let conn = setup_db();
+        let mut note = create_note(&conn, "Optics", "lenses");
+        note.body = "mirrors".to_string();
+        UnitOfWork::run(&conn, |uow| NoteRepository::update(uow, &note)).unwrap();
+
+        assert!(NoteRepository::search(&conn, 1, "lenses", 10).unwrap().is_empty());
+        assert_eq!(NoteRepository::search(&conn, 1, "mirrors", 10).unwrap().len(), 1);
+
+        NoteRepository::delete(&conn, note.id.unwrap()).unwrap();
+        assert!(NoteRepository::search(&conn, 1, "mirrors", 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,487 @@
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+
+use crate::errors::QuizError;
+use crate::models::{
+    rank_weakest, shuffle, AnswerReview, Question, QuizAttempt, QuizQuestion, QuizResult, QuizView, SubmittedAnswer,
+    TopicKind, TopicScore,
+};
+use crate::repository::UnitOfWork;
+
+const QUESTION_COLUMNS: &str =
+    "q.id, q.user_id, q.subject_id, q.note_id, q.task_id, q.kind, q.prompt, q.choices, q.answers, q.explanation";
+
+/// Attempts with their answer counts; `correct` only counts once graded.
+const ATTEMPT_SELECT: &str = "SELECT a.id, a.user_id, a.subject_id, a.tag_id, a.started_at, a.finished_at,
+        (SELECT COUNT(*) FROM quiz_answers qa WHERE qa.attempt_id = a.id) AS total,
+        (SELECT COUNT(*) FROM quiz_answers qa WHERE qa.attempt_id = a.id AND qa.correct) AS correct
+    FROM quiz_attempts a";
+
+pub struct QuizRepository;
+
+impl QuizRepository {
+    pub fn create_question(uow: &UnitOfWork, question: &mut Question) -> Result<(), QuizError> {
+        QuizRepository::check_question(uow, question)?;
+        uow.execute(
+            "INSERT INTO questions (user_id, subject_id, note_id, task_id, kind, prompt, choices, answers, explanation)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                question.user_id,
+                question.subject_id,
+                question.note_id,
+                question.task_id,
+                question.kind,
+                question.prompt.trim(),
+                to_json(&question.choices),
+                to_json(&question.answers),
+                question.explanation,
+            ],
+        )?;
+        let id = uow.last_insert_rowid() as u32;
+        question.id = Some(id);
+        QuizRepository::set_tags(uow, id, &question.tag_ids)
+    }
+
+    pub fn update_question(uow: &UnitOfWork, question: &Question) -> Result<(), QuizError> {
+        let id = question.id.ok_or_else(|| QuizError::DatabaseError("Question id missing".into()))?;
+        QuizRepository::check_question(uow, question)?;
+        let updated = uow.execute(
+            "UPDATE questions SET subject_id = ?2, note_id = ?3, task_id = ?4, kind = ?5, prompt = ?6,
+                choices = ?7, answers = ?8, explanation = ?9
+             WHERE id = ?1",
+            params![
+                id,
+                question.subject_id,
+                question.note_id,
+                question.task_id,
+                question.kind,
+                question.prompt.trim(),
+                to_json(&question.choices),
+                to_json(&question.answers),
+                question.explanation,
+            ],
+        )?;
+        if updated == 0 {
+            return Err(QuizError::NotFound(id));
+        }
+        QuizRepository::set_tags(uow, id, &question.tag_ids)
+    }
+
+    pub fn find_question(conn: &Connection, question_id: u32) -> Result<Question, QuizError> {
+        let question = conn
+            .query_row(
+                &format!("SELECT {} FROM questions q WHERE q.id = ?1", QUESTION_COLUMNS),
+                params![question_id],
+                |row| Question::try_from(row),
+            )
+            .optional()?
+            .ok_or(QuizError::NotFound(question_id))?;
+        QuizRepository::with_tags(conn, vec![question]).map(|mut questions| questions.remove(0))
+    }
+
+    /// The user's questions, optionally only those of a subject and/or tag.
+    pub fn list_questions(
+        conn: &Connection,
+        user_id: u32,
+        subject_id: Option<u32>,
+        tag_id: Option<u32>,
+    ) -> Result<Vec<Question>, QuizError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM questions q
+             WHERE q.user_id = ?1
+                AND (?2 IS NULL OR q.subject_id = ?2)
+                AND (?3 IS NULL OR EXISTS (SELECT 1 FROM question_tags qt WHERE qt.question_id = q.id AND qt.tag_id = ?3))
+             ORDER BY q.id",
+            QUESTION_COLUMNS
+        ))?;
+        let questions = stmt
+            .query_map(params![user_id, subject_id, tag_id], |row| Question::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        QuizRepository::with_tags(conn, questions)
+    }
+
+    pub fn delete_question(conn: &Connection, question_id: u32) -> Result<bool, QuizError> {
+        Ok(conn.execute("DELETE FROM questions WHERE id = ?1", params![question_id])? > 0)
+    }
+
+    /// Draws up to `size` random questions matching the filters. `seed`
+    /// fixes both the questions drawn and the order of their choices.
+    pub fn start_quiz(
+        uow: &UnitOfWork,
+        user_id: u32,
+        subject_id: Option<u32>,
+        tag_id: Option<u32>,
+        size: u32,
+        seed: u32,
+        now: NaiveDateTime,
+    ) -> Result<QuizView, QuizError> {
+        if size == 0 {
+            return Err(QuizError::InvalidQuiz("A quiz needs at least one question".into()));
+        }
+        let mut questions = QuizRepository::list_questions(uow, user_id, subject_id, tag_id)?;
+        if questions.is_empty() {
+            return Err(QuizError::InvalidQuiz("No questions match this quiz".into()));
+        }
+        shuffle(&mut questions, seed as u64);
+        questions.truncate(size as usize);
+
+        uow.execute(
+            "INSERT INTO quiz_attempts (user_id, subject_id, tag_id, seed, started_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![user_id, subject_id, tag_id, seed, now.format("%Y-%m-%dT%H:%M").to_string()],
+        )?;
+        let attempt_id = uow.last_insert_rowid() as u32;
+        for (position, question) in questions.iter().enumerate() {
+            uow.execute(
+                "INSERT INTO quiz_answers (attempt_id, question_id, position) VALUES (?1, ?2, ?3)",
+                params![attempt_id, question.id, position as u32],
+            )?;
+        }
+
+        QuizRepository::quiz(uow, attempt_id)
+    }
+
+    /// Rebuilds an attempt's questions in the order they were drawn.
+    pub fn quiz(conn: &Connection, attempt_id: u32) -> Result<QuizView, QuizError> {
+        let seed: u32 = conn
+            .query_row("SELECT seed FROM quiz_attempts WHERE id = ?1", params![attempt_id], |row| row.get(0))
+            .optional()?
+            .ok_or(QuizError::AttemptNotFound(attempt_id))?;
+        let questions = QuizRepository::attempt_questions(conn, attempt_id)?
+            .iter()
+            .map(|(question, _, _)| QuizQuestion::new(question, seed as u64))
+            .collect();
+        Ok(QuizView { attempt_id, questions })
+    }
+
+    /// Grades and closes the attempt. Questions without an answer count as
+    /// wrong.
+    pub fn submit_quiz(
+        uow: &UnitOfWork,
+        attempt_id: u32,
+        answers: &[SubmittedAnswer],
+        now: NaiveDateTime,
+    ) -> Result<QuizResult, QuizError> {
+        let attempt = QuizRepository::attempt(uow, attempt_id)?;
+        if attempt.finished_at.is_some() {
+            return Err(QuizError::AlreadySubmitted(attempt_id));
+        }
+
+        let given: HashMap<u32, &str> = answers
+            .iter()
+            .map(|answer| (answer.question_id, answer.answer.as_str()))
+            .collect();
+        for (question, _, _) in QuizRepository::attempt_questions(uow, attempt_id)? {
+            let question_id = question.id.unwrap_or_default();
+            let answer = given.get(&question_id).map(|answer| answer.trim()).filter(|answer| !answer.is_empty());
+            uow.execute(
+                "UPDATE quiz_answers SET given_answer = ?3, correct = ?4 WHERE attempt_id = ?1 AND question_id = ?2",
+                params![attempt_id, question_id, answer, answer.is_some_and(|answer| question.is_correct(answer))],
+            )?;
+        }
+        uow.execute(
+            "UPDATE quiz_attempts SET finished_at = ?2 WHERE id = ?1",
+            params![attempt_id, now.format("%Y-%m-%dT%H:%M").to_string()],
+        )?;
+
+        QuizRepository::result(uow, attempt_id)
+    }
+
+    pub fn result(conn: &Connection, attempt_id: u32) -> Result<QuizResult, QuizError> {
+        let attempt = QuizRepository::attempt(conn, attempt_id)?;
+        let answers = QuizRepository::attempt_questions(conn, attempt_id)?
+            .into_iter()
+            .map(|(question, given, correct)| AnswerReview { question, given, correct })
+            .collect();
+        Ok(QuizResult { attempt, answers })
+    }
+
+    /// Finished quizzes, newest first.
+    pub fn history(conn: &Connection, user_id: u32) -> Result<Vec<QuizAttempt>, QuizError> {
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE a.user_id = ?1 AND a.finished_at IS NOT NULL ORDER BY a.finished_at DESC, a.id DESC",
+            ATTEMPT_SELECT
+        ))?;
+        let attempts = stmt
+            .query_map(params![user_id], |row| QuizAttempt::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(attempts)
+    }
+
+    /// Subjects and tags ranked by accuracy in finished quizzes, skipping
+    /// those with fewer than `min_answers` graded answers.
+    pub fn weakest_topics(conn: &Connection, user_id: u32, min_answers: u32) -> Result<Vec<TopicScore>, QuizError> {
+        let mut topics = Vec::new();
+        let queries = [
+            (
+                TopicKind::Subject,
+                "SELECT s.id, s.name, COUNT(*), SUM(qa.correct) FROM quiz_answers qa
+                 JOIN quiz_attempts a ON a.id = qa.attempt_id
+                 JOIN questions q ON q.id = qa.question_id
+                 JOIN subjects s ON s.id = q.subject_id
+                 WHERE a.user_id = ?1 AND a.finished_at IS NOT NULL
+                 GROUP BY s.id",
+            ),
+            (
+                TopicKind::Tag,
+                "SELECT t.id, t.tag_name, COUNT(*), SUM(qa.correct) FROM quiz_answers qa
+                 JOIN quiz_attempts a ON a.id = qa.attempt_id
+                 JOIN question_tags qt ON qt.question_id = qa.question_id
+                 JOIN tags t ON t.id = qt.tag_id
+                 WHERE a.user_id = ?1 AND a.finished_at IS NOT NULL
+                 GROUP BY t.id",
+            ),
+        ];
+        for (kind, sql) in queries {
+            let mut stmt = conn.prepare(sql)?;
+            let rows = stmt
+                .query_map(params![user_id], |row| {
+                    Ok(TopicScore::new(kind, row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            topics.extend(rows);
+        }
+        Ok(rank_weakest(topics, min_answers))
+    }
+
+    fn attempt(conn: &Connection, attempt_id: u32) -> Result<QuizAttempt, QuizError> {
+        conn.query_row(&format!("{} WHERE a.id = ?1", ATTEMPT_SELECT), params![attempt_id], |row| {
+            QuizAttempt::try_from(row)
+        })
+        .optional()?
+        .ok_or(QuizError::AttemptNotFound(attempt_id))
+    }
+
+    /// The attempt's questions in quiz order, with the answer given and
+    /// whether it was right.
+    fn attempt_questions(conn: &Connection, attempt_id: u32) -> Result<Vec<(Question, Option<String>, bool)>, QuizError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, qa.given_answer, qa.correct FROM quiz_answers qa
+             JOIN questions q ON q.id = qa.question_id
+             WHERE qa.attempt_id = ?1
+             ORDER BY qa.position",
+            QUESTION_COLUMNS
+        ))?;
+        let rows = stmt
+            .query_map(params![attempt_id], |row| {
+                Ok((Question::try_from(row)?, row.get("given_answer")?, row.get("correct")?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (questions, graded): (Vec<_>, Vec<_>) =
+            rows.into_iter().map(|(question, given, correct)| (question, (given, correct))).unzip();
+        Ok(QuizRepository::with_tags(conn, questions)?
+            .into_iter()
+            .zip(graded)
+            .map(|(question, (given, correct))| (question, given, correct))
+            .collect())
+    }
+
+    fn with_tags(conn: &Connection, mut questions: Vec<Question>) -> Result<Vec<Question>, QuizError> {
+        let mut stmt = conn.prepare("SELECT tag_id FROM question_tags WHERE question_id = ?1 ORDER BY tag_id")?;
+        for question in &mut questions {
+            question.tag_ids = stmt
+                .query_map(params![question.id], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+        Ok(questions)
+    }
+
+    fn set_tags(uow: &UnitOfWork, question_id: u32, tag_ids: &[u32]) -> Result<(), QuizError> {
+        uow.execute("DELETE FROM question_tags WHERE question_id = ?1", params![question_id])?;
+        for tag_id in tag_ids {
+            let exists: bool =
+                uow.query_row("SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)", params![tag_id], |row| row.get(0))?;
+            if !exists {
+                return Err(QuizError::InvalidQuestion(format!("Tag {} not found", tag_id)));
+            }
+            uow.execute(
+                "INSERT OR IGNORE INTO question_tags (question_id, tag_id) VALUES (?1, ?2)",
+                params![question_id, tag_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Validates the question and that what it links to belongs to its user.
+    fn check_question(conn: &Connection, question: &Question) -> Result<(), QuizError> {
+        question.validate()?;
+        let links = [
+            ("subjects", "Subject", question.subject_id),
+            ("notes", "Note", question.note_id),
+            ("tasks", "Task", question.task_id),
+        ];
+        for (table, label, id) in links {
+            let Some(id) = id else { continue };
+            let exists: bool = conn.query_row(
+                &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1 AND user_id = ?2)", table),
+                params![id, question.user_id],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                return Err(QuizError::InvalidQuestion(format!("{} {} not found", label, id)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_json(values: &[String]) -> String {
+    serde_json::to_string(values).unwrap_or_else(|_| "[]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{QuestionKind, Subject};
+    use crate::repository::SubjectRepository;
+    use crate::utils::initialize_database;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_subject(conn: &Connection, name: &str) -> u32 {
+        let mut subject = Subject::new(1, name.to_string(), "blue".to_string()).unwrap();
+        SubjectRepository::create(conn, &mut subject).unwrap();
+        subject.id.unwrap()
+    }
+
+    fn create_tag(conn: &Connection, name: &str) -> u32 {
+        conn.execute("INSERT INTO tags (tag_name, tag_color) VALUES (?1, 'red')", params![name])
+            .unwrap();
+        conn.last_insert_rowid() as u32
+    }
+
+    fn create_question(conn: &Connection, prompt: &str, answer: &str, subject_id: Option<u32>, tag_ids: Vec<u32>) -> u32 {
+        let mut question = Question {
+            id: None,
+            user_id: 1,
+            subject_id,
+            note_id: None,
+            task_id: None,
+            kind: QuestionKind::ShortAnswer,
+            prompt: prompt.to_string(),
+            choices: Vec::new(),
+            answers: vec![answer.to_string()],
+            explanation: None,
+            tag_ids,
+        };
+        UnitOfWork::run(conn, |uow| QuizRepository::create_question(uow, &mut question)).unwrap();
+        question.id.unwrap()
+    }
+
+    fn answer(question_id: u32, answer: &str) -> SubmittedAnswer {
+        SubmittedAnswer { question_id, answer: answer.to_string() }
+    }
+
+    #[test]
+    fn test_question_round_trip_with_tags() {
+        let conn = setup_db();
+        let tag = create_tag(&conn, "optics");
+        let mut question = Question {
+            id: None,
+            user_id: 1,
+            subject_id: None,
+            note_id: None,
+            task_id: None,
+            kind: QuestionKind::MultipleChoice,
+            prompt: "Speed of light?".to_string(),
+            choices: vec!["3e8 m/s".to_string(), "340 m/s".to_string()],
+            answers: vec!["3e8 m/s".to_string()],
+            explanation: Some("In vacuum".to_string()),
+            tag_ids: vec![tag],
+        };
+        UnitOfWork::run(&conn, |uow| QuizRepository::create_question(uow, &mut question)).unwrap();
+
+        assert_eq!(QuizRepository::find_question(&conn, question.id.unwrap()).unwrap(), question);
+        assert_eq!(QuizRepository::list_questions(&conn, 1, None, Some(tag)).unwrap().len(), 1);
+        assert!(QuizRepository::list_questions(&conn, 1, Some(99), None).unwrap().is_empty());
+
+        question.tag_ids = vec![999];
+        let result = UnitOfWork::run(&conn, |uow| QuizRepository::update_question(uow, &question));
+        assert!(matches!(result, Err(QuizError::InvalidQuestion(_))));
+    }
+
+    #[test]
+    fn test_quiz_draws_filtered_questions_deterministically() {
+        let conn = setup_db();
+        let physics = create_subject(&conn, "Physics");
+        for i in 0..6 {
+            create_question(&conn, &format!("Physics {}", i), "x", Some(physics), Vec::new());
+        }
+        create_question(&conn, "History", "x", None, Vec::new());
+
+        let quiz = UnitOfWork::run(&conn, |uow| {
+            QuizRepository::start_quiz(uow, 1, Some(physics), None, 4, 7, at("2025-03-10 10:00"))
+        })
+        .unwrap();
+
+        assert_eq!(quiz.questions.len(), 4);
+        assert!(quiz.questions.iter().all(|q| q.prompt.starts_with("Physics")));
+        assert_eq!(QuizRepository::quiz(&conn, quiz.attempt_id).unwrap().questions, quiz.questions);
+
+        let empty = UnitOfWork::run(&conn, |uow| QuizRepository::start_quiz(uow, 1, Some(99), None, 4, 7, at("2025-03-10 10:00")));
+        assert!(matches!(empty, Err(QuizError::InvalidQuiz(_))));
+    }
+
+    #[test]
+    fn test_submit_grades_and_records_history() {
+        let conn = setup_db();
+        let capital = create_question(&conn, "Capital of France?", "Paris", None, Vec::new());
+        let sum = create_question(&conn, "2 + 2?", "4", None, Vec::new());
+        create_question(&conn, "Largest planet?", "Jupiter", None, Vec::new());
+        let quiz = UnitOfWork::run(&conn, |uow| QuizRepository::start_quiz(uow, 1, None, None, 10, 1, at("2025-03-10 10:00")))
+            .unwrap();
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            QuizRepository::submit_quiz(uow, quiz.attempt_id, &[answer(capital, " paris "), answer(sum, "5")], at("2025-03-10 10:05"))
+        })
+        .unwrap();
+
+        assert_eq!((result.attempt.correct, result.attempt.total), (1, 3));
+        assert_eq!(result.attempt.score, Some(33.3));
+        let unanswered = result.answers.iter().find(|a| a.question.prompt == "Largest planet?").unwrap();
+        assert_eq!((unanswered.given.as_deref(), unanswered.correct), (None, false));
+        assert_eq!(QuizRepository::history(&conn, 1).unwrap(), vec![result.attempt]);
+
+        let again = UnitOfWork::run(&conn, |uow| QuizRepository::submit_quiz(uow, quiz.attempt_id, &[], at("2025-03-10 10:06")));
+        assert!(matches!(again, Err(QuizError::AlreadySubmitted(_))));
+    }
+
+    #[test]
+    fn test_weakest_topics_across_subjects_and_tags() {
+        let conn = setup_db();
+        let physics = create_subject(&conn, "Physics");
+        let history = create_subject(&conn, "History");
+        let dates = create_tag(&conn, "dates");
+        let p1 = create_question(&conn, "p1", "a", Some(physics), Vec::new());
+        let p2 = create_question(&conn, "p2", "a", Some(physics), Vec::new());
+        let h1 = create_question(&conn, "h1", "a", Some(history), vec![dates]);
+        let h2 = create_question(&conn, "h2", "a", Some(history), vec![dates]);
+        let quiz = UnitOfWork::run(&conn, |uow| QuizRepository::start_quiz(uow, 1, None, None, 10, 3, at("2025-03-10 10:00")))
+            .unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            QuizRepository::submit_quiz(
+                uow,
+                quiz.attempt_id,
+                &[answer(p1, "a"), answer(p2, "a"), answer(h1, "a"), answer(h2, "b")],
+                at("2025-03-10 10:05"),
+            )
+        })
+        .unwrap();
+
+        let topics = QuizRepository::weakest_topics(&conn, 1, 2).unwrap();
+
+        let summary: Vec<(&str, f64)> = topics.iter().map(|t| (t.name.as_str(), t.accuracy)).collect();
+        assert_eq!(summary, [("History", 0.5), ("dates", 0.5), ("Physics", 1.0)]);
+    }
+}
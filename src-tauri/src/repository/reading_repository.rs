@@ -0,0 +1,308 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::ReadingError;
+use crate::models::{reading_schedule, Book, ReadingProgress, ReadingSession, Task};
+use crate::repository::{SubjectRepository, TaskRepository, UnitOfWork, WorkflowStatusRepository};
+
+const BOOK_COLUMNS: &str = "id, user_id, subject_id, title, author, total_units, unit, target_date";
+const SESSION_COLUMNS: &str = "id, book_id, read_on, units, minutes";
+
+fn database_error(e: impl ToString) -> ReadingError {
+    ReadingError::DatabaseError(e.to_string())
+}
+
+pub struct ReadingRepository;
+
+impl ReadingRepository {
+    pub fn create_book(conn: &Connection, book: &mut Book) -> Result<(), ReadingError> {
+        ReadingRepository::check_book(conn, book)?;
+        conn.execute(
+            "INSERT INTO books (user_id, subject_id, title, author, total_units, unit, target_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                book.user_id,
+                book.subject_id,
+                book.title,
+                book.author,
+                book.total_units,
+                book.unit,
+                book.target_date.map(|date| date.format("%Y-%m-%d").to_string()),
+            ],
+        )?;
+        book.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn update_book(conn: &Connection, book: &Book) -> Result<(), ReadingError> {
+        let id = book.id.ok_or_else(|| ReadingError::DatabaseError("Book id missing".into()))?;
+        ReadingRepository::check_book(conn, book)?;
+        let updated = conn.execute(
+            "UPDATE books SET subject_id = ?2, title = ?3, author = ?4, total_units = ?5, unit = ?6, target_date = ?7
+             WHERE id = ?1",
+            params![
+                id,
+                book.subject_id,
+                book.title,
+                book.author,
+                book.total_units,
+                book.unit,
+                book.target_date.map(|date| date.format("%Y-%m-%d").to_string()),
+            ],
+        )?;
+        if updated == 0 {
+            return Err(ReadingError::NotFound(id));
+        }
+        Ok(())
+    }
+
+    pub fn find_book(conn: &Connection, book_id: u32) -> Result<Book, ReadingError> {
+        conn.query_row(
+            &format!("SELECT {} FROM books WHERE id = ?1", BOOK_COLUMNS),
+            params![book_id],
+            |row| Book::try_from(row),
+        )
+        .optional()?
+        .ok_or(ReadingError::NotFound(book_id))
+    }
+
+    pub fn list_books(conn: &Connection, user_id: u32) -> Result<Vec<Book>, ReadingError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM books WHERE user_id = ?1 ORDER BY title COLLATE NOCASE, id",
+            BOOK_COLUMNS
+        ))?;
+        let books = stmt
+            .query_map(params![user_id], |row| Book::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(books)
+    }
+
+    /// Deletes the book and its pending reading tasks; completed ones stay.
+    pub fn delete_book(uow: &UnitOfWork, book_id: u32) -> Result<bool, ReadingError> {
+        ReadingRepository::delete_open_tasks(uow, book_id)?;
+        Ok(uow.execute("DELETE FROM books WHERE id = ?1", params![book_id])? > 0)
+    }
+
+    pub fn log_session(conn: &Connection, session: &mut ReadingSession) -> Result<(), ReadingError> {
+        ReadingRepository::find_book(conn, session.book_id)?;
+        conn.execute(
+            "INSERT INTO reading_sessions (book_id, read_on, units, minutes) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                session.book_id,
+                session.read_on.format("%Y-%m-%d").to_string(),
+                session.units,
+                session.minutes,
+            ],
+        )?;
+        session.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn sessions(conn: &Connection, book_id: u32) -> Result<Vec<ReadingSession>, ReadingError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reading_sessions WHERE book_id = ?1 ORDER BY read_on, id",
+            SESSION_COLUMNS
+        ))?;
+        let sessions = stmt
+            .query_map(params![book_id], |row| ReadingSession::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sessions)
+    }
+
+    pub fn delete_session(conn: &Connection, session_id: u32) -> Result<bool, ReadingError> {
+        Ok(conn.execute("DELETE FROM reading_sessions WHERE id = ?1", params![session_id])? > 0)
+    }
+
+    pub fn progress(conn: &Connection, book_id: u32, today: NaiveDate) -> Result<ReadingProgress, ReadingError> {
+        let book = ReadingRepository::find_book(conn, book_id)?;
+        let sessions = ReadingRepository::sessions(conn, book_id)?;
+        Ok(ReadingProgress::new(book, &sessions, today))
+    }
+
+    pub fn progress_for_user(conn: &Connection, user_id: u32, today: NaiveDate) -> Result<Vec<ReadingProgress>, ReadingError> {
+        ReadingRepository::list_books(conn, user_id)?
+            .into_iter()
+            .map(|book| {
+                let sessions = ReadingRepository::sessions(conn, book.id.unwrap_or_default())?;
+                Ok(ReadingProgress::new(book, &sessions, today))
+            })
+            .collect()
+    }
+
+    /// Replaces the book's pending reading tasks with one task per day from
+    /// `today` to the target date, covering what is left to read. Estimates
+    /// use the minutes per unit of past sessions, when they were timed.
+    pub fn schedule_tasks(
+        uow: &UnitOfWork,
+        book_id: u32,
+        today: NaiveDate,
+        to_utc: impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<Vec<Task>, ReadingError> {
+        let progress = ReadingRepository::progress(uow, book_id, today)?;
+        let book = &progress.book;
+        let target = book
+            .target_date
+            .ok_or_else(|| ReadingError::InvalidDate(format!("'{}' has no target date", book.title)))?;
+        if target < today {
+            return Err(ReadingError::InvalidDate(format!("The target date for '{}' has passed", book.title)));
+        }
+
+        ReadingRepository::delete_open_tasks(uow, book_id)?;
+
+        let minutes_per_unit = ReadingRepository::minutes_per_unit(uow, book_id)?;
+        let status = WorkflowStatusRepository::initial_status(uow, book.user_id).map_err(database_error)?;
+        let mut tasks = Vec::new();
+        for assignment in reading_schedule(progress.units_read, book.total_units, today, target) {
+            let title = format!("Read {}: {}", book.title, book.unit.describe_range(assignment.from, assignment.to));
+            let mut task = Task::new(title, book.user_id, None, None).map_err(database_error)?;
+            task.status = status.clone();
+            task.subject_id = book.subject_id;
+            task.set_due_day(assignment.date, &to_utc);
+            task.estimated_minutes = minutes_per_unit
+                .map(|minutes| ((assignment.to - assignment.from + 1) as f64 * minutes).ceil() as u32);
+            TaskRepository::insert_task(uow, &mut task).map_err(database_error)?;
+            uow.execute(
+                "INSERT INTO reading_tasks (task_id, book_id) VALUES (?1, ?2)",
+                params![task.id, book_id],
+            )?;
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    fn delete_open_tasks(uow: &UnitOfWork, book_id: u32) -> Result<(), ReadingError> {
+        let mut stmt = uow.prepare(
+            "SELECT r.task_id FROM reading_tasks r
+             JOIN tasks t ON t.id = r.task_id
+             LEFT JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE r.book_id = ?1 AND COALESCE(ws.category, '') != 'completed'",
+        )?;
+        let task_ids = stmt
+            .query_map(params![book_id], |row| row.get::<_, u32>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        for task_id in task_ids {
+            TaskRepository::delete_task(uow, task_id).map_err(database_error)?;
+        }
+        Ok(())
+    }
+
+    fn minutes_per_unit(conn: &Connection, book_id: u32) -> Result<Option<f64>, ReadingError> {
+        let (minutes, units): (Option<u32>, Option<u32>) = conn.query_row(
+            "SELECT SUM(minutes), SUM(units) FROM reading_sessions WHERE book_id = ?1 AND minutes IS NOT NULL",
+            params![book_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(minutes.zip(units).map(|(minutes, units)| minutes as f64 / units as f64))
+    }
+
+    fn check_book(conn: &Connection, book: &Book) -> Result<(), ReadingError> {
+        book.validate()?;
+        if let Some(subject_id) = book.subject_id {
+            SubjectRepository::find(conn, subject_id).map_err(|_| ReadingError::SubjectNotFound(subject_id))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, ReadingUnit};
+    use crate::utils::initialize_database;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_book(conn: &Connection, target: Option<&str>) -> u32 {
+        let mut book = Book::new(1, "Dom Casmurro".to_string(), 100, ReadingUnit::Pages).unwrap();
+        book.target_date = target.map(date);
+        ReadingRepository::create_book(conn, &mut book).unwrap();
+        book.id.unwrap()
+    }
+
+    fn log(conn: &Connection, book_id: u32, read_on: &str, units: u32, minutes: Option<u32>) {
+        let mut session = ReadingSession::new(book_id, date(read_on), units, minutes).unwrap();
+        ReadingRepository::log_session(conn, &mut session).unwrap();
+    }
+
+    fn schedule(conn: &Connection, book_id: u32, today: &str) -> Result<Vec<Task>, ReadingError> {
+        UnitOfWork::run(conn, |uow| ReadingRepository::schedule_tasks(uow, book_id, date(today), |dt| dt))
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sessions_drive_progress() {
+        let conn = setup_db();
+        let book_id = create_book(&conn, Some("2025-03-20"));
+        log(&conn, book_id, "2025-03-09", 20, None);
+        log(&conn, book_id, "2025-03-10", 20, None);
+
+        let progress = ReadingRepository::progress(&conn, book_id, date("2025-03-10")).unwrap();
+
+        assert_eq!(progress.units_read, 40);
+        assert_eq!(progress.pace_per_day, Some(20.0));
+        assert_eq!(progress.estimated_finish, Some(date("2025-03-13")));
+        assert_eq!(ReadingRepository::progress_for_user(&conn, 1, date("2025-03-10")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_schedule_tasks_covers_remaining_pages() {
+        let conn = setup_db();
+        let book_id = create_book(&conn, Some("2025-03-12"));
+        log(&conn, book_id, "2025-03-09", 40, Some(80));
+
+        let tasks = schedule(&conn, book_id, "2025-03-10").unwrap();
+
+        assert_eq!(
+            titles(&tasks),
+            ["Read Dom Casmurro: pages 41–60", "Read Dom Casmurro: pages 61–80", "Read Dom Casmurro: pages 81–100"]
+        );
+        assert_eq!(tasks[0].estimated_minutes, Some(40));
+        assert_eq!(tasks[2].due_date, Some(date("2025-03-12").and_hms_opt(23, 59, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_rescheduling_keeps_completed_tasks() {
+        let conn = setup_db();
+        let book_id = create_book(&conn, Some("2025-03-11"));
+        let first = schedule(&conn, book_id, "2025-03-10").unwrap();
+        let done = first[0].id.unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, done, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+        log(&conn, book_id, "2025-03-10", 50, None);
+
+        let second = schedule(&conn, book_id, "2025-03-11").unwrap();
+
+        assert_eq!(titles(&second), ["Read Dom Casmurro: pages 51–100"]);
+        assert!(TaskRepository::get_task_by_id(&conn, done).is_ok());
+        assert!(TaskRepository::get_task_by_id(&conn, first[1].id.unwrap()).is_err());
+
+        UnitOfWork::run(&conn, |uow| ReadingRepository::delete_book(uow, book_id)).unwrap();
+        assert!(TaskRepository::get_task_by_id(&conn, second[0].id.unwrap()).is_err());
+        assert!(TaskRepository::get_task_by_id(&conn, done).is_ok());
+    }
+
+    #[test]
+    fn test_schedule_requires_a_future_target() {
+        let conn = setup_db();
+        let without_target = create_book(&conn, None);
+        let overdue = create_book(&conn, Some("2025-03-01"));
+
+        assert!(matches!(schedule(&conn, without_target, "2025-03-10"), Err(ReadingError::InvalidDate(_))));
+        assert!(matches!(schedule(&conn, overdue, "2025-03-10"), Err(ReadingError::InvalidDate(_))));
+    }
+}
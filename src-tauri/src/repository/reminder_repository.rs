@@ -0,0 +1,268 @@
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection};
+
+use crate::errors::ReminderError;
+use crate::models::{DueReminder, Reminder, ReminderRule};
+use crate::utils::{truncate_to_minute, SqliteDateTime};
+
+const REMINDER_COLUMNS: &str =
+    "r.id, r.task_id, r.remind_at, r.minutes_before_due, r.snoozed_until, r.fired_at, r.dismissed_at";
+
+fn format(dt: NaiveDateTime) -> String {
+    truncate_to_minute(dt).format("%Y-%m-%dT%H:%M").to_string()
+}
+
+/// Reminder rules per task and their firing state. A reminder fires once;
+/// snoozing re-arms it for a later time, dismissing retires it. Moving a
+/// task's due date re-arms its relative reminders (see the
+/// `tasks_rearm_reminders` trigger).
+pub struct ReminderRepository;
+
+impl ReminderRepository {
+    pub fn add(conn: &Connection, task_id: u32, rule: ReminderRule) -> Result<Reminder, ReminderError> {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1)",
+            params![task_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(ReminderError::TaskNotFound(task_id));
+        }
+
+        let (remind_at, minutes_before_due) = match rule {
+            ReminderRule::At(at) => (Some(format(at)), None),
+            ReminderRule::BeforeDue(minutes) => (None, Some(minutes)),
+        };
+        conn.execute(
+            "INSERT INTO reminders (task_id, remind_at, minutes_before_due) VALUES (?1, ?2, ?3)",
+            params![task_id, remind_at, minutes_before_due],
+        )?;
+        ReminderRepository::find(conn, conn.last_insert_rowid() as u32)
+    }
+
+    pub fn find(conn: &Connection, reminder_id: u32) -> Result<Reminder, ReminderError> {
+        conn.query_row(
+            &format!("SELECT {} FROM reminders r WHERE r.id = ?1", REMINDER_COLUMNS),
+            params![reminder_id],
+            |row| Reminder::try_from(row),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => ReminderError::NotFound(reminder_id),
+            e => e.into(),
+        })
+    }
+
+    pub fn list_for_task(conn: &Connection, task_id: u32) -> Result<Vec<Reminder>, ReminderError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reminders r WHERE r.task_id = ?1 ORDER BY r.id",
+            REMINDER_COLUMNS
+        ))?;
+        let reminders = stmt
+            .query_map(params![task_id], |row| Reminder::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(reminders)
+    }
+
+    pub fn delete(conn: &Connection, reminder_id: u32) -> Result<bool, ReminderError> {
+        Ok(conn.execute("DELETE FROM reminders WHERE id = ?1", params![reminder_id])? > 0)
+    }
+
+    /// Re-arms a reminder to fire again at `until`, even if it already fired
+    /// or was dismissed.
+    pub fn snooze(conn: &Connection, reminder_id: u32, until: NaiveDateTime) -> Result<Reminder, ReminderError> {
+        let updated = conn.execute(
+            "UPDATE reminders SET snoozed_until = ?1, fired_at = NULL, dismissed_at = NULL WHERE id = ?2",
+            params![format(until), reminder_id],
+        )?;
+        if updated == 0 {
+            return Err(ReminderError::NotFound(reminder_id));
+        }
+        ReminderRepository::find(conn, reminder_id)
+    }
+
+    pub fn dismiss(conn: &Connection, reminder_id: u32, now: NaiveDateTime) -> Result<Reminder, ReminderError> {
+        let updated = conn.execute(
+            "UPDATE reminders SET dismissed_at = ?1 WHERE id = ?2",
+            params![format(now), reminder_id],
+        )?;
+        if updated == 0 {
+            return Err(ReminderError::NotFound(reminder_id));
+        }
+        ReminderRepository::find(conn, reminder_id)
+    }
+
+    /// Pending reminders due at or before `now` on tasks that are not
+    /// completed, oldest first. Those more than `grace` late are `missed`.
+    pub fn due(
+        conn: &Connection,
+        now: NaiveDateTime,
+        grace: chrono::Duration,
+    ) -> Result<Vec<DueReminder>, ReminderError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, t.user_id, t.title, t.due_date
+             FROM reminders r
+             JOIN tasks t ON t.id = r.task_id
+             LEFT JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE r.fired_at IS NULL AND r.dismissed_at IS NULL
+                AND COALESCE(ws.category, '') != 'completed'",
+            REMINDER_COLUMNS
+        ))?;
+        let pending = stmt
+            .query_map([], |row| {
+                let user_id: u32 = row.get("user_id")?;
+                let title: String = row.get("title")?;
+                let due_date = row.get::<_, Option<SqliteDateTime>>("due_date")?.map(NaiveDateTime::from);
+                Ok((Reminder::try_from(row)?, user_id, title, due_date))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let now = truncate_to_minute(now);
+        let mut due: Vec<DueReminder> = pending
+            .into_iter()
+            .filter_map(|(reminder, user_id, title, due_date)| {
+                let trigger_at = reminder.trigger_at(due_date)?;
+                (trigger_at <= now).then(|| DueReminder {
+                    reminder_id: reminder.id.unwrap_or_default(),
+                    task_id: reminder.task_id,
+                    user_id,
+                    title,
+                    due_date,
+                    trigger_at,
+                    missed: trigger_at < now - grace,
+                })
+            })
+            .collect();
+        due.sort_by_key(|reminder| (reminder.trigger_at, reminder.reminder_id));
+        Ok(due)
+    }
+
+    pub fn mark_fired(conn: &Connection, reminder_ids: &[u32], now: NaiveDateTime) -> Result<(), ReminderError> {
+        let now = format(now);
+        for id in reminder_ids {
+            conn.execute(
+                "UPDATE reminders SET fired_at = ?1 WHERE id = ?2",
+                params![now, id],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, Task};
+    use crate::repository::{TaskRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+    use chrono::Duration;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_task(conn: &Connection, title: &str, due: &str) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.due_date = Some(at(due));
+        TaskRepository::insert_task(conn, &mut task).unwrap();
+        task.id.unwrap()
+    }
+
+    fn due_ids(conn: &Connection, now: &str) -> Vec<(u32, bool)> {
+        ReminderRepository::due(conn, at(now), Duration::minutes(5))
+            .unwrap()
+            .iter()
+            .map(|reminder| (reminder.reminder_id, reminder.missed))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_validates_task() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", "2025-03-10 18:00");
+
+        let reminder = ReminderRepository::add(&conn, task_id, ReminderRule::BeforeDue(60)).unwrap();
+        assert_eq!(reminder.rule, ReminderRule::BeforeDue(60));
+        assert!(reminder.is_pending());
+        assert!(matches!(
+            ReminderRepository::add(&conn, 99, ReminderRule::BeforeDue(60)),
+            Err(ReminderError::TaskNotFound(99))
+        ));
+        assert!(matches!(ReminderRepository::find(&conn, 42), Err(ReminderError::NotFound(42))));
+    }
+
+    #[test]
+    fn test_due_reminders_fire_once_and_flag_missed() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", "2025-03-10 18:00");
+        let before = ReminderRepository::add(&conn, task_id, ReminderRule::BeforeDue(60)).unwrap();
+        let early = ReminderRepository::add(&conn, task_id, ReminderRule::At(at("2025-03-09 09:00"))).unwrap();
+        let (before, early) = (before.id.unwrap(), early.id.unwrap());
+
+        assert_eq!(due_ids(&conn, "2025-03-09 08:59"), []);
+        assert_eq!(due_ids(&conn, "2025-03-10 17:02"), [(early, true), (before, false)]);
+
+        ReminderRepository::mark_fired(&conn, &[early, before], at("2025-03-10 17:02")).unwrap();
+        assert_eq!(due_ids(&conn, "2025-03-10 17:03"), []);
+    }
+
+    #[test]
+    fn test_snooze_and_dismiss() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", "2025-03-10 18:00");
+        let id = ReminderRepository::add(&conn, task_id, ReminderRule::BeforeDue(0)).unwrap().id.unwrap();
+        ReminderRepository::mark_fired(&conn, &[id], at("2025-03-10 18:00")).unwrap();
+
+        let snoozed = ReminderRepository::snooze(&conn, id, at("2025-03-10 18:10")).unwrap();
+        assert!(snoozed.is_pending());
+        assert_eq!(due_ids(&conn, "2025-03-10 18:09"), []);
+        assert_eq!(due_ids(&conn, "2025-03-10 18:10"), [(id, false)]);
+
+        let dismissed = ReminderRepository::dismiss(&conn, id, at("2025-03-10 18:11")).unwrap();
+        assert!(!dismissed.is_pending());
+        assert_eq!(due_ids(&conn, "2025-03-10 18:12"), []);
+        assert!(matches!(ReminderRepository::dismiss(&conn, 42, at("2025-03-10 18:11")), Err(ReminderError::NotFound(42))));
+    }
+
+    #[test]
+    fn test_moving_the_due_date_rearms_relative_reminders() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", "2025-03-10 18:00");
+        let before = ReminderRepository::add(&conn, task_id, ReminderRule::BeforeDue(60)).unwrap().id.unwrap();
+        let fixed = ReminderRepository::add(&conn, task_id, ReminderRule::At(at("2025-03-10 09:00"))).unwrap().id.unwrap();
+        ReminderRepository::mark_fired(&conn, &[before, fixed], at("2025-03-10 17:00")).unwrap();
+        let update_due = |due: &str| {
+            UnitOfWork::run(&conn, |uow| {
+                TaskRepository::update_task(uow, task_id, None, None, None, None, Some(at(due)), None)
+            })
+            .unwrap()
+        };
+
+        update_due("2025-03-10 18:00");
+        assert_eq!(due_ids(&conn, "2025-03-12 17:00"), []);
+
+        update_due("2025-03-12 18:00");
+        assert_eq!(due_ids(&conn, "2025-03-12 16:59"), []);
+        assert_eq!(due_ids(&conn, "2025-03-12 17:00"), [(before, false)]);
+    }
+
+    #[test]
+    fn test_completed_tasks_do_not_remind() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", "2025-03-10 18:00");
+        ReminderRepository::add(&conn, task_id, ReminderRule::BeforeDue(30)).unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, task_id, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        assert_eq!(due_ids(&conn, "2025-03-10 18:00"), []);
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+use crate::errors::RescheduleError;
+use crate::models::{move_due_to, BulkSummary, RescheduleChange, RescheduleOptions, ReschedulePreview, Task};
+use crate::repository::{SettingsRepository, StudyPlanRepository, TaskRepository, UndoRepository, UnitOfWork};
+
+pub struct RescheduleRepository;
+
+impl RescheduleRepository {
+    /// Proposes new due dates for the user's overdue tasks, starting today.
+    /// Classes and the estimates of tasks already due on a day take up its
+    /// capacity.
+    pub fn preview(
+        conn: &Connection,
+        user_id: u32,
+        options: &RescheduleOptions,
+        now: NaiveDateTime,
+    ) -> Result<ReschedulePreview, RescheduleError> {
+        options.validate()?;
+        let calendar = SettingsRepository::get(conn, user_id)?.calendar();
+        let today = calendar.local_date(now);
+        let last = today + Duration::days(options.horizon_days as i64 - 1);
+        let overdue = TaskRepository::get_overdue_tasks(conn, user_id, now)?;
+
+        let mut busy = StudyPlanRepository::busy_minutes(conn, user_id, today, last, &|local| calendar.to_utc(local))
+            .map_err(|e| RescheduleError::DatabaseError(e.to_string()))?;
+        // Tasks that fell overdue earlier today are moved, not kept.
+        for task in &overdue {
+            if let (Some(due), Some(minutes)) = (task.due_date, task.estimated_minutes) {
+                if let Some(taken) = busy.get_mut(&calendar.local_date(due)) {
+                    *taken = taken.saturating_sub(minutes);
+                }
+            }
+        }
+
+        let days = options.days(today, |date| busy.get(&date).copied().unwrap_or(0));
+        Ok(ReschedulePreview::new(&overdue, &days, options.default_task_minutes, |task, date| {
+            move_due_to(task.due_date.unwrap_or(now), date, now, &calendar)
+        }))
+    }
+
+    /// Saves the accepted `changes`. Tasks that are no longer open are
+    /// reported as missing; a task whose due date moved since the preview
+    /// fails the whole batch. The previous dates go to the undo log.
+    pub fn apply(uow: &UnitOfWork, user_id: u32, changes: &[RescheduleChange]) -> Result<BulkSummary, RescheduleError> {
+        let mut open: HashMap<u32, Task> = TaskRepository::get_open_tasks(uow, user_id)?
+            .into_iter()
+            .filter_map(|task| task.id.map(|id| (id, task)))
+            .collect();
+        let mut summary = BulkSummary::default();
+        let mut snapshot = Vec::new();
+
+        for change in changes {
+            let Some(mut task) = open.remove(&change.task_id) else {
+                summary.missing_ids.push(change.task_id);
+                continue;
+            };
+            if task.due_date != change.old_due_date {
+                return Err(RescheduleError::StaleChange(change.task_id));
+            }
+            snapshot.push(task.clone());
+            task.due_date = Some(change.new_due_date);
+            TaskRepository::set_dates(uow, &task)?;
+        }
+
+        summary.matched = snapshot.len() as u32;
+        summary.affected = snapshot.len() as u32;
+        if !snapshot.is_empty() {
+            summary.undo_id = Some(UndoRepository::record(uow, user_id, "reschedule_overdue", &snapshot)?);
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_priority::TaskPriority, UserSettings};
+    use crate::utils::initialize_database;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        SettingsRepository::save(&conn, &UserSettings { utc_offset_minutes: Some(0), ..UserSettings::new(1) }).unwrap();
+        conn
+    }
+
+    fn overdue_task(conn: &Connection, title: &str, priority: TaskPriority, due: &str, minutes: u32) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, Some(priority)).unwrap();
+        task.due_date = Some(at(due));
+        task.estimated_minutes = Some(minutes);
+        TaskRepository::insert_task(conn, &mut task).unwrap();
+        task.id.unwrap()
+    }
+
+    #[test]
+    fn test_preview_then_apply_and_undo() {
+        let conn = setup_db();
+        let now = at("2025-03-10 12:00");
+        let essay = overdue_task(&conn, "Essay", TaskPriority::Low, "2025-03-03 18:00", 90);
+        let lab = overdue_task(&conn, "Lab report", TaskPriority::High, "2025-03-07 09:00", 90);
+        let options = RescheduleOptions { daily_capacity_minutes: 120, ..Default::default() };
+
+        let preview = RescheduleRepository::preview(&conn, 1, &options, now).unwrap();
+        let moved: Vec<(u32, NaiveDateTime)> =
+            preview.changes.iter().map(|change| (change.task_id, change.new_due_date)).collect();
+        assert_eq!(moved, [(lab, at("2025-03-10 23:59")), (essay, at("2025-03-11 18:00"))]);
+        // Nothing is saved by the preview.
+        assert_eq!(TaskRepository::get_task_by_id(&conn, essay).unwrap().due_date, Some(at("2025-03-03 18:00")));
+
+        let summary = UnitOfWork::run(&conn, |uow| RescheduleRepository::apply(uow, 1, &preview.changes)).unwrap();
+        assert_eq!(summary.affected, 2);
+        assert!(TaskRepository::get_overdue_tasks(&conn, 1, now).unwrap().is_empty());
+
+        UnitOfWork::run(&conn, |uow| UndoRepository::undo_last(uow, 1)).unwrap();
+        assert_eq!(TaskRepository::get_overdue_tasks(&conn, 1, now).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_stale_change_rolls_back_the_batch() {
+        let conn = setup_db();
+        let now = at("2025-03-10 12:00");
+        let first = overdue_task(&conn, "First", TaskPriority::High, "2025-03-03 18:00", 30);
+        let second = overdue_task(&conn, "Second", TaskPriority::Low, "2025-03-04 18:00", 30);
+        let preview = RescheduleRepository::preview(&conn, 1, &RescheduleOptions::default(), now).unwrap();
+
+        let mut task = TaskRepository::get_task_by_id(&conn, second).unwrap();
+        task.due_date = Some(at("2025-03-20 10:00"));
+        TaskRepository::set_dates(&conn, &task).unwrap();
+
+        let result = UnitOfWork::run(&conn, |uow| RescheduleRepository::apply(uow, 1, &preview.changes));
+        assert!(matches!(result, Err(RescheduleError::StaleChange(id)) if id == second));
+        assert_eq!(TaskRepository::get_task_by_id(&conn, first).unwrap().due_date, Some(at("2025-03-03 18:00")));
+    }
+}
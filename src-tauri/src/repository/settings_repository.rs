@@ -0,0 +1,56 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::SettingsError;
+use crate::models::UserSettings;
+
+pub struct SettingsRepository;
+
+impl SettingsRepository {
+    /// The user's settings, or the defaults when none were saved.
+    pub fn get(conn: &Connection, user_id: u32) -> Result<UserSettings, SettingsError> {
+        Ok(conn
+            .query_row(
+                "SELECT user_id, week_start, utc_offset_minutes FROM user_settings WHERE user_id = ?1",
+                params![user_id],
+                |row| UserSettings::try_from(row),
+            )
+            .optional()?
+            .unwrap_or_else(|| UserSettings::new(user_id)))
+    }
+
+    pub fn save(conn: &Connection, settings: &UserSettings) -> Result<(), SettingsError> {
+        settings.validate()?;
+        conn.execute(
+            "INSERT INTO user_settings (user_id, week_start, utc_offset_minutes) VALUES (?1, ?2, ?3)
+             ON CONFLICT (user_id) DO UPDATE SET week_start = ?2, utc_offset_minutes = ?3",
+            params![
+                settings.user_id,
+                settings.week_start.num_days_from_monday(),
+                settings.utc_offset_minutes
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::initialize_database;
+    use chrono::Weekday;
+
+    #[test]
+    fn test_defaults_and_round_trip() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+
+        assert_eq!(SettingsRepository::get(&conn, 1).unwrap(), UserSettings::new(1));
+
+        let settings = UserSettings { user_id: 1, week_start: Weekday::Sun, utc_offset_minutes: Some(-180) };
+        SettingsRepository::save(&conn, &settings).unwrap();
+        SettingsRepository::save(&conn, &settings).unwrap();
+        assert_eq!(SettingsRepository::get(&conn, 1).unwrap(), settings);
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+
+use crate::errors::{TagError, TaskError, UserError};
+use crate::models::{
+    task_priority::TaskPriority,
+    task_status::TaskStatus,
+    Tag, Task, User,
+};
+use crate::repository::{
+    TagRepository, TagStore, TaskRepository, TaskStore, UnitOfWork, UserRepository, UserStore,
+};
+
+/// SQLite backend for the repository traits. Multi-table writes run in a
+/// [`UnitOfWork`], joining the caller's transaction when one is open.
+pub struct SqliteStore<'c> {
+    conn: &'c Connection,
+}
+
+impl<'c> SqliteStore<'c> {
+    pub fn new(conn: &'c Connection) -> Self {
+        Self { conn }
+    }
+}
+
+impl UserStore for SqliteStore<'_> {
+    fn create_user(&mut self, user: &mut User) -> Result<(), UserError> {
+        UserRepository::create(self.conn, user)
+    }
+
+    fn count_active_users(&self) -> Result<u32, UserError> {
+        UserRepository::count_active(self.conn)
+    }
+
+    fn find_active_user_id(&self) -> Result<Option<u32>, UserError> {
+        UserRepository::find_active_id(self.conn)
+    }
+}
+
+impl TagStore for SqliteStore<'_> {
+    fn create_tag(&mut self, tag: &mut Tag) -> Result<(), TagError> {
+        TagRepository::create(self.conn, tag)
+    }
+
+    fn rename_tag(&mut self, id: u32, tag_name: String) -> Result<(), TagError> {
+        TagRepository::update_tag(self.conn, id, tag_name)
+    }
+
+    fn find_tag(&self, id: u32) -> Result<Option<Tag>, TagError> {
+        TagRepository::find_by_id(self.conn, id)
+    }
+
+    fn list_tags(&self) -> Result<Vec<Tag>, TagError> {
+        TagRepository::list_all(self.conn)
+    }
+
+    fn delete_tag(&mut self, id: u32) -> Result<bool, TagError> {
+        UnitOfWork::run(self.conn, |uow| TagRepository::delete_by_id(uow, id))
+    }
+
+    fn get_task_tags(&self, task_id: u32) -> Result<Vec<Tag>, TagError> {
+        Ok(TagRepository::get_task_tags(self.conn, task_id)?.unwrap_or_default())
+    }
+
+    fn set_task_tags(&mut self, task_id: u32, tags: &[Tag]) -> Result<(), TagError> {
+        UnitOfWork::run(self.conn, |uow| TagRepository::update_task_tags(uow, task_id, tags))
+    }
+}
+
+impl TaskStore for SqliteStore<'_> {
+    fn insert_task(&mut self, task: &mut Task, tags: &[Tag]) -> Result<(), TaskError> {
+        UnitOfWork::run(self.conn, |uow| TaskRepository::insert_task_with_tags(uow, task, tags))
+    }
+
+    fn get_task(&self, task_id: u32) -> Result<Task, TaskError> {
+        TaskRepository::get_task_by_id(self.conn, task_id)
+    }
+
+    fn list_tasks(&self) -> Result<Vec<Task>, TaskError> {
+        TaskRepository::get_all_tasks(self.conn)
+    }
+
+    fn update_task(
+        &mut self,
+        task_id: u32,
+        title: Option<String>,
+        description: Option<String>,
+        status: Option<TaskStatus>,
+        priority: Option<TaskPriority>,
+        due_date: Option<NaiveDateTime>,
+        tags: Option<Vec<Tag>>,
+    ) -> Result<(), TaskError> {
+        UnitOfWork::run(self.conn, |uow| {
+            TaskRepository::update_task(
+                uow,
+                task_id,
+                title,
+                description,
+                status,
+                priority,
+                due_date,
+                tags,
+            )
+        })
+    }
+
+    fn delete_task(&mut self, task_id: u32) -> Result<(), TaskError> {
+        UnitOfWork::run(self.conn, |uow| TaskRepository::delete_task(uow, task_id))
+    }
+
+    fn list_column(&self, user_id: u32, status: &TaskStatus) -> Result<Vec<Task>, TaskError> {
+        TaskRepository::get_column(self.conn, user_id, status)
+    }
+
+    fn move_task(&mut self, task_id: u32, status: TaskStatus, index: Option<usize>) -> Result<Task, TaskError> {
+        UnitOfWork::run(self.conn, |uow| TaskRepository::move_task(uow, task_id, status, index))
+    }
+
+    fn wip_limit(&self, user_id: u32, status: &TaskStatus) -> Result<Option<u32>, TaskError> {
+        TaskRepository::get_wip_limit(self.conn, user_id, status)
+    }
+
+    fn set_wip_limit(&mut self, user_id: u32, status: &TaskStatus, limit: Option<u32>) -> Result<(), TaskError> {
+        TaskRepository::set_wip_limit(self.conn, user_id, status, limit)
+    }
+
+    fn get_tasks_due_between(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError> {
+        TaskRepository::get_tasks_due_between(self.conn, start, end)
+    }
+}
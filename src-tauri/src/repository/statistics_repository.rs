@@ -0,0 +1,152 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+
+use crate::errors::StatisticsError;
+use crate::models::{CompletedTask, LoggedMinutes, Statistics, StatsGrouping};
+use crate::repository::SettingsRepository;
+use crate::utils::SqliteDateTime;
+
+/// Longest range, in days, that `statistics` accepts.
+pub const MAX_STATISTICS_DAYS: i64 = 731;
+
+pub struct StatisticsRepository;
+
+impl StatisticsRepository {
+    /// Statistics for the user's local days `from..=to`.
+    pub fn statistics(
+        conn: &Connection,
+        user_id: u32,
+        from: NaiveDate,
+        to: NaiveDate,
+        grouping: StatsGrouping,
+    ) -> Result<Statistics, StatisticsError> {
+        if from > to {
+            return Err(StatisticsError::InvalidRange(format!("{} is after {}", from, to)));
+        }
+        if (to - from).num_days() >= MAX_STATISTICS_DAYS {
+            return Err(StatisticsError::InvalidRange(format!(
+                "Ranges are limited to {} days",
+                MAX_STATISTICS_DAYS
+            )));
+        }
+
+        let calendar = SettingsRepository::get(conn, user_id)?.calendar();
+        let start = calendar.day_start(from).format("%Y-%m-%dT%H:%M").to_string();
+        let end = calendar.day_start(to + Duration::days(1)).format("%Y-%m-%dT%H:%M").to_string();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.priority, t.created_at, t.completed_at, t.due_date FROM tasks t
+             WHERE t.user_id = ?1 AND t.completed_at >= ?2 AND t.completed_at < ?3",
+        )?;
+        let completed = stmt
+            .query_map(params![user_id, start, end], |row| {
+                Ok(CompletedTask {
+                    priority: row.get(0)?,
+                    created_at: row.get::<_, SqliteDateTime>(1)?.into(),
+                    completed_at: row.get::<_, SqliteDateTime>(2)?.into(),
+                    due_date: row.get::<_, Option<SqliteDateTime>>(3)?.map(Into::into),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT l.id, l.started_at, l.minutes, g.tag_name FROM time_logs l
+             JOIN tasks t ON t.id = l.task_id
+             LEFT JOIN task_tags tt ON tt.task_id = t.id
+             LEFT JOIN tags g ON g.id = tt.tag_id
+             WHERE t.user_id = ?1 AND l.minutes IS NOT NULL
+                AND l.started_at >= ?2 AND l.started_at < ?3
+             ORDER BY l.id, g.tag_name",
+        )?;
+        let mut rows = stmt.query(params![user_id, start, end])?;
+        let mut logged: Vec<(u32, LoggedMinutes)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id: u32 = row.get(0)?;
+            if logged.last().map(|(last, _)| *last) != Some(id) {
+                logged.push((
+                    id,
+                    LoggedMinutes {
+                        started_at: row.get::<_, SqliteDateTime>(1)?.into(),
+                        minutes: row.get(2)?,
+                        tags: Vec::new(),
+                    },
+                ));
+            }
+            if let (Some((_, log)), Some(tag)) = (logged.last_mut(), row.get::<_, Option<String>>(3)?) {
+                log.tags.push(tag);
+            }
+        }
+        let logged: Vec<LoggedMinutes> = logged.into_iter().map(|(_, log)| log).collect();
+
+        Ok(Statistics::new(from, to, grouping, &completed, &logged, &calendar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, Tag, Task, UserSettings};
+    use crate::repository::{TagRepository, TaskRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        SettingsRepository::save(&conn, &UserSettings { utc_offset_minutes: Some(0), ..UserSettings::new(1) }).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_statistics_from_tasks_and_logs() {
+        let conn = setup_db();
+        let mut task = Task::new("Essay".to_string(), 1, None, None).unwrap();
+        TaskRepository::insert_task(&conn, &mut task).unwrap();
+        let task_id = task.id.unwrap();
+        let tags = [
+            Tag::new("history".into(), "#123456".into()).unwrap(),
+            Tag::new("writing".into(), "#654321".into()).unwrap(),
+        ];
+        UnitOfWork::run(&conn, |uow| TagRepository::update_task_tags(uow, task_id, &tags)).unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, task_id, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+        conn.execute(
+            "UPDATE tasks SET created_at = '2025-03-01T10:00', completed_at = '2025-03-04T10:00', due_date = '2025-03-03T23:59'",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO time_logs (task_id, started_at, ended_at, minutes) VALUES
+                (?1, '2025-03-02T09:00', '2025-03-02T09:40', 40),
+                (?1, '2025-03-03T09:00', NULL, NULL)",
+            params![task_id],
+        )
+        .unwrap();
+
+        let stats = StatisticsRepository::statistics(&conn, 1, date("2025-03-01"), date("2025-03-07"), StatsGrouping::Day).unwrap();
+
+        assert_eq!(stats.completed, vec![0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(stats.late[3], 1);
+        assert_eq!(stats.minutes_logged.iter().sum::<u32>(), 40);
+        assert_eq!(stats.time_per_tag.len(), 2);
+        assert_eq!(stats.weekly.iter().map(|w| w.minutes_logged).sum::<u32>(), 40);
+        assert_eq!(stats.cycle_time[0].value, 72.0);
+    }
+
+    #[test]
+    fn test_rejects_bad_ranges() {
+        let conn = setup_db();
+
+        let reversed = StatisticsRepository::statistics(&conn, 1, date("2025-03-02"), date("2025-03-01"), StatsGrouping::Day);
+        let too_long = StatisticsRepository::statistics(&conn, 1, date("2020-01-01"), date("2025-01-01"), StatsGrouping::Week);
+        assert!(matches!(reversed, Err(StatisticsError::InvalidRange(_))));
+        assert!(matches!(too_long, Err(StatisticsError::InvalidRange(_))));
+    }
+}
@@ -0,0 +1,381 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+
+use crate::errors::StudyPlanError;
+use crate::models::{
+    available_days, format_rest_days, plan_study, StudyPlan, StudyPlanView, Task, TopicEstimate, TopicProgress,
+};
+use crate::repository::{SubjectRepository, TaskRepository, TimetableRepository, UnitOfWork, WorkflowStatusRepository};
+
+const PLAN_COLUMNS: &str =
+    "id, user_id, subject_id, title, exam_date, daily_capacity_minutes, block_minutes, rest_days";
+
+fn database_error(e: impl ToString) -> StudyPlanError {
+    StudyPlanError::DatabaseError(e.to_string())
+}
+
+/// Study plans and the block tasks scheduled for them.
+pub struct StudyPlanRepository;
+
+impl StudyPlanRepository {
+    /// Saves the plan and schedules its topics from `today` until the exam.
+    /// Block times are local; `to_utc` converts them to stored due dates.
+    pub fn create(
+        uow: &UnitOfWork,
+        plan: &mut StudyPlan,
+        topics: &[TopicEstimate],
+        today: NaiveDate,
+        to_utc: impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<StudyPlanView, StudyPlanError> {
+        plan.validate()?;
+        if topics.is_empty() {
+            return Err(StudyPlanError::InvalidTopic("A plan needs at least one topic".into()));
+        }
+        if let Some(topic) = topics.iter().find(|topic| topic.name.trim().is_empty() || topic.minutes == 0) {
+            return Err(StudyPlanError::InvalidTopic(format!(
+                "'{}' needs a name and a positive estimate",
+                topic.name
+            )));
+        }
+        if let Some(subject_id) = plan.subject_id {
+            SubjectRepository::find(uow, subject_id).map_err(|_| StudyPlanError::SubjectNotFound(subject_id))?;
+        }
+
+        uow.execute(
+            "INSERT INTO study_plans (user_id, subject_id, title, exam_date, daily_capacity_minutes, block_minutes, rest_days)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                plan.user_id,
+                plan.subject_id,
+                plan.title,
+                plan.exam_date.format("%Y-%m-%d").to_string(),
+                plan.daily_capacity_minutes,
+                plan.block_minutes,
+                format_rest_days(&plan.rest_days),
+            ],
+        )?;
+        let plan_id = uow.last_insert_rowid() as u32;
+        plan.id = Some(plan_id);
+
+        for (position, topic) in topics.iter().enumerate() {
+            uow.execute(
+                "INSERT INTO study_plan_topics (plan_id, name, minutes, position) VALUES (?1, ?2, ?3, ?4)",
+                params![plan_id, topic.name.trim(), topic.minutes, position as u32],
+            )?;
+        }
+
+        let topics = StudyPlanRepository::topics(uow, plan_id)?;
+        StudyPlanRepository::schedule(uow, plan, &topics, today, &to_utc)?;
+        StudyPlanRepository::view(uow, plan_id)
+    }
+
+    /// Redistributes what is left: completed blocks count as done, every
+    /// other block (missed or upcoming) is dropped and the remaining minutes
+    /// are scheduled again from `today`.
+    pub fn replan(
+        uow: &UnitOfWork,
+        plan_id: u32,
+        today: NaiveDate,
+        to_utc: impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<StudyPlanView, StudyPlanError> {
+        let plan = StudyPlanRepository::find(uow, plan_id)?;
+        let done = StudyPlanRepository::minutes_by_topic(uow, plan_id, true)?;
+
+        for task_id in StudyPlanRepository::block_tasks(uow, plan_id, Some(false))? {
+            TaskRepository::delete_task(uow, task_id).map_err(database_error)?;
+        }
+
+        let remaining: Vec<TopicEstimate> = StudyPlanRepository::topics(uow, plan_id)?
+            .into_iter()
+            .map(|topic| TopicEstimate {
+                minutes: topic.minutes.saturating_sub(done.get(&topic.id.unwrap_or_default()).copied().unwrap_or(0)),
+                ..topic
+            })
+            .collect();
+        StudyPlanRepository::schedule(uow, &plan, &remaining, today, &to_utc)?;
+        StudyPlanRepository::view(uow, plan_id)
+    }
+
+    pub fn find(conn: &Connection, plan_id: u32) -> Result<StudyPlan, StudyPlanError> {
+        conn.query_row(
+            &format!("SELECT {} FROM study_plans WHERE id = ?1", PLAN_COLUMNS),
+            params![plan_id],
+            |row| StudyPlan::try_from(row),
+        )
+        .optional()?
+        .ok_or(StudyPlanError::NotFound(plan_id))
+    }
+
+    pub fn list(conn: &Connection, user_id: u32) -> Result<Vec<StudyPlan>, StudyPlanError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM study_plans WHERE user_id = ?1 ORDER BY exam_date, id",
+            PLAN_COLUMNS
+        ))?;
+        let plans = stmt
+            .query_map(params![user_id], |row| StudyPlan::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(plans)
+    }
+
+    pub fn view(conn: &Connection, plan_id: u32) -> Result<StudyPlanView, StudyPlanError> {
+        let plan = StudyPlanRepository::find(conn, plan_id)?;
+        let done = StudyPlanRepository::minutes_by_topic(conn, plan_id, true)?;
+        let scheduled = StudyPlanRepository::minutes_by_topic(conn, plan_id, false)?;
+        let topics = StudyPlanRepository::topics(conn, plan_id)?
+            .into_iter()
+            .map(|topic| {
+                let id = topic.id.unwrap_or_default();
+                TopicProgress {
+                    done_minutes: done.get(&id).copied().unwrap_or(0),
+                    scheduled_minutes: scheduled.get(&id).copied().unwrap_or(0),
+                    topic,
+                }
+            })
+            .collect();
+
+        let mut tasks = StudyPlanRepository::block_tasks(conn, plan_id, None)?
+            .into_iter()
+            .map(|task_id| TaskRepository::get_task_by_id(conn, task_id).map_err(database_error))
+            .collect::<Result<Vec<Task>, _>>()?;
+        tasks.sort_by_key(|task| (task.due_date.is_none(), task.due_date, task.id));
+
+        let unscheduled_minutes = conn.query_row(
+            "SELECT unscheduled_minutes FROM study_plans WHERE id = ?1",
+            params![plan_id],
+            |row| row.get(0),
+        )?;
+        Ok(StudyPlanView { plan, topics, tasks, unscheduled_minutes })
+    }
+
+    /// Deletes the plan and its pending blocks; completed blocks stay as
+    /// ordinary tasks.
+    pub fn delete(uow: &UnitOfWork, plan_id: u32) -> Result<bool, StudyPlanError> {
+        for task_id in StudyPlanRepository::block_tasks(uow, plan_id, Some(false))? {
+            TaskRepository::delete_task(uow, task_id).map_err(database_error)?;
+        }
+        Ok(uow.execute("DELETE FROM study_plans WHERE id = ?1", params![plan_id])? > 0)
+    }
+
+    fn schedule(
+        uow: &UnitOfWork,
+        plan: &StudyPlan,
+        topics: &[TopicEstimate],
+        today: NaiveDate,
+        to_utc: &impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<(), StudyPlanError> {
+        let plan_id = plan.id.unwrap_or_default();
+        let busy = StudyPlanRepository::busy_minutes(uow, plan.user_id, today, plan.exam_date, to_utc)?;
+        let days = available_days(plan, today, |date| busy.get(&date).copied().unwrap_or(0));
+        let planned = plan_study(topics, &days, plan.block_minutes);
+
+        let status = WorkflowStatusRepository::initial_status(uow, plan.user_id).map_err(database_error)?;
+        for block in &planned.blocks {
+            let topic = &topics[block.topic];
+            let mut task = Task::new(format!("{}: {}", plan.title, topic.name), plan.user_id, None, None)
+                .map_err(|e| StudyPlanError::InvalidTopic(e.to_string()))?;
+            task.status = status.clone();
+            task.subject_id = plan.subject_id;
+            task.estimated_minutes = Some(block.minutes);
+            task.set_due_day(block.date, to_utc);
+            TaskRepository::insert_task(uow, &mut task).map_err(database_error)?;
+
+            uow.execute(
+                "INSERT INTO study_plan_blocks (task_id, plan_id, topic_id, minutes) VALUES (?1, ?2, ?3, ?4)",
+                params![task.id, plan_id, topic.id, block.minutes],
+            )?;
+        }
+
+        let unscheduled: u32 = planned.unscheduled.iter().map(|(_, minutes)| minutes).sum();
+        uow.execute(
+            "UPDATE study_plans SET unscheduled_minutes = ?1 WHERE id = ?2",
+            params![unscheduled, plan_id],
+        )?;
+        Ok(())
+    }
+
+    /// Minutes per local day already taken by classes and by the estimates
+    /// of the user's open tasks due that day.
+    pub(crate) fn busy_minutes(
+        conn: &Connection,
+        user_id: u32,
+        from: NaiveDate,
+        to: NaiveDate,
+        to_utc: &impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<HashMap<NaiveDate, u32>, StudyPlanError> {
+        let mut busy = HashMap::new();
+        for class in TimetableRepository::classes_between(conn, user_id, from, to).map_err(database_error)? {
+            *busy.entry(class.date).or_insert(0) += (class.end_at - class.start_at).num_minutes() as u32;
+        }
+
+        let open_tasks = TaskRepository::get_open_tasks(conn, user_id).map_err(database_error)?;
+        let mut date = from;
+        while date <= to {
+            let start = to_utc(date.and_hms_opt(0, 0, 0).unwrap());
+            let end = to_utc((date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap());
+            let estimated: u32 = open_tasks
+                .iter()
+                .filter(|task| task.due_date.is_some_and(|due| start <= due && due < end))
+                .filter_map(|task| task.estimated_minutes)
+                .sum();
+            *busy.entry(date).or_insert(0) += estimated;
+            date += Duration::days(1);
+        }
+        Ok(busy)
+    }
+
+    fn topics(conn: &Connection, plan_id: u32) -> Result<Vec<TopicEstimate>, StudyPlanError> {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, minutes FROM study_plan_topics WHERE plan_id = ?1 ORDER BY position",
+        )?;
+        let topics = stmt
+            .query_map(params![plan_id], |row| {
+                Ok(TopicEstimate { id: Some(row.get(0)?), name: row.get(1)?, minutes: row.get(2)? })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(topics)
+    }
+
+    /// Block task ids, optionally only completed (`Some(true)`) or pending ones.
+    fn block_tasks(conn: &Connection, plan_id: u32, completed: Option<bool>) -> Result<Vec<u32>, StudyPlanError> {
+        let mut stmt = conn.prepare(
+            "SELECT b.task_id, ws.category = 'completed' FROM study_plan_blocks b
+             JOIN tasks t ON t.id = b.task_id
+             LEFT JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE b.plan_id = ?1
+             ORDER BY b.task_id",
+        )?;
+        let blocks = stmt
+            .query_map(params![plan_id], |row| Ok((row.get::<_, u32>(0)?, row.get::<_, Option<bool>>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(blocks
+            .into_iter()
+            .filter(|(_, is_completed)| completed.is_none_or(|wanted| is_completed.unwrap_or(false) == wanted))
+            .map(|(task_id, _)| task_id)
+            .collect())
+    }
+
+    fn minutes_by_topic(conn: &Connection, plan_id: u32, completed: bool) -> Result<HashMap<u32, u32>, StudyPlanError> {
+        let mut stmt = conn.prepare(
+            "SELECT b.topic_id, SUM(b.minutes) FROM study_plan_blocks b
+             JOIN tasks t ON t.id = b.task_id
+             LEFT JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE b.plan_id = ?1 AND (COALESCE(ws.category, '') = 'completed') = ?2
+             GROUP BY b.topic_id",
+        )?;
+        let minutes = stmt
+            .query_map(params![plan_id, completed], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task_status::TaskStatus;
+    use crate::utils::initialize_database;
+    use chrono::Weekday;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn topic(name: &str, minutes: u32) -> TopicEstimate {
+        TopicEstimate { id: None, name: name.to_string(), minutes }
+    }
+
+    /// Exam on Friday 2025-03-14, planned from Monday, weekends off, 60
+    /// minutes a day in blocks of up to 60.
+    fn create_plan(conn: &Connection, topics: &[TopicEstimate]) -> StudyPlanView {
+        let mut plan = StudyPlan::new(1, "Midterm".to_string(), date("2025-03-14")).unwrap();
+        plan.daily_capacity_minutes = 60;
+        plan.block_minutes = 60;
+        plan.rest_days = vec![Weekday::Sat, Weekday::Sun];
+        UnitOfWork::run(conn, |uow| StudyPlanRepository::create(uow, &mut plan, topics, date("2025-03-10"), |dt| dt))
+            .unwrap()
+    }
+
+    fn due_days(view: &StudyPlanView) -> Vec<String> {
+        view.tasks.iter().map(|task| task.due_date.unwrap().format("%d").to_string()).collect()
+    }
+
+    #[test]
+    fn test_create_schedules_block_tasks() {
+        let conn = setup_db();
+        let view = create_plan(&conn, &[topic("Kinematics", 120), topic("Dynamics", 120)]);
+
+        assert_eq!(due_days(&view), ["10", "11", "12", "13"]);
+        assert_eq!(view.tasks[0].title, "Midterm: Kinematics");
+        assert_eq!(view.tasks[2].title, "Midterm: Dynamics");
+        assert_eq!(view.tasks[0].estimated_minutes, Some(60));
+        assert_eq!(view.topics[0].scheduled_minutes, 120);
+        assert_eq!(view.unscheduled_minutes, 0);
+    }
+
+    #[test]
+    fn test_existing_work_reduces_capacity() {
+        let conn = setup_db();
+        let mut busy = Task::new("Essay".to_string(), 1, None, None).unwrap();
+        busy.due_date = Some(date("2025-03-10").and_hms_opt(18, 0, 0).unwrap());
+        busy.estimated_minutes = Some(60);
+        TaskRepository::insert_task(&conn, &mut busy).unwrap();
+
+        let view = create_plan(&conn, &[topic("Kinematics", 240)]);
+
+        assert_eq!(due_days(&view), ["11", "12", "13"]);
+        assert_eq!(view.unscheduled_minutes, 60);
+    }
+
+    #[test]
+    fn test_replan_redistributes_missed_blocks() {
+        let conn = setup_db();
+        let view = create_plan(
+            &conn,
+            &[topic("Kinematics", 60), topic("Dynamics", 60), topic("Optics", 60), topic("Waves", 60)],
+        );
+        let plan_id = view.plan.id.unwrap();
+        // Monday's block is done, Tuesday's was missed.
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, view.tasks[0].id.unwrap(), None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        let replanned =
+            UnitOfWork::run(&conn, |uow| StudyPlanRepository::replan(uow, plan_id, date("2025-03-12"), |dt| dt)).unwrap();
+
+        assert_eq!(due_days(&replanned), ["10", "12", "13"]);
+        assert_eq!(replanned.tasks[1].title, "Midterm: Dynamics");
+        assert_eq!(replanned.topics[0].done_minutes, 60);
+        assert_eq!(replanned.topics[1].scheduled_minutes, 60);
+        assert_eq!(replanned.unscheduled_minutes, 60);
+
+        let again =
+            UnitOfWork::run(&conn, |uow| StudyPlanRepository::replan(uow, plan_id, date("2025-03-12"), |dt| dt)).unwrap();
+        assert_eq!(due_days(&again), due_days(&replanned));
+    }
+
+    #[test]
+    fn test_delete_keeps_completed_blocks() {
+        let conn = setup_db();
+        let view = create_plan(&conn, &[topic("Kinematics", 120)]);
+        let done = view.tasks[0].id.unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, done, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        assert!(UnitOfWork::run(&conn, |uow| StudyPlanRepository::delete(uow, view.plan.id.unwrap())).unwrap());
+
+        assert!(TaskRepository::get_task_by_id(&conn, done).is_ok());
+        assert!(TaskRepository::get_task_by_id(&conn, view.tasks[1].id.unwrap()).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::SubjectError;
+use crate::models::{task_kind::TaskKind, week_start, Subject, SubjectDashboard};
+use crate::repository::TaskRepository;
+
+const SUBJECT_COLUMNS: &str =
+    "id, user_id, name, teacher, color, term, credit_hours, weekly_goal_minutes, grading_scheme, passing_grade";
+
+pub struct SubjectRepository;
+
+impl SubjectRepository {
+    pub fn create(conn: &Connection, subject: &mut Subject) -> Result<(), SubjectError> {
+        subject.validate()?;
+        SubjectRepository::ensure_unique_name(conn, subject)?;
+
+        conn.execute(
+            "INSERT INTO subjects (user_id, name, teacher, color, term, credit_hours, weekly_goal_minutes,
+                grading_scheme, passing_grade)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                subject.user_id,
+                subject.name,
+                subject.teacher,
+                subject.color,
+                subject.term,
+                subject.credit_hours,
+                subject.weekly_goal_minutes,
+                subject.grading_scheme,
+                subject.passing_grade,
+            ],
+        )?;
+        subject.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn update(conn: &Connection, subject: &Subject) -> Result<(), SubjectError> {
+        let id = subject.id.ok_or_else(|| SubjectError::DatabaseError("Subject id missing".into()))?;
+        subject.validate()?;
+        SubjectRepository::ensure_unique_name(conn, subject)?;
+
+        let updated = conn.execute(
+            "UPDATE subjects SET name = ?2, teacher = ?3, color = ?4, term = ?5, credit_hours = ?6,
+                weekly_goal_minutes = ?7, grading_scheme = ?8, passing_grade = ?9
+             WHERE id = ?1",
+            params![
+                id,
+                subject.name,
+                subject.teacher,
+                subject.color,
+                subject.term,
+                subject.credit_hours,
+                subject.weekly_goal_minutes,
+                subject.grading_scheme,
+                subject.passing_grade,
+            ],
+        )?;
+        if updated == 0 {
+            return Err(SubjectError::NotFound(id));
+        }
+        Ok(())
+    }
+
+    pub fn find(conn: &Connection, subject_id: u32) -> Result<Subject, SubjectError> {
+        conn.query_row(
+            &format!("SELECT {} FROM subjects WHERE id = ?1", SUBJECT_COLUMNS),
+            params![subject_id],
+            |row| Subject::try_from(row),
+        )
+        .optional()?
+        .ok_or(SubjectError::NotFound(subject_id))
+    }
+
+    pub fn list(conn: &Connection, user_id: u32) -> Result<Vec<Subject>, SubjectError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM subjects WHERE user_id = ?1 ORDER BY name COLLATE NOCASE, id",
+            SUBJECT_COLUMNS
+        ))?;
+        let subjects = stmt
+            .query_map(params![user_id], |row| Subject::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(subjects)
+    }
+
+    /// Deletes the subject; its tasks stay, without a subject.
+    pub fn delete(conn: &Connection, subject_id: u32) -> Result<bool, SubjectError> {
+        Ok(conn.execute("DELETE FROM subjects WHERE id = ?1", params![subject_id])? > 0)
+    }
+
+    pub fn set_task_subject(
+        conn: &Connection,
+        task_id: u32,
+        subject_id: Option<u32>,
+    ) -> Result<(), SubjectError> {
+        if let Some(subject_id) = subject_id {
+            SubjectRepository::find(conn, subject_id)?;
+        }
+        let updated = conn.execute(
+            "UPDATE tasks SET subject_id = ?1 WHERE id = ?2",
+            params![subject_id, task_id],
+        )?;
+        if updated == 0 {
+            return Err(SubjectError::TaskNotFound(task_id));
+        }
+        Ok(())
+    }
+
+    /// Minutes logged on the subject's tasks in the week (Monday to Sunday)
+    /// containing `now`.
+    pub fn minutes_in_week(conn: &Connection, subject_id: u32, now: NaiveDateTime) -> Result<u32, SubjectError> {
+        let start = week_start(now.date()).and_hms_opt(0, 0, 0).unwrap();
+        let end = start + Duration::days(7);
+
+        Ok(conn.query_row(
+            "SELECT COALESCE(SUM(l.minutes), 0) FROM time_logs l
+             JOIN tasks t ON t.id = l.task_id
+             WHERE t.subject_id = ?1 AND l.minutes IS NOT NULL
+                AND l.started_at >= ?2 AND l.started_at < ?3",
+            params![
+                subject_id,
+                start.format("%Y-%m-%dT%H:%M").to_string(),
+                end.format("%Y-%m-%dT%H:%M").to_string()
+            ],
+            |row| row.get(0),
+        )?)
+    }
+
+    pub fn dashboard(conn: &Connection, subject_id: u32, now: NaiveDateTime) -> Result<SubjectDashboard, SubjectError> {
+        let subject = SubjectRepository::find(conn, subject_id)?;
+        let (exams, pending_tasks): (Vec<_>, Vec<_>) = TaskRepository::get_open_subject_tasks(conn, subject_id)
+            .map_err(|e| SubjectError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .partition(|task| task.kind == TaskKind::Exam);
+        let minutes_this_week = SubjectRepository::minutes_in_week(conn, subject_id, now)?;
+
+        Ok(SubjectDashboard {
+            goal_progress: SubjectDashboard::goal_progress(minutes_this_week, subject.weekly_goal_minutes),
+            subject,
+            pending_tasks,
+            upcoming_exams: exams.into_iter().filter(|exam| exam.due_date.is_none_or(|due| due >= now)).collect(),
+            minutes_this_week,
+        })
+    }
+
+    pub fn dashboards(conn: &Connection, user_id: u32, now: NaiveDateTime) -> Result<Vec<SubjectDashboard>, SubjectError> {
+        SubjectRepository::list(conn, user_id)?
+            .into_iter()
+            .map(|subject| SubjectRepository::dashboard(conn, subject.id.unwrap_or_default(), now))
+            .collect()
+    }
+
+    fn ensure_unique_name(conn: &Connection, subject: &Subject) -> Result<(), SubjectError> {
+        let taken: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM subjects
+                WHERE user_id = ?1 AND name = ?2 COLLATE NOCASE AND id != ?3)",
+            params![subject.user_id, subject.name.trim(), subject.id.unwrap_or(0)],
+            |row| row.get(0),
+        )?;
+        if taken {
+            return Err(SubjectError::DuplicateName(subject.name.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, Task};
+    use crate::repository::{TimeLogRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_subject(conn: &Connection, name: &str) -> Subject {
+        let mut subject = Subject::new(1, name.to_string(), "blue".to_string()).unwrap();
+        SubjectRepository::create(conn, &mut subject).unwrap();
+        subject
+    }
+
+    fn create_task(conn: &Connection, title: &str, subject_id: u32, kind: TaskKind, due: &str) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.subject_id = Some(subject_id);
+        task.kind = kind;
+        task.due_date = Some(at(due));
+        TaskRepository::insert_task(conn, &mut task).unwrap();
+        task.id.unwrap()
+    }
+
+    #[test]
+    fn test_crud() {
+        let conn = setup_db();
+        let mut physics = create_subject(&conn, "Physics");
+        create_subject(&conn, "calculus");
+
+        let mut duplicate = Subject::new(1, "physics".to_string(), "red".to_string()).unwrap();
+        assert!(matches!(
+            SubjectRepository::create(&conn, &mut duplicate),
+            Err(SubjectError::DuplicateName(_))
+        ));
+
+        physics.teacher = Some("Dr. Lima".to_string());
+        physics.weekly_goal_minutes = Some(240);
+        SubjectRepository::update(&conn, &physics).unwrap();
+        assert_eq!(SubjectRepository::find(&conn, physics.id.unwrap()).unwrap(), physics);
+
+        let names: Vec<String> = SubjectRepository::list(&conn, 1).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["calculus", "Physics"]);
+
+        assert!(SubjectRepository::delete(&conn, physics.id.unwrap()).unwrap());
+        assert!(matches!(
+            SubjectRepository::find(&conn, physics.id.unwrap()),
+            Err(SubjectError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_deleting_subject_keeps_tasks() {
+        let conn = setup_db();
+        let subject_id = create_subject(&conn, "Physics").id.unwrap();
+        let task_id = create_task(&conn, "Lab report", subject_id, TaskKind::Task, "2025-03-12 09:00");
+
+        SubjectRepository::delete(&conn, subject_id).unwrap();
+
+        assert_eq!(TaskRepository::get_task_by_id(&conn, task_id).unwrap().subject_id, None);
+    }
+
+    #[test]
+    fn test_set_task_subject() {
+        let conn = setup_db();
+        let physics = create_subject(&conn, "Physics").id.unwrap();
+        let calculus = create_subject(&conn, "Calculus").id.unwrap();
+        let task_id = create_task(&conn, "Lab report", physics, TaskKind::Task, "2025-03-12 09:00");
+
+        SubjectRepository::set_task_subject(&conn, task_id, Some(calculus)).unwrap();
+        assert_eq!(TaskRepository::get_task_by_id(&conn, task_id).unwrap().subject_id, Some(calculus));
+
+        assert!(matches!(
+            SubjectRepository::set_task_subject(&conn, task_id, Some(99)),
+            Err(SubjectError::NotFound(99))
+        ));
+        assert!(matches!(
+            SubjectRepository::set_task_subject(&conn, 99, None),
+            Err(SubjectError::TaskNotFound(99))
+        ));
+    }
+
+    #[test]
+    fn test_dashboard() {
+        let conn = setup_db();
+        let mut subject = create_subject(&conn, "Physics");
+        subject.weekly_goal_minutes = Some(120);
+        SubjectRepository::update(&conn, &subject).unwrap();
+        let subject_id = subject.id.unwrap();
+
+        let report = create_task(&conn, "Lab report", subject_id, TaskKind::Task, "2025-03-12 09:00");
+        let done = create_task(&conn, "Reading", subject_id, TaskKind::Task, "2025-03-11 09:00");
+        create_task(&conn, "Past exam", subject_id, TaskKind::Exam, "2025-03-01 09:00");
+        let midterm = create_task(&conn, "Midterm", subject_id, TaskKind::Exam, "2025-03-20 09:00");
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, done, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        TimeLogRepository::log_time(&conn, report, at("2025-03-10 10:00"), 45, None).unwrap();
+        TimeLogRepository::log_time(&conn, done, at("2025-03-09 10:00"), 30, None).unwrap();
+
+        let dashboard = SubjectRepository::dashboard(&conn, subject_id, at("2025-03-12 08:00")).unwrap();
+        let ids = |tasks: &[Task]| tasks.iter().map(|task| task.id.unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(ids(&dashboard.pending_tasks), [report]);
+        assert_eq!(ids(&dashboard.upcoming_exams), [midterm]);
+        assert_eq!(dashboard.minutes_this_week, 45);
+        assert_eq!(dashboard.goal_progress, Some(0.375));
+    }
+}
@@ -1,5 +1,5 @@
 use rusqlite::{Connection, params, OptionalExtension};
-use crate::{models::Tag, errors::TagError};
+use crate::{models::Tag, errors::TagError, repository::UnitOfWork};
 
 pub struct TagRepository;
 
@@ -18,7 +18,7 @@ impl TagRepository {
     pub fn update_tag(conn: &Connection, id: u32, tag_name: String) -> Result<(), TagError> {
         conn.execute(
             "UPDATE tags SET tag_name = ?1 WHERE id = ?2",
-            &[&tag_name as &str, &id.to_string()],
+            params![tag_name, id],
         )
         .map_err(|e| TagError::DatabaseError(e.to_string()))?;
         
@@ -26,7 +26,7 @@ impl TagRepository {
     }
 
     pub fn find_by_id(conn: &Connection, id: u32) -> Result<Option<Tag>, TagError> {
-        let mut stmt = conn.prepare("SELECT id, name, color FROM tags WHERE id = ?1")
+        let mut stmt = conn.prepare("SELECT id, tag_name, tag_color FROM tags WHERE id = ?1")
             .map_err(|e| TagError::DatabaseError(e.to_string()))?;
         
         let tag = stmt.query_row(params![id], |row| {
@@ -43,7 +43,7 @@ impl TagRepository {
     }
     
     pub fn list_all(conn: &Connection) -> Result<Vec<Tag>, TagError> {
-        let mut stmt = conn.prepare("SELECT id, tag_name, tag_color FROM tags")
+        let mut stmt = conn.prepare("SELECT id, tag_name, tag_color FROM tags ORDER BY id")
             .map_err(|e| TagError::DatabaseError(e.to_string()))?;
         
         let tag_iter = stmt
@@ -63,8 +63,11 @@ impl TagRepository {
         Ok(tags)
     }
 
-    pub fn delete_by_id(conn: &Connection, id: u32) -> Result<bool, TagError> {
-        let rows_affected = conn
+    pub fn delete_by_id(uow: &UnitOfWork, id: u32) -> Result<bool, TagError> {
+        uow.execute("DELETE FROM task_tags WHERE tag_id = ?1", params![id])?;
+        uow.execute("DELETE FROM question_tags WHERE tag_id = ?1", params![id])?;
+
+        let rows_affected = uow
             .execute("DELETE FROM tags WHERE id = ?1", params![id])
             .map_err(|e| TagError::DatabaseError(e.to_string()))?;
 
@@ -80,7 +83,8 @@ impl TagRepository {
                     t.tag_color
                 FROM tags t
                 JOIN task_tags tt ON t.id = tt.tag_id
-                WHERE tt.task_id = ?"
+                WHERE tt.task_id = ?
+                ORDER BY t.id"
             )
             .map_err(|e| TagError::DatabaseError(e.to_string()))?;
         
@@ -104,35 +108,35 @@ impl TagRepository {
         }
     }
 
-    pub fn update_task_tags(conn: &Connection, task_id: u32, tags: &[Tag]) -> Result<(), TagError> {
-    conn.execute(
-        "DELETE FROM task_tags WHERE task_id = ?",
-        params![task_id],
-    ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
+    pub fn update_task_tags(uow: &UnitOfWork, task_id: u32, tags: &[Tag]) -> Result<(), TagError> {
+        uow.execute(
+            "DELETE FROM task_tags WHERE task_id = ?",
+            params![task_id],
+        ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
 
-    for tag in tags {
-        let tag_id: Option<u32> = conn.query_row(
-            "SELECT id FROM tags WHERE tag_name = ?",
-            params![tag.name],
-            |row| row.get(0),
-        ).optional().map_err(|e| TagError::DatabaseError(e.to_string()))?;
+        for tag in tags {
+            let tag_id: Option<u32> = uow.query_row(
+                "SELECT id FROM tags WHERE tag_name = ?",
+                params![tag.name],
+                |row| row.get(0),
+            ).optional().map_err(|e| TagError::DatabaseError(e.to_string()))?;
 
-        let tag_id = match tag_id {
-            Some(id) => id,
-            None => {
-                conn.execute(
-                    "INSERT INTO tags (tag_name, tag_color) VALUES (?, ?)",
-                    params![tag.name, tag.color],
-                ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
-                conn.last_insert_rowid() as u32
-            }
-        };
+            let tag_id = match tag_id {
+                Some(id) => id,
+                None => {
+                    uow.execute(
+                        "INSERT INTO tags (tag_name, tag_color) VALUES (?, ?)",
+                        params![tag.name, tag.color],
+                    ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
+                    uow.last_insert_rowid() as u32
+                }
+            };
 
-        conn.execute(
-            "INSERT INTO task_tags (task_id, tag_id) VALUES (?, ?)",
-            params![task_id, tag_id],
-        ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
-    }
+            uow.execute(
+                "INSERT INTO task_tags (task_id, tag_id) VALUES (?, ?)",
+                params![task_id, tag_id],
+            ).map_err(|e| TagError::DatabaseError(e.to_string()))?;
+        }
         Ok(())
     }
     
@@ -142,18 +146,11 @@ impl TagRepository {
 mod tests {
     use super::*;
     use rusqlite::Connection;
+    use crate::utils::initialize_database;
     
     fn setup_db() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
-        conn.execute(
-            "CREATE TABLE tags (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                color TEXT NOT NULL
-            )",
-            [],
-        )
-        .unwrap();
+        initialize_database(&conn).unwrap();
         conn
     }
     
@@ -167,7 +164,7 @@ mod tests {
         
         let stored_tag: Tag = conn
             .query_row(
-                "SELECT id, name, color FROM tags WHERE id = ?1",
+                "SELECT id, tag_name, tag_color FROM tags WHERE id = ?1",
                 [tag.id.unwrap()],
                 |row| {
                     Ok(Tag {
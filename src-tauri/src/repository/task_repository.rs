@@ -1,5 +1,5 @@
-use rusqlite::{params, Connection, ToSql};
-use chrono::{Utc, NaiveDateTime, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
+use chrono::{Utc, NaiveDateTime};
 use crate::models::{
     task_status::TaskStatus, 
     task_priority::TaskPriority,
@@ -8,7 +8,22 @@ use crate::models::{
 };
 use crate::errors::TaskError;
 use crate::utils::format_date::truncate_to_minute;
-use crate::repository::TagRepository;
+use crate::utils::SqliteDateTime;
+use crate::utils::rank::{initial_ranks, rank_between};
+use crate::repository::{TagRepository, UnitOfWork};
+
+/// Columns read by `Task::try_from`, for queries over `tasks t`. `overdue`
+/// is evaluated against the current UTC minute.
+const TASK_COLUMNS: &str = "t.id, t.user_id, t.title, t.description, t.status, t.priority, t.created_at, t.updated_at,
+    t.due_date, t.all_day, t.start_date, t.rank, t.estimated_minutes, t.subject_id, t.kind, t.completed_at,
+    (t.completed_at IS NULL AND t.due_date IS NOT NULL AND t.due_date < strftime('%Y-%m-%dT%H:%M', 'now')) AS overdue";
+
+/// Orders by due date with undated tasks last.
+const BY_DUE_DATE: &str = "t.due_date IS NULL, t.due_date, t.id";
+
+fn format_minute(at: NaiveDateTime) -> String {
+    truncate_to_minute(at).format("%Y-%m-%dT%H:%M").to_string()
+}
 
 pub struct TaskRepository;
 
@@ -16,42 +31,61 @@ impl TaskRepository {
     pub fn insert_task(conn: &Connection, task: &mut Task) -> Result<(), TaskError> {
         let created_at = truncate_to_minute(task.created_at);
         let updated_at = truncate_to_minute(task.updated_at);
-        let due_date = truncate_to_minute(task.due_date);
+        TaskRepository::check_status_defined(conn, task.user_id, &task.status)?;
+        task.check_dates()?;
+        let rank = TaskRepository::next_rank(conn, task.user_id, &task.status)?;
 
         conn.execute(
-            "INSERT INTO tasks (title, user_id, description, status, priority, created_at, updated_at, due_date)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO tasks (title, user_id, description, status, priority, created_at, updated_at, due_date, rank, estimated_minutes,
+                subject_id, kind, completed_at, all_day, start_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 task.title,
                 task.user_id,
                 task.description,
                 task.status.to_string(),
-                task.priority.to_string(),
+                task.priority,
                 created_at.format("%Y-%m-%dT%H:%M").to_string(),
                 updated_at.format("%Y-%m-%dT%H:%M").to_string(),
-                due_date.format("%Y-%m-%dT%H:%M").to_string(),
+                task.due_date.map(format_minute),
+                rank,
+                task.estimated_minutes,
+                task.subject_id,
+                task.kind,
+                task.completed_at.map(format_minute),
+                task.all_day,
+                task.start_date.map(format_minute),
             ],
         ).map_err(|e| TaskError::DatabaseError(e.to_string()))?;
 
-        task.id = Some(conn.last_insert_rowid() as u32);
+        let task_id = conn.last_insert_rowid() as u32;
+        TaskRepository::sync_completed_at(conn, task_id, updated_at)?;
+        task.completed_at = conn
+            .query_row("SELECT completed_at FROM tasks WHERE id = ?1", params![task_id], |row| {
+                row.get::<_, Option<SqliteDateTime>>(0)
+            })?
+            .map(Into::into);
+        task.id = Some(task_id);
+        task.rank = rank;
+        Ok(())
+    }
+
+    pub fn insert_task_with_tags(uow: &UnitOfWork, task: &mut Task, tags: &[Tag]) -> Result<(), TaskError> {
+        TaskRepository::insert_task(uow, task)?;
+
+        let task_id = task.id.ok_or_else(|| TaskError::DatabaseError("Task id missing after insert".to_owned()))?;
+        TagRepository::update_task_tags(uow, task_id, tags)
+            .map_err(|e| TaskError::DatabaseError(format!("Failed to associate tags: {}", e)))?;
+
+        task.tags = TagRepository::get_task_tags(uow, task_id)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?
+            .unwrap_or_default();
         Ok(())
     }
 
     pub fn get_all_tasks(conn: &Connection) -> Result<Vec<Task>, TaskError> {
         let mut stmt = conn
-            .prepare(
-                "SELECT 
-                    id, 
-                    user_id, 
-                    title, 
-                    description, 
-                    status, 
-                    priority,
-                    created_at, 
-                    updated_at, 
-                    due_date
-                FROM tasks",
-            )
+            .prepare(&format!("SELECT {} FROM tasks t ORDER BY t.id", TASK_COLUMNS))
             .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
 
         let tasks = stmt
@@ -75,8 +109,10 @@ impl TaskRepository {
         tasks_with_tags
     }
 
-    pub fn delete_task(conn: &Connection, task_id: u32) -> Result<(), TaskError> {
-        conn.execute(
+    /// Deletes a task. Its tags, time logs, reminders, links and attachments
+    /// go with it through the foreign keys.
+    pub fn delete_task(uow: &UnitOfWork, task_id: u32) -> Result<(), TaskError> {
+        uow.execute(
             "DELETE FROM tasks WHERE id = ?1",
             params![task_id],
         )
@@ -85,12 +121,7 @@ impl TaskRepository {
     }
 
     pub fn get_task_by_id(conn: &Connection, task_id: u32) -> Result<Task, TaskError> {
-        let mut stmt = conn.prepare(
-            "SELECT 
-                id, user_id, title, description, status, priority, created_at, updated_at, due_date
-             FROM tasks
-             WHERE id = ?1",
-        )
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM tasks t WHERE t.id = ?1", TASK_COLUMNS))
         .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
     
         let mut task = stmt.query_row([task_id], |row| Task::try_from((conn, row)))
@@ -100,7 +131,8 @@ impl TaskRepository {
             "SELECT t.id, t.tag_name, t.tag_color
              FROM tags t
              JOIN task_tags tt ON t.id = tt.tag_id
-             WHERE tt.task_id = ?1"
+             WHERE tt.task_id = ?1
+             ORDER BY t.id"
         ).map_err(|e| TaskError::DatabaseError(e.to_string()))?;
     
         let tags = tag_stmt
@@ -120,8 +152,9 @@ impl TaskRepository {
         Ok(task)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_task(
-        conn: &Connection,
+        uow: &UnitOfWork,
         task_id: u32,
         title: Option<String>,
         description: Option<String>,
@@ -133,6 +166,7 @@ impl TaskRepository {
         let mut query = String::from("UPDATE tasks SET ");
         let mut updates = Vec::new();
         let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        let status_changed = status.is_some();
 
         if let Some(title) = title {
             updates.push("title = ?");
@@ -145,19 +179,44 @@ impl TaskRepository {
         }
 
         if let Some(status) = status {
+            let current: Option<(u32, TaskStatus)> = uow
+                .query_row(
+                    "SELECT user_id, status FROM tasks WHERE id = ?1",
+                    params![task_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if let Some((user_id, _)) = current.filter(|(_, current)| current != &status) {
+                TaskRepository::check_status_defined(uow, user_id, &status)?;
+                TaskRepository::check_wip_limit(uow, user_id, &status, task_id)?;
+                updates.push("rank = ?");
+                params.push(Box::new(TaskRepository::next_rank(uow, user_id, &status)?));
+            }
             updates.push("status = ?");
             params.push(Box::new(status.to_string()));
         }
 
         if let Some(priority) = priority {
             updates.push("priority = ?");
-            params.push(Box::new(priority.to_string()));
+            params.push(Box::new(priority));
         }
 
+        // A new due date is a timed one and must not precede the start date.
         if let Some(due_date) = due_date {
-            let due_date = truncate_to_minute(due_date);
+            let exists: bool = uow.query_row(
+                "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1)",
+                params![task_id],
+                |row| row.get(0),
+            )?;
+            if exists {
+                let mut task = TaskRepository::get_task_by_id(uow, task_id)?;
+                task.due_date = Some(due_date);
+                task.all_day = false;
+                task.check_dates()?;
+            }
             updates.push("due_date = ?");
-            params.push(Box::new(due_date.format("%Y-%m-%dT%H:%M").to_string()));
+            params.push(Box::new(format_minute(due_date)));
+            updates.push("all_day = 0");
         }
 
         let updated_at = truncate_to_minute(Utc::now().naive_utc());
@@ -174,24 +233,87 @@ impl TaskRepository {
 
         let params_ref: Vec<&dyn ToSql> = params.iter().map(|p| &**p).collect();
 
-        conn.execute(&query, params_ref.as_slice())
+        uow.execute(&query, params_ref.as_slice())
             .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+        if status_changed {
+            TaskRepository::sync_completed_at(uow, task_id, updated_at)?;
+        }
 
-            if let Some(tags) = &tags {
-                for tag in tags {
-                    if tag.id.is_none() {
-                        return Err(TaskError::InvalidTag(format!(
-                            "A tag '{}' não tem um ID associado", tag.name
-                        )));
-                    }
+        if let Some(tags) = &tags {
+            for tag in tags {
+                if tag.id.is_none() {
+                    return Err(TaskError::InvalidTag(format!(
+                        "A tag '{}' não tem um ID associado", tag.name
+                    )));
                 }
-                TaskRepository::update_task_tags(conn, task_id, tags)?;
             }
-            
+            TaskRepository::update_task_tags(uow, task_id, tags)?;
+        }
 
         Ok(())
     }
 
+    /// Writes a previously captured task back exactly as it was, re-creating it
+    /// under its original id if it has since been deleted.
+    pub fn restore_task(uow: &UnitOfWork, task: &Task) -> Result<(), TaskError> {
+        let task_id = task.id.ok_or_else(|| TaskError::DatabaseError("Cannot restore a task without id".to_owned()))?;
+        // Subjects and tags deleted since the snapshot are dropped, as their own delete would have done.
+        let subject_id = match task.subject_id {
+            Some(subject_id) => uow
+                .query_row("SELECT id FROM subjects WHERE id = ?1", params![subject_id], |row| row.get::<_, u32>(0))
+                .optional()?,
+            None => None,
+        };
+        let mut tags = Vec::with_capacity(task.tags.len());
+        for tag in &task.tags {
+            let exists: bool = uow.query_row(
+                "SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)",
+                params![tag.id],
+                |row| row.get(0),
+            )?;
+            if exists {
+                tags.push(tag.clone());
+            }
+        }
+        let values = params![
+            task_id,
+            task.title,
+            task.user_id,
+            task.description,
+            task.status.to_string(),
+            task.priority,
+            truncate_to_minute(task.created_at).format("%Y-%m-%dT%H:%M").to_string(),
+            truncate_to_minute(task.updated_at).format("%Y-%m-%dT%H:%M").to_string(),
+            task.due_date.map(format_minute),
+            task.rank,
+            task.estimated_minutes,
+            subject_id,
+            task.kind,
+            task.completed_at.map(format_minute),
+            task.all_day,
+            task.start_date.map(format_minute),
+        ];
+
+        let updated = uow.execute(
+            "UPDATE tasks SET title = ?2, user_id = ?3, description = ?4, status = ?5, priority = ?6,
+                created_at = ?7, updated_at = ?8, due_date = ?9, rank = ?10,
+                estimated_minutes = ?11, subject_id = ?12, kind = ?13, completed_at = ?14, all_day = ?15, start_date = ?16
+             WHERE id = ?1",
+            values,
+        )?;
+
+        if updated == 0 {
+            uow.execute(
+                "INSERT INTO tasks (id, title, user_id, description, status, priority, created_at, updated_at, due_date, rank, estimated_minutes,
+                    subject_id, kind, completed_at, all_day, start_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                values,
+            )?;
+        }
+
+        TaskRepository::update_task_tags(uow, task_id, &tags)
+    }
+
     pub fn update_task_tags(conn: &Connection, task_id: u32, tags: &[Tag]) -> Result<(), TaskError> {
         conn.execute(
             "DELETE FROM task_tags WHERE task_id = ?1",
@@ -209,22 +331,367 @@ impl TaskRepository {
         Ok(())
     }
 
-    pub fn get_tasks_for_today(conn: &Connection) -> Result<Vec<Task>, TaskError> {
-        let today: NaiveDate = Utc::now().date_naive();
+    /// Sets or clears the estimate; `Some(0)` is rejected.
+    pub fn set_estimated_minutes(conn: &Connection, task_id: u32, minutes: Option<u32>) -> Result<(), TaskError> {
+        if minutes == Some(0) {
+            return Err(TaskError::InvalidEstimate("Estimate must be at least one minute".to_owned()));
+        }
+        let updated = conn.execute(
+            "UPDATE tasks SET estimated_minutes = ?1 WHERE id = ?2",
+            params![minutes, task_id],
+        )?;
+        if updated == 0 {
+            return Err(TaskError::DatabaseError(format!("Task {} not found", task_id)));
+        }
+        Ok(())
+    }
+
+    /// Saves `task`'s due date, all-day flag and start date.
+    pub fn set_dates(conn: &Connection, task: &Task) -> Result<(), TaskError> {
+        let task_id = task.id.ok_or_else(|| TaskError::DatabaseError("Task id missing".to_owned()))?;
+        task.check_dates()?;
+        let updated = conn.execute(
+            "UPDATE tasks SET due_date = ?1, all_day = ?2, start_date = ?3, updated_at = ?4 WHERE id = ?5",
+            params![
+                task.due_date.map(format_minute),
+                task.all_day,
+                task.start_date.map(format_minute),
+                format_minute(Utc::now().naive_utc()),
+                task_id
+            ],
+        )?;
+        if updated == 0 {
+            return Err(TaskError::DatabaseError(format!("Task {} not found", task_id)));
+        }
+        Ok(())
+    }
+
+    /// A user's open tasks that have started by `now`, by due date with undated
+    /// tasks last.
+    pub fn get_available_tasks(conn: &Connection, user_id: u32, now: NaiveDateTime) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE t.user_id = ?1 AND ws.category != 'completed' AND (t.start_date IS NULL OR t.start_date <= ?2)
+             ORDER BY {}",
+            TASK_COLUMNS, BY_DUE_DATE
+        ))?;
+
+        let tasks = stmt
+            .query_map(params![user_id, format_minute(now)], |row| Task::try_from((conn, row)))?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// A user's tasks whose status is not in the "completed" category.
+    pub fn get_open_tasks(conn: &Connection, user_id: u32) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE t.user_id = ?1 AND ws.category != 'completed'
+             ORDER BY {}",
+            TASK_COLUMNS, BY_DUE_DATE
+        ))?;
+
+        let tasks = stmt
+            .query_map(params![user_id], |row| Task::try_from((conn, row)))?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// A subject's tasks and exams whose status is not in the "completed"
+    /// category, by due date.
+    pub fn get_open_subject_tasks(conn: &Connection, subject_id: u32) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE t.subject_id = ?1 AND ws.category != 'completed'
+             ORDER BY {}",
+            TASK_COLUMNS, BY_DUE_DATE
+        ))?;
+
+        let tasks = stmt
+            .query_map(params![subject_id], |row| Task::try_from((conn, row)))?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// The user's tasks in one board column, in rank order.
+    pub fn get_column(conn: &Connection, user_id: u32, status: &TaskStatus) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t WHERE t.user_id = ?1 AND t.status = ?2 ORDER BY t.rank, t.id",
+            TASK_COLUMNS
+        ))?;
+
+        let tasks = stmt
+            .query_map(params![user_id, status.to_string()], |row| Task::try_from((conn, row)))?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// Moves a task to `index` within its owner's `status` column (the end
+    /// when `None` or out of range). Only the moved task's row is rewritten, unless the
+    /// column holds ranks that leave no room and has to be renumbered.
+    pub fn move_task(
+        uow: &UnitOfWork,
+        task_id: u32,
+        status: TaskStatus,
+        index: Option<usize>,
+    ) -> Result<Task, TaskError> {
+        let task = TaskRepository::get_task_by_id(uow, task_id)?;
+        if task.status != status {
+            TaskRepository::check_status_defined(uow, task.user_id, &status)?;
+            TaskRepository::check_wip_limit(uow, task.user_id, &status, task_id)?;
+        }
+
+        let mut ranks = TaskRepository::column_ranks(uow, task.user_id, &status, task_id)?;
+        let index = index.unwrap_or(ranks.len()).min(ranks.len());
+
+        let rank = match TaskRepository::rank_at(&ranks, index) {
+            Ok(rank) => rank,
+            Err(_) => {
+                TaskRepository::renumber_column(uow, task.user_id, &status, task_id)?;
+                ranks = TaskRepository::column_ranks(uow, task.user_id, &status, task_id)?;
+                TaskRepository::rank_at(&ranks, index).map_err(TaskError::DatabaseError)?
+            }
+        };
+
+        let updated_at = truncate_to_minute(Utc::now().naive_utc());
+        uow.execute(
+            "UPDATE tasks SET status = ?1, rank = ?2, updated_at = ?3 WHERE id = ?4",
+            params![
+                status.to_string(),
+                rank,
+                updated_at.format("%Y-%m-%dT%H:%M").to_string(),
+                task_id
+            ],
+        )?;
+        TaskRepository::sync_completed_at(uow, task_id, updated_at)?;
+
+        TaskRepository::get_task_by_id(uow, task_id)
+    }
+
+    /// Stamps `completed_at` with `now` when the task is in a completed status
+    /// and has none yet, and clears it otherwise. Call after any status change.
+    pub fn sync_completed_at(conn: &Connection, task_id: u32, now: NaiveDateTime) -> Result<(), TaskError> {
+        conn.execute(
+            "UPDATE tasks SET completed_at = CASE
+                WHEN EXISTS(SELECT 1 FROM workflow_statuses ws
+                            WHERE ws.user_id = tasks.user_id AND ws.key = tasks.status AND ws.category = 'completed')
+                THEN COALESCE(completed_at, ?2)
+                ELSE NULL
+             END
+             WHERE id = ?1",
+            params![task_id, truncate_to_minute(now).format("%Y-%m-%dT%H:%M").to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// A user's open tasks due before `now`, most overdue first.
+    pub fn get_overdue_tasks(conn: &Connection, user_id: u32, now: NaiveDateTime) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             WHERE t.user_id = ?1 AND t.completed_at IS NULL AND t.due_date IS NOT NULL AND t.due_date < ?2
+             ORDER BY t.due_date, t.id",
+            TASK_COLUMNS
+        ))?;
+        let tasks = stmt
+            .query_map(
+                params![user_id, truncate_to_minute(now).format("%Y-%m-%dT%H:%M").to_string()],
+                |row| Task::try_from((conn, row)),
+            )?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// A user's tasks, open or not, due in `[start, end)`, in due order.
+    pub fn get_tasks_in_range(
+        conn: &Connection,
+        user_id: u32,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             WHERE t.user_id = ?1 AND t.due_date >= ?2 AND t.due_date < ?3
+             ORDER BY t.due_date, t.id",
+            TASK_COLUMNS
+        ))?;
+        let tasks = stmt
+            .query_map(
+                params![
+                    user_id,
+                    truncate_to_minute(start).format("%Y-%m-%dT%H:%M").to_string(),
+                    truncate_to_minute(end).format("%Y-%m-%dT%H:%M").to_string()
+                ],
+                |row| Task::try_from((conn, row)),
+            )?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// A user's tasks completed in `[start, end)`, in completion order.
+    pub fn get_completed_between(
+        conn: &Connection,
+        user_id: u32,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             WHERE t.user_id = ?1 AND t.completed_at >= ?2 AND t.completed_at < ?3
+             ORDER BY t.completed_at, t.id",
+            TASK_COLUMNS
+        ))?;
+        let tasks = stmt
+            .query_map(
+                params![
+                    user_id,
+                    truncate_to_minute(start).format("%Y-%m-%dT%H:%M").to_string(),
+                    truncate_to_minute(end).format("%Y-%m-%dT%H:%M").to_string()
+                ],
+                |row| Task::try_from((conn, row)),
+            )?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
+
+    /// A user's tasks completed after their due date, most recent first.
+    pub fn get_completed_late(conn: &Connection, user_id: u32) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t
+             WHERE t.user_id = ?1 AND t.completed_at > t.due_date
+             ORDER BY t.completed_at DESC, t.id",
+            TASK_COLUMNS
+        ))?;
+        let tasks = stmt
+            .query_map(params![user_id], |row| Task::try_from((conn, row)))?
+            .collect::<Result<Vec<Task>, _>>()?;
+        Ok(tasks)
+    }
 
-        let start_of_day: NaiveDateTime = today.and_hms_opt(0, 0, 0).unwrap();
-        let end_of_day: NaiveDateTime = today.and_hms_opt(23, 59, 59).unwrap();
+    pub fn get_wip_limit(conn: &Connection, user_id: u32, status: &TaskStatus) -> Result<Option<u32>, TaskError> {
+        Ok(conn
+            .query_row(
+                "SELECT max_tasks FROM wip_limits WHERE user_id = ?1 AND status = ?2",
+                params![user_id, status.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
 
+    /// Sets or, with `None`, removes the WIP limit of one of the user's
+    /// columns. Columns already over a new limit keep their tasks; only
+    /// further moves in are refused.
+    pub fn set_wip_limit(conn: &Connection, user_id: u32, status: &TaskStatus, limit: Option<u32>) -> Result<(), TaskError> {
+        match limit {
+            Some(0) => Err(TaskError::InvalidStatus("WIP limit must be greater than zero".to_owned())),
+            Some(limit) => {
+                TaskRepository::check_status_defined(conn, user_id, status)?;
+                conn.execute(
+                    "INSERT INTO wip_limits (user_id, status, max_tasks) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(user_id, status) DO UPDATE SET max_tasks = excluded.max_tasks",
+                    params![user_id, status.to_string(), limit],
+                )?;
+                Ok(())
+            }
+            None => {
+                conn.execute(
+                    "DELETE FROM wip_limits WHERE user_id = ?1 AND status = ?2",
+                    params![user_id, status.to_string()],
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Fails if moving `task_id` into the user's `status` column would exceed
+    /// its limit.
+    fn check_wip_limit(conn: &Connection, user_id: u32, status: &TaskStatus, task_id: u32) -> Result<(), TaskError> {
+        let Some(limit) = TaskRepository::get_wip_limit(conn, user_id, status)? else {
+            return Ok(());
+        };
+
+        let count: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM tasks WHERE user_id = ?1 AND status = ?2 AND id != ?3",
+            params![user_id, status.to_string(), task_id],
+            |row| row.get(0),
+        )?;
+
+        if count >= limit {
+            return Err(TaskError::WipLimitReached(format!(
+                "column '{}' already has {} of {} tasks", status, count, limit
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_status_defined(conn: &Connection, user_id: u32, status: &TaskStatus) -> Result<(), TaskError> {
+        let defined: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM workflow_statuses WHERE user_id = ?1 AND key = ?2)",
+            params![user_id, status.as_str()],
+            |row| row.get(0),
+        )?;
+        if !defined {
+            return Err(TaskError::InvalidStatus(format!(
+                "'{}' is not one of user {}'s statuses", status, user_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn next_rank(conn: &Connection, user_id: u32, status: &TaskStatus) -> Result<String, TaskError> {
+        let last: Option<String> = conn.query_row(
+            "SELECT MAX(rank) FROM tasks WHERE user_id = ?1 AND status = ?2 AND rank != ''",
+            params![user_id, status.to_string()],
+            |row| row.get(0),
+        )?;
+        rank_between(last.as_deref(), None).map_err(TaskError::DatabaseError)
+    }
+
+    fn column_ranks(conn: &Connection, user_id: u32, status: &TaskStatus, excluding: u32) -> Result<Vec<String>, TaskError> {
         let mut stmt = conn.prepare(
-            "SELECT 
-                id, user_id, title, description, status, priority, created_at, updated_at, due_date
-             FROM tasks
-             WHERE due_date >= ?1 AND due_date <= ?2",
-        ).map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+            "SELECT rank FROM tasks WHERE user_id = ?1 AND status = ?2 AND id != ?3 ORDER BY rank, id",
+        )?;
+        let ranks = stmt
+            .query_map(params![user_id, status.to_string(), excluding], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ranks)
+    }
+
+    fn rank_at(ranks: &[String], index: usize) -> Result<String, String> {
+        let before = index.checked_sub(1).map(|i| ranks[i].as_str());
+        rank_between(before, ranks.get(index).map(String::as_str))
+    }
+
+    /// Re-spaces a column whose ranks are missing or collide, keeping its order.
+    fn renumber_column(conn: &Connection, user_id: u32, status: &TaskStatus, excluding: u32) -> Result<(), TaskError> {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM tasks WHERE user_id = ?1 AND status = ?2 AND id != ?3 ORDER BY rank, id",
+        )?;
+        let ids = stmt
+            .query_map(params![user_id, status.to_string(), excluding], |row| row.get(0))?
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        for (id, rank) in ids.iter().zip(initial_ranks(ids.len())) {
+            conn.execute("UPDATE tasks SET rank = ?1 WHERE id = ?2", params![rank, id])?;
+        }
+        Ok(())
+    }
+
+    pub fn get_tasks_due_between(
+        conn: &Connection,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tasks t WHERE t.due_date >= ?1 AND t.due_date <= ?2 ORDER BY t.due_date, t.id",
+            TASK_COLUMNS
+        )).map_err(|e| TaskError::DatabaseError(e.to_string()))?;
 
         let tasks = stmt.query_map(params![
-            start_of_day.format("%Y-%m-%dT%H:%M:%S").to_string(),
-            end_of_day.format("%Y-%m-%dT%H:%M:%S").to_string()
+            truncate_to_minute(start).format("%Y-%m-%dT%H:%M").to_string(),
+            truncate_to_minute(end).format("%Y-%m-%dT%H:%M").to_string()
         ], |row| {
             Task::try_from((conn, row))
         })
@@ -235,3 +702,328 @@ impl TaskRepository {
         Ok(tasks)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::initialize_database;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO users (name, status) VALUES ('Alice', 'active')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn fail_inserts_into(conn: &Connection, table: &str) {
+        conn.execute_batch(&format!(
+            "CREATE TRIGGER fail_{table} BEFORE INSERT ON {table}
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END;"
+        ))
+        .unwrap();
+    }
+
+    fn count(conn: &Connection, table: &str) -> u32 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn tag(name: &str) -> Tag {
+        Tag::new(name.to_string(), "#123456".to_string()).unwrap()
+    }
+
+    fn create_task(conn: &Connection, title: &str, tags: &[Tag]) -> Task {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        UnitOfWork::run(conn, |uow| TaskRepository::insert_task_with_tags(uow, &mut task, tags))
+            .unwrap();
+        task
+    }
+
+    #[test]
+    fn test_insert_task_with_tags() {
+        let conn = setup_db();
+
+        let task = create_task(&conn, "Study", &[tag("Physics"), tag("Math")]);
+
+        let stored = TaskRepository::get_task_by_id(&conn, task.id.unwrap()).unwrap();
+        assert_eq!(stored.title, "Study");
+        assert_eq!(stored.tags.len(), 2);
+        assert_eq!(task.tags.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_task_rolls_back_when_tag_link_fails() {
+        let conn = setup_db();
+        fail_inserts_into(&conn, "task_tags");
+
+        let mut task = Task::new("Study".to_string(), 1, None, None).unwrap();
+        let result = UnitOfWork::run(&conn, |uow| {
+            TaskRepository::insert_task_with_tags(uow, &mut task, &[tag("Physics")])
+        });
+
+        assert!(result.is_err());
+        assert_eq!(count(&conn, "tasks"), 0);
+        assert_eq!(count(&conn, "tags"), 0);
+    }
+
+    #[test]
+    fn test_insert_task_rolls_back_when_second_tag_fails() {
+        let conn = setup_db();
+        conn.execute_batch(
+            "CREATE TRIGGER fail_second_tag BEFORE INSERT ON tags
+             WHEN NEW.tag_name = 'Math'
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+        )
+        .unwrap();
+
+        let mut task = Task::new("Study".to_string(), 1, None, None).unwrap();
+        let result = UnitOfWork::run(&conn, |uow| {
+            TaskRepository::insert_task_with_tags(uow, &mut task, &[tag("Physics"), tag("Math")])
+        });
+
+        assert!(result.is_err());
+        assert_eq!(count(&conn, "tasks"), 0);
+        assert_eq!(count(&conn, "tags"), 0);
+        assert_eq!(count(&conn, "task_tags"), 0);
+    }
+
+    #[test]
+    fn test_update_task_rolls_back_when_tag_link_fails() {
+        let conn = setup_db();
+        let task = create_task(&conn, "Study", &[tag("Physics")]);
+        let task_id = task.id.unwrap();
+        let existing_tags = task.tags.clone();
+        fail_inserts_into(&conn, "task_tags");
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(
+                uow,
+                task_id,
+                Some("Renamed".to_string()),
+                None,
+                Some(TaskStatus::Done),
+                None,
+                None,
+                Some(existing_tags),
+            )
+        });
+
+        assert!(result.is_err());
+        let stored = TaskRepository::get_task_by_id(&conn, task_id).unwrap();
+        assert_eq!(stored.title, "Study");
+        assert_eq!(stored.status, TaskStatus::Todo);
+        assert_eq!(stored.tags.len(), 1);
+    }
+
+    #[test]
+    fn test_update_task_rejects_tags_without_id() {
+        let conn = setup_db();
+        let task = create_task(&conn, "Study", &[]);
+        let task_id = task.id.unwrap();
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(
+                uow,
+                task_id,
+                Some("Renamed".to_string()),
+                None,
+                None,
+                None,
+                None,
+                Some(vec![tag("Physics")]),
+            )
+        });
+
+        assert!(matches!(result, Err(TaskError::InvalidTag(_))));
+        let stored = TaskRepository::get_task_by_id(&conn, task_id).unwrap();
+        assert_eq!(stored.title, "Study");
+    }
+
+    #[test]
+    fn test_delete_task_removes_tag_links() {
+        let conn = setup_db();
+        let task = create_task(&conn, "Study", &[tag("Physics")]);
+        conn.execute("INSERT INTO reminders (task_id, minutes_before_due) VALUES (?1, 30)", [task.id]).unwrap();
+        conn.execute("INSERT INTO time_logs (task_id, started_at) VALUES (?1, '2025-03-10T09:00')", [task.id]).unwrap();
+
+        UnitOfWork::run(&conn, |uow| TaskRepository::delete_task(uow, task.id.unwrap()))
+            .unwrap();
+
+        assert_eq!(count(&conn, "tasks"), 0);
+        assert_eq!(count(&conn, "task_tags"), 0);
+        assert_eq!(count(&conn, "reminders"), 0);
+        assert_eq!(count(&conn, "time_logs"), 0);
+        assert_eq!(count(&conn, "tags"), 1);
+    }
+
+    #[test]
+    fn test_delete_task_rolls_back_when_task_delete_fails() {
+        let conn = setup_db();
+        let task = create_task(&conn, "Study", &[tag("Physics")]);
+        conn.execute_batch(
+            "CREATE TRIGGER fail_task_delete BEFORE DELETE ON tasks
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+        )
+        .unwrap();
+
+        let result =
+            UnitOfWork::run(&conn, |uow| TaskRepository::delete_task(uow, task.id.unwrap()));
+
+        assert!(result.is_err());
+        assert_eq!(count(&conn, "tasks"), 1);
+        assert_eq!(count(&conn, "task_tags"), 1);
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_move_task_rewrites_only_the_moved_row() {
+        let conn = setup_db();
+        let first = create_task(&conn, "First", &[]);
+        let second = create_task(&conn, "Second", &[]);
+        let third = create_task(&conn, "Third", &[]);
+
+        let moved = UnitOfWork::run(&conn, |uow| {
+            TaskRepository::move_task(uow, third.id.unwrap(), TaskStatus::Todo, Some(0))
+        })
+        .unwrap();
+
+        let column = TaskRepository::get_column(&conn, 1, &TaskStatus::Todo).unwrap();
+        assert_eq!(titles(&column), ["Third", "First", "Second"]);
+        assert_eq!(moved.rank, column[0].rank);
+        assert_eq!(column[1].rank, first.rank);
+        assert_eq!(column[2].rank, second.rank);
+    }
+
+    #[test]
+    fn test_move_task_renumbers_colliding_ranks() {
+        let conn = setup_db();
+        create_task(&conn, "First", &[]);
+        create_task(&conn, "Second", &[]);
+        let third = create_task(&conn, "Third", &[]);
+        conn.execute("UPDATE tasks SET rank = 'V'", []).unwrap();
+
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::move_task(uow, third.id.unwrap(), TaskStatus::Todo, Some(1))
+        })
+        .unwrap();
+
+        let column = TaskRepository::get_column(&conn, 1, &TaskStatus::Todo).unwrap();
+        assert_eq!(titles(&column), ["First", "Third", "Second"]);
+    }
+
+    #[test]
+    fn test_update_task_enforces_wip_limit() {
+        let conn = setup_db();
+        let first = create_task(&conn, "First", &[]);
+        let second = create_task(&conn, "Second", &[]);
+        TaskRepository::set_wip_limit(&conn, 1, &TaskStatus::InProgress, Some(1)).unwrap();
+
+        let change_status = |task_id| {
+            UnitOfWork::run(&conn, |uow| {
+                TaskRepository::update_task(uow, task_id, None, None, Some(TaskStatus::InProgress), None, None, None)
+            })
+        };
+
+        change_status(first.id.unwrap()).unwrap();
+        change_status(first.id.unwrap()).unwrap();
+        let result = change_status(second.id.unwrap());
+
+        assert!(matches!(result, Err(TaskError::WipLimitReached(_))));
+        let stored = TaskRepository::get_task_by_id(&conn, second.id.unwrap()).unwrap();
+        assert_eq!(stored.status, TaskStatus::Todo);
+    }
+
+    #[test]
+    fn test_zero_wip_limit_is_rejected() {
+        let conn = setup_db();
+        let result = TaskRepository::set_wip_limit(&conn, 1, &TaskStatus::Done, Some(0));
+        assert!(result.is_err());
+        assert_eq!(TaskRepository::get_wip_limit(&conn, 1, &TaskStatus::Done).unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_tasks_skip_completed_statuses() {
+        let conn = setup_db();
+        let open = create_task(&conn, "Open", &[]);
+        let finished = create_task(&conn, "Finished", &[]);
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, finished.id.unwrap(), None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        let tasks = TaskRepository::get_open_tasks(&conn, 1).unwrap();
+
+        assert_eq!(titles(&tasks), ["Open"]);
+        assert_eq!(tasks[0].id, open.id);
+        assert!(TaskRepository::get_open_tasks(&conn, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_overdue_and_completion_queries() {
+        let conn = setup_db();
+        let late = create_task(&conn, "Late", &[]);
+        let early = create_task(&conn, "Early", &[]);
+        let open = create_task(&conn, "Open", &[]);
+        for task in [&late, &early] {
+            UnitOfWork::run(&conn, |uow| {
+                TaskRepository::update_task(uow, task.id.unwrap(), None, None, Some(TaskStatus::Done), None, None, None)
+            })
+            .unwrap();
+        }
+        conn.execute("UPDATE tasks SET due_date = '2025-03-10T12:00'", []).unwrap();
+        conn.execute(
+            "UPDATE tasks SET completed_at = CASE title WHEN 'Late' THEN '2025-03-11T09:00' ELSE '2025-03-09T09:00' END
+             WHERE completed_at IS NOT NULL",
+            [],
+        )
+        .unwrap();
+        let at = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+
+        let overdue = TaskRepository::get_overdue_tasks(&conn, 1, at("2025-03-10 12:01")).unwrap();
+        assert_eq!(titles(&overdue), ["Open"]);
+        assert!(overdue[0].overdue);
+        assert!(TaskRepository::get_overdue_tasks(&conn, 1, at("2025-03-10 12:00")).unwrap().is_empty());
+
+        let completed = TaskRepository::get_completed_between(&conn, 1, at("2025-03-09 00:00"), at("2025-03-10 00:00")).unwrap();
+        assert_eq!(titles(&completed), ["Early"]);
+        assert_eq!(titles(&TaskRepository::get_completed_late(&conn, 1).unwrap()), ["Late"]);
+        assert_eq!(open.completed_at, None);
+    }
+
+    #[test]
+    fn test_optional_due_and_start_dates() {
+        let conn = setup_db();
+        let undated = create_task(&conn, "Undated", &[]);
+        let mut later = create_task(&conn, "Later", &[]);
+        let mut sooner = create_task(&conn, "Sooner", &[]);
+        let at = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+
+        later.due_date = Some(at("2025-03-12 09:00"));
+        later.start_date = Some(at("2025-03-11 08:00"));
+        TaskRepository::set_dates(&conn, &later).unwrap();
+        sooner.set_due_day(at("2025-03-10 00:00").date(), |local| local);
+        TaskRepository::set_dates(&conn, &sooner).unwrap();
+
+        let open = TaskRepository::get_open_tasks(&conn, 1).unwrap();
+        assert_eq!(titles(&open), ["Sooner", "Later", "Undated"]);
+        assert_eq!((open[0].all_day, open[0].due_date), (true, Some(at("2025-03-10 23:59"))));
+        assert_eq!(open[2].due_date, None);
+        assert!(!open[2].overdue);
+
+        let available = TaskRepository::get_available_tasks(&conn, 1, at("2025-03-10 12:00")).unwrap();
+        assert_eq!(titles(&available), ["Sooner", "Undated"]);
+
+        later.start_date = Some(at("2025-03-13 08:00"));
+        assert!(matches!(TaskRepository::set_dates(&conn, &later), Err(TaskError::InvalidDate(_))));
+        let mut all_day = undated.clone();
+        all_day.all_day = true;
+        assert!(matches!(TaskRepository::set_dates(&conn, &all_day), Err(TaskError::InvalidDate(_))));
+    }
+}
@@ -0,0 +1,311 @@
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::TimeLogError;
+use crate::models::{
+    weekly_effort, EffortReport, EstimationAccuracy, TagEffort, TaskEffort, TimeLog, WeekEffort,
+};
+use crate::utils::{truncate_to_minute, SqliteDateTime};
+
+const LOG_COLUMNS: &str = "id, task_id, started_at, ended_at, minutes, note";
+
+fn format(dt: NaiveDateTime) -> String {
+    truncate_to_minute(dt).format("%Y-%m-%dT%H:%M").to_string()
+}
+
+/// Actual effort on tasks, from manual entries and start/stop timers.
+pub struct TimeLogRepository;
+
+impl TimeLogRepository {
+    pub fn start_timer(conn: &Connection, task_id: u32, now: NaiveDateTime) -> Result<TimeLog, TimeLogError> {
+        TimeLogRepository::ensure_task(conn, task_id)?;
+        if TimeLogRepository::running_timer(conn, task_id)?.is_some() {
+            return Err(TimeLogError::TimerRunning(task_id));
+        }
+
+        conn.execute(
+            "INSERT INTO time_logs (task_id, started_at) VALUES (?1, ?2)",
+            params![task_id, format(now)],
+        )?;
+        TimeLogRepository::find(conn, conn.last_insert_rowid() as u32)
+    }
+
+    pub fn stop_timer(conn: &Connection, task_id: u32, now: NaiveDateTime) -> Result<TimeLog, TimeLogError> {
+        let running = TimeLogRepository::running_timer(conn, task_id)?
+            .ok_or(TimeLogError::NoTimerRunning(task_id))?;
+
+        let ended_at = truncate_to_minute(now).max(running.started_at);
+        let minutes = (ended_at - running.started_at).num_minutes() as u32;
+        conn.execute(
+            "UPDATE time_logs SET ended_at = ?1, minutes = ?2 WHERE id = ?3",
+            params![format(ended_at), minutes, running.id],
+        )?;
+        TimeLogRepository::find(conn, running.id.unwrap_or_default())
+    }
+
+    pub fn running_timer(conn: &Connection, task_id: u32) -> Result<Option<TimeLog>, TimeLogError> {
+        Ok(conn
+            .query_row(
+                &format!("SELECT {} FROM time_logs WHERE task_id = ?1 AND ended_at IS NULL", LOG_COLUMNS),
+                params![task_id],
+                |row| TimeLog::try_from(row),
+            )
+            .optional()?)
+    }
+
+    /// Records time spent without a timer.
+    pub fn log_time(
+        conn: &Connection,
+        task_id: u32,
+        started_at: NaiveDateTime,
+        minutes: u32,
+        note: Option<String>,
+    ) -> Result<TimeLog, TimeLogError> {
+        if minutes == 0 {
+            return Err(TimeLogError::InvalidDuration("Logged time must be at least one minute".to_owned()));
+        }
+        TimeLogRepository::ensure_task(conn, task_id)?;
+
+        let ended_at = started_at + Duration::minutes(minutes as i64);
+        conn.execute(
+            "INSERT INTO time_logs (task_id, started_at, ended_at, minutes, note) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![task_id, format(started_at), format(ended_at), minutes, note],
+        )?;
+        TimeLogRepository::find(conn, conn.last_insert_rowid() as u32)
+    }
+
+    pub fn delete(conn: &Connection, log_id: u32) -> Result<bool, TimeLogError> {
+        Ok(conn.execute("DELETE FROM time_logs WHERE id = ?1", params![log_id])? > 0)
+    }
+
+    pub fn list_for_task(conn: &Connection, task_id: u32) -> Result<Vec<TimeLog>, TimeLogError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM time_logs WHERE task_id = ?1 ORDER BY started_at, id",
+            LOG_COLUMNS
+        ))?;
+        let logs = stmt
+            .query_map(params![task_id], |row| TimeLog::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(logs)
+    }
+
+    /// Estimate against finished logged time for each of the user's tasks.
+    pub fn task_effort(conn: &Connection, user_id: u32) -> Result<Vec<TaskEffort>, TimeLogError> {
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.title, t.estimated_minutes,
+                COALESCE((SELECT SUM(l.minutes) FROM time_logs l WHERE l.task_id = t.id), 0),
+                COALESCE(ws.category = 'completed', 0)
+             FROM tasks t
+             LEFT JOIN workflow_statuses ws ON ws.user_id = t.user_id AND ws.key = t.status
+             WHERE t.user_id = ?1
+             ORDER BY t.id",
+        )?;
+        let efforts = stmt
+            .query_map(params![user_id], |row| {
+                Ok(TaskEffort {
+                    task_id: row.get(0)?,
+                    title: row.get(1)?,
+                    estimated_minutes: row.get(2)?,
+                    actual_minutes: row.get(3)?,
+                    completed: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(efforts)
+    }
+
+    /// Estimates and logged time summed over the tasks carrying each tag.
+    pub fn tag_effort(conn: &Connection, user_id: u32) -> Result<Vec<TagEffort>, TimeLogError> {
+        let mut stmt = conn.prepare(
+            "SELECT g.id, g.tag_name,
+                COALESCE(SUM(t.estimated_minutes), 0),
+                COALESCE(SUM((SELECT SUM(l.minutes) FROM time_logs l WHERE l.task_id = t.id)), 0)
+             FROM tags g
+             JOIN task_tags tt ON tt.tag_id = g.id
+             JOIN tasks t ON t.id = tt.task_id
+             WHERE t.user_id = ?1
+             GROUP BY g.id
+             ORDER BY g.id",
+        )?;
+        let efforts = stmt
+            .query_map(params![user_id], |row| {
+                Ok(TagEffort {
+                    tag_id: row.get(0)?,
+                    tag_name: row.get(1)?,
+                    estimated_minutes: row.get(2)?,
+                    actual_minutes: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(efforts)
+    }
+
+    pub fn weekly_effort(conn: &Connection, user_id: u32) -> Result<Vec<WeekEffort>, TimeLogError> {
+        let mut stmt = conn.prepare(
+            "SELECT l.started_at, l.minutes FROM time_logs l
+             JOIN tasks t ON t.id = l.task_id
+             WHERE t.user_id = ?1 AND l.minutes IS NOT NULL",
+        )?;
+        let logged = stmt
+            .query_map(params![user_id], |row| {
+                Ok((row.get::<_, SqliteDateTime>(0)?.into(), row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT due_date, estimated_minutes FROM tasks
+             WHERE user_id = ?1 AND estimated_minutes IS NOT NULL AND due_date IS NOT NULL",
+        )?;
+        let estimated = stmt
+            .query_map(params![user_id], |row| {
+                Ok((row.get::<_, SqliteDateTime>(0)?.into(), row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(weekly_effort(&logged, &estimated))
+    }
+
+    pub fn report(conn: &Connection, user_id: u32) -> Result<EffortReport, TimeLogError> {
+        let tasks = TimeLogRepository::task_effort(conn, user_id)?;
+        Ok(EffortReport {
+            accuracy: EstimationAccuracy::from_tasks(&tasks),
+            tags: TimeLogRepository::tag_effort(conn, user_id)?,
+            weeks: TimeLogRepository::weekly_effort(conn, user_id)?,
+            tasks,
+        })
+    }
+
+    fn find(conn: &Connection, log_id: u32) -> Result<TimeLog, TimeLogError> {
+        Ok(conn.query_row(
+            &format!("SELECT {} FROM time_logs WHERE id = ?1", LOG_COLUMNS),
+            params![log_id],
+            |row| TimeLog::try_from(row),
+        )?)
+    }
+
+    fn ensure_task(conn: &Connection, task_id: u32) -> Result<(), TimeLogError> {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1)",
+            params![task_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(TimeLogError::TaskNotFound(task_id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{task_status::TaskStatus, Tag, Task};
+    use crate::repository::{TaskRepository, UnitOfWork};
+    use crate::utils::initialize_database;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create_task(conn: &Connection, title: &str, estimate: Option<u32>, tags: &[&str]) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.due_date = Some(at("2025-03-12 09:00"));
+        task.estimated_minutes = estimate;
+        let tags: Vec<Tag> = tags
+            .iter()
+            .map(|name| Tag::new(name.to_string(), "red".to_string()).unwrap())
+            .collect();
+        UnitOfWork::run(conn, |uow| TaskRepository::insert_task_with_tags(uow, &mut task, &tags))
+            .unwrap();
+        task.id.unwrap()
+    }
+
+    #[test]
+    fn test_timer_accumulates_minutes() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", Some(60), &[]);
+
+        let running = TimeLogRepository::start_timer(&conn, task_id, at("2025-03-10 09:00")).unwrap();
+        assert!(running.is_running());
+        assert!(matches!(
+            TimeLogRepository::start_timer(&conn, task_id, at("2025-03-10 09:05")),
+            Err(TimeLogError::TimerRunning(_))
+        ));
+
+        let stopped = TimeLogRepository::stop_timer(&conn, task_id, at("2025-03-10 09:50")).unwrap();
+        assert_eq!(stopped.minutes, Some(50));
+        assert!(matches!(
+            TimeLogRepository::stop_timer(&conn, task_id, at("2025-03-10 10:00")),
+            Err(TimeLogError::NoTimerRunning(_))
+        ));
+    }
+
+    #[test]
+    fn test_manual_logs_are_validated() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", None, &[]);
+
+        assert!(matches!(
+            TimeLogRepository::log_time(&conn, task_id, at("2025-03-10 09:00"), 0, None),
+            Err(TimeLogError::InvalidDuration(_))
+        ));
+        assert!(matches!(
+            TimeLogRepository::log_time(&conn, 99, at("2025-03-10 09:00"), 30, None),
+            Err(TimeLogError::TaskNotFound(99))
+        ));
+
+        let log = TimeLogRepository::log_time(&conn, task_id, at("2025-03-10 09:00"), 90, Some("Library".to_string()))
+            .unwrap();
+        assert_eq!(log.ended_at, Some(at("2025-03-10 10:30")));
+        assert_eq!(TimeLogRepository::list_for_task(&conn, task_id).unwrap(), [log]);
+    }
+
+    #[test]
+    fn test_report_per_task_tag_and_week() {
+        let conn = setup_db();
+        let essay = create_task(&conn, "Essay", Some(60), &["Writing"]);
+        let notes = create_task(&conn, "Notes", Some(30), &["Writing", "Math"]);
+        TimeLogRepository::log_time(&conn, essay, at("2025-03-10 09:00"), 90, None).unwrap();
+        TimeLogRepository::log_time(&conn, notes, at("2025-03-11 09:00"), 30, None).unwrap();
+        TimeLogRepository::start_timer(&conn, notes, at("2025-03-11 12:00")).unwrap();
+        UnitOfWork::run(&conn, |uow| {
+            TaskRepository::update_task(uow, essay, None, None, Some(TaskStatus::Done), None, None, None)
+        })
+        .unwrap();
+
+        let report = TimeLogRepository::report(&conn, 1).unwrap();
+
+        let actual: Vec<_> = report.tasks.iter().map(|t| (t.actual_minutes, t.completed)).collect();
+        assert_eq!(actual, [(90, true), (30, false)]);
+        let tags: Vec<_> = report
+            .tags
+            .iter()
+            .map(|t| (t.tag_name.as_str(), t.estimated_minutes, t.actual_minutes))
+            .collect();
+        assert_eq!(tags, [("Writing", 90, 120), ("Math", 30, 30)]);
+        assert_eq!(report.weeks.len(), 1);
+        assert_eq!((report.weeks[0].estimated_minutes, report.weeks[0].actual_minutes), (90, 120));
+        assert_eq!(report.accuracy.tasks_compared, 1);
+        assert_eq!(report.accuracy.bias, Some(1.5));
+    }
+
+    #[test]
+    fn test_deleting_a_task_removes_its_logs() {
+        let conn = setup_db();
+        let task_id = create_task(&conn, "Essay", None, &[]);
+        TimeLogRepository::log_time(&conn, task_id, at("2025-03-10 09:00"), 30, None).unwrap();
+
+        UnitOfWork::run(&conn, |uow| TaskRepository::delete_task(uow, task_id)).unwrap();
+
+        let count: u32 = conn.query_row("SELECT COUNT(*) FROM time_logs", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,355 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::TimetableError;
+use crate::models::{
+    class_dates, week_start, ClassSlot, ScheduledClass, Task, Term, TermException, TimetableDay,
+};
+use crate::repository::{TaskRepository, UnitOfWork, WorkflowStatusRepository};
+
+const TERM_COLUMNS: &str = "id, user_id, name, start_date, end_date";
+const CLASS_COLUMNS: &str = "id, term_id, subject_id, weekday, start_time, end_time, room";
+const EXCEPTION_COLUMNS: &str = "id, term_id, class_id, start_date, end_date, description";
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Terms, their weekly timetable and the days without classes.
+pub struct TimetableRepository;
+
+impl TimetableRepository {
+    pub fn create_term(conn: &Connection, term: &mut Term) -> Result<(), TimetableError> {
+        conn.execute(
+            "INSERT INTO terms (user_id, name, start_date, end_date) VALUES (?1, ?2, ?3, ?4)",
+            params![term.user_id, term.name, format_date(term.start_date), format_date(term.end_date)],
+        )?;
+        term.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn find_term(conn: &Connection, term_id: u32) -> Result<Term, TimetableError> {
+        conn.query_row(
+            &format!("SELECT {} FROM terms WHERE id = ?1", TERM_COLUMNS),
+            params![term_id],
+            |row| Term::try_from(row),
+        )
+        .optional()?
+        .ok_or(TimetableError::TermNotFound(term_id))
+    }
+
+    pub fn list_terms(conn: &Connection, user_id: u32) -> Result<Vec<Term>, TimetableError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM terms WHERE user_id = ?1 ORDER BY start_date, id",
+            TERM_COLUMNS
+        ))?;
+        let terms = stmt
+            .query_map(params![user_id], |row| Term::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(terms)
+    }
+
+    /// Deletes the term with its timetable and exceptions.
+    pub fn delete_term(conn: &Connection, term_id: u32) -> Result<bool, TimetableError> {
+        Ok(conn.execute("DELETE FROM terms WHERE id = ?1", params![term_id])? > 0)
+    }
+
+    pub fn add_class(conn: &Connection, class: &mut ClassSlot) -> Result<(), TimetableError> {
+        let term = TimetableRepository::find_term(conn, class.term_id)?;
+        let owns_subject: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM subjects WHERE id = ?1 AND user_id = ?2)",
+            params![class.subject_id, term.user_id],
+            |row| row.get(0),
+        )?;
+        if !owns_subject {
+            return Err(TimetableError::SubjectNotFound(class.subject_id));
+        }
+
+        conn.execute(
+            "INSERT INTO class_slots (term_id, subject_id, weekday, start_time, end_time, room)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                class.term_id,
+                class.subject_id,
+                class.weekday.num_days_from_monday(),
+                class.start_time.format("%H:%M").to_string(),
+                class.end_time.format("%H:%M").to_string(),
+                class.room,
+            ],
+        )?;
+        class.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn list_classes(conn: &Connection, term_id: u32) -> Result<Vec<ClassSlot>, TimetableError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM class_slots WHERE term_id = ?1 ORDER BY weekday, start_time, id",
+            CLASS_COLUMNS
+        ))?;
+        let classes = stmt
+            .query_map(params![term_id], |row| ClassSlot::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(classes)
+    }
+
+    pub fn delete_class(conn: &Connection, class_id: u32) -> Result<bool, TimetableError> {
+        Ok(conn.execute("DELETE FROM class_slots WHERE id = ?1", params![class_id])? > 0)
+    }
+
+    pub fn add_exception(conn: &Connection, exception: &mut TermException) -> Result<(), TimetableError> {
+        TimetableRepository::find_term(conn, exception.term_id)?;
+        if let Some(class_id) = exception.class_id {
+            let in_term: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM class_slots WHERE id = ?1 AND term_id = ?2)",
+                params![class_id, exception.term_id],
+                |row| row.get(0),
+            )?;
+            if !in_term {
+                return Err(TimetableError::ClassNotFound(class_id));
+            }
+        }
+
+        conn.execute(
+            "INSERT INTO term_exceptions (term_id, class_id, start_date, end_date, description)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                exception.term_id,
+                exception.class_id,
+                format_date(exception.start_date),
+                format_date(exception.end_date),
+                exception.description,
+            ],
+        )?;
+        exception.id = Some(conn.last_insert_rowid() as u32);
+        Ok(())
+    }
+
+    pub fn list_exceptions(conn: &Connection, term_id: u32) -> Result<Vec<TermException>, TimetableError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM term_exceptions WHERE term_id = ?1 ORDER BY start_date, id",
+            EXCEPTION_COLUMNS
+        ))?;
+        let exceptions = stmt
+            .query_map(params![term_id], |row| TermException::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(exceptions)
+    }
+
+    pub fn delete_exception(conn: &Connection, exception_id: u32) -> Result<bool, TimetableError> {
+        Ok(conn.execute("DELETE FROM term_exceptions WHERE id = ?1", params![exception_id])? > 0)
+    }
+
+    /// Every class meeting of the user's terms in `from..=to`, in order.
+    pub fn classes_between(
+        conn: &Connection,
+        user_id: u32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ScheduledClass>, TimetableError> {
+        let mut scheduled = Vec::new();
+        for term in TimetableRepository::list_terms(conn, user_id)? {
+            if term.end_date < from || term.start_date > to {
+                continue;
+            }
+            let term_id = term.id.unwrap_or_default();
+            let exceptions = TimetableRepository::list_exceptions(conn, term_id)?;
+
+            for class in TimetableRepository::list_classes(conn, term_id)? {
+                let (subject_name, color): (String, String) = conn.query_row(
+                    "SELECT name, color FROM subjects WHERE id = ?1",
+                    params![class.subject_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+                for date in class_dates(&term, &class, &exceptions, from, to) {
+                    scheduled.push(ScheduledClass {
+                        class_id: class.id.unwrap_or_default(),
+                        term_id,
+                        subject_id: class.subject_id,
+                        subject_name: subject_name.clone(),
+                        color: color.clone(),
+                        room: class.room.clone(),
+                        date,
+                        start_at: date.and_time(class.start_time),
+                        end_at: date.and_time(class.end_time),
+                    });
+                }
+            }
+        }
+        scheduled.sort_by_key(|class| (class.start_at, class.class_id));
+        Ok(scheduled)
+    }
+
+    /// The seven days (Monday first) of the week containing `date`.
+    pub fn week_grid(conn: &Connection, user_id: u32, date: NaiveDate) -> Result<Vec<TimetableDay>, TimetableError> {
+        let start = week_start(date);
+        let mut classes = TimetableRepository::classes_between(conn, user_id, start, start + Duration::days(6))?;
+
+        Ok((0..7)
+            .map(|offset| {
+                let date = start + Duration::days(offset);
+                let (today, rest) = classes.drain(..).partition(|class| class.date == date);
+                classes = rest;
+                TimetableDay { date, classes: today }
+            })
+            .collect())
+    }
+
+    /// Creates a "Prepare for <subject>" task for every class meeting in
+    /// `from..=to` that does not have one yet, due `lead` before the class.
+    /// Class times are local; `to_utc` converts them to stored due dates.
+    pub fn generate_prep_tasks(
+        uow: &UnitOfWork,
+        user_id: u32,
+        from: NaiveDate,
+        to: NaiveDate,
+        lead: Duration,
+        to_utc: impl Fn(NaiveDateTime) -> NaiveDateTime,
+    ) -> Result<Vec<Task>, TimetableError> {
+        let status = WorkflowStatusRepository::initial_status(uow, user_id)
+            .map_err(|e| TimetableError::DatabaseError(e.to_string()))?;
+        let mut created = Vec::new();
+
+        for class in TimetableRepository::classes_between(uow, user_id, from, to)? {
+            let exists: bool = uow.query_row(
+                "SELECT EXISTS(SELECT 1 FROM class_prep_tasks WHERE class_id = ?1 AND date = ?2)",
+                params![class.class_id, format_date(class.date)],
+                |row| row.get(0),
+            )?;
+            if exists {
+                continue;
+            }
+
+            let description = match &class.room {
+                Some(room) => format!("Class on {} in {}", class.start_at.format("%Y-%m-%d %H:%M"), room),
+                None => format!("Class on {}", class.start_at.format("%Y-%m-%d %H:%M")),
+            };
+            let mut task = Task::new(format!("Prepare for {}", class.subject_name), user_id, Some(description), None)
+                .map_err(|e| TimetableError::InvalidName(e.to_string()))?;
+            task.status = status.clone();
+            task.subject_id = Some(class.subject_id);
+            task.due_date = Some(to_utc(class.start_at - lead));
+            TaskRepository::insert_task(uow, &mut task).map_err(|e| TimetableError::DatabaseError(e.to_string()))?;
+
+            uow.execute(
+                "INSERT INTO class_prep_tasks (class_id, date, task_id) VALUES (?1, ?2, ?3)",
+                params![class.class_id, format_date(class.date), task.id],
+            )?;
+            created.push(task);
+        }
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Subject;
+    use crate::repository::SubjectRepository;
+    use crate::utils::initialize_database;
+    use chrono::{NaiveTime, Weekday};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    /// A March term with Physics on Monday and Wednesday mornings and a
+    /// holiday on 2025-03-05.
+    fn setup_timetable(conn: &Connection) -> (u32, u32, u32) {
+        let mut physics = Subject::new(1, "Physics".to_string(), "blue".to_string()).unwrap();
+        SubjectRepository::create(conn, &mut physics).unwrap();
+        let subject_id = physics.id.unwrap();
+
+        let mut term = Term::new(1, "2025/1".to_string(), date("2025-03-03"), date("2025-03-31")).unwrap();
+        TimetableRepository::create_term(conn, &mut term).unwrap();
+        let term_id = term.id.unwrap();
+
+        let mut ids = Vec::new();
+        for (weekday, start, end) in [(Weekday::Mon, "08:00", "09:40"), (Weekday::Wed, "10:00", "11:40")] {
+            let mut class = ClassSlot::new(term_id, subject_id, weekday, time(start), time(end), Some("B12".to_string())).unwrap();
+            TimetableRepository::add_class(conn, &mut class).unwrap();
+            ids.push(class.id.unwrap());
+        }
+
+        let mut holiday = TermException::new(term_id, None, date("2025-03-05"), date("2025-03-05"), "Ash Wednesday".to_string()).unwrap();
+        TimetableRepository::add_exception(conn, &mut holiday).unwrap();
+        (term_id, ids[0], ids[1])
+    }
+
+    #[test]
+    fn test_add_class_requires_own_subject() {
+        let conn = setup_db();
+        let (term_id, _, _) = setup_timetable(&conn);
+
+        let mut class = ClassSlot::new(term_id, 99, Weekday::Fri, time("08:00"), time("09:00"), None).unwrap();
+        assert!(matches!(
+            TimetableRepository::add_class(&conn, &mut class),
+            Err(TimetableError::SubjectNotFound(99))
+        ));
+
+        let mut exception = TermException::new(term_id, Some(99), date("2025-03-10"), date("2025-03-10"), "x".to_string()).unwrap();
+        assert!(matches!(
+            TimetableRepository::add_exception(&conn, &mut exception),
+            Err(TimetableError::ClassNotFound(99))
+        ));
+    }
+
+    #[test]
+    fn test_classes_on_a_day() {
+        let conn = setup_db();
+        let (_, monday, _) = setup_timetable(&conn);
+
+        let classes = TimetableRepository::classes_between(&conn, 1, date("2025-03-10"), date("2025-03-10")).unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].class_id, monday);
+        assert_eq!(classes[0].subject_name, "Physics");
+        assert_eq!(classes[0].room.as_deref(), Some("B12"));
+
+        assert!(TimetableRepository::classes_between(&conn, 1, date("2025-04-07"), date("2025-04-07")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_week_grid_skips_holidays() {
+        let conn = setup_db();
+        setup_timetable(&conn);
+
+        let grid = TimetableRepository::week_grid(&conn, 1, date("2025-03-06")).unwrap();
+        let counts: Vec<usize> = grid.iter().map(|day| day.classes.len()).collect();
+
+        assert_eq!(grid[0].date, date("2025-03-03"));
+        assert_eq!(counts, [1, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_generate_prep_tasks_once_per_meeting() {
+        let conn = setup_db();
+        setup_timetable(&conn);
+        let generate = |from: &str, to: &str| {
+            UnitOfWork::run(&conn, |uow| {
+                TimetableRepository::generate_prep_tasks(uow, 1, date(from), date(to), Duration::hours(12), |dt| dt)
+            })
+            .unwrap()
+        };
+
+        let created = generate("2025-03-03", "2025-03-09");
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].title, "Prepare for Physics");
+        assert_eq!(created[0].due_date, Some(date("2025-03-02").and_hms_opt(20, 0, 0).unwrap()));
+        assert!(created[0].subject_id.is_some());
+
+        let created = generate("2025-03-03", "2025-03-12");
+        assert_eq!(created.len(), 2);
+        assert!(generate("2025-03-03", "2025-03-12").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::NaiveDateTime;
+
+use crate::errors::{TagError, TaskError, UserError};
+use crate::models::{
+    task_priority::TaskPriority,
+    task_status::TaskStatus,
+    Tag, Task, User,
+};
+
+/// Storage for users, implemented by [`SqliteStore`](crate::repository::SqliteStore)
+/// and [`InMemoryStore`](crate::repository::InMemoryStore).
+pub trait UserStore {
+    fn create_user(&mut self, user: &mut User) -> Result<(), UserError>;
+    fn count_active_users(&self) -> Result<u32, UserError>;
+    fn find_active_user_id(&self) -> Result<Option<u32>, UserError>;
+}
+
+/// Storage for tags and their links to tasks.
+pub trait TagStore {
+    fn create_tag(&mut self, tag: &mut Tag) -> Result<(), TagError>;
+    fn rename_tag(&mut self, id: u32, tag_name: String) -> Result<(), TagError>;
+    fn find_tag(&self, id: u32) -> Result<Option<Tag>, TagError>;
+    fn list_tags(&self) -> Result<Vec<Tag>, TagError>;
+    /// Removes the tag and every link to it; returns whether the tag existed.
+    fn delete_tag(&mut self, id: u32) -> Result<bool, TagError>;
+    fn get_task_tags(&self, task_id: u32) -> Result<Vec<Tag>, TagError>;
+    /// Replaces the task's tags, matching by name and creating missing ones.
+    fn set_task_tags(&mut self, task_id: u32, tags: &[Tag]) -> Result<(), TagError>;
+}
+
+/// Storage for tasks. Writes that touch tags are atomic.
+pub trait TaskStore {
+    fn insert_task(&mut self, task: &mut Task, tags: &[Tag]) -> Result<(), TaskError>;
+    fn get_task(&self, task_id: u32) -> Result<Task, TaskError>;
+    fn list_tasks(&self) -> Result<Vec<Task>, TaskError>;
+    #[allow(clippy::too_many_arguments)]
+    fn update_task(
+        &mut self,
+        task_id: u32,
+        title: Option<String>,
+        description: Option<String>,
+        status: Option<TaskStatus>,
+        priority: Option<TaskPriority>,
+        due_date: Option<NaiveDateTime>,
+        tags: Option<Vec<Tag>>,
+    ) -> Result<(), TaskError>;
+    fn delete_task(&mut self, task_id: u32) -> Result<(), TaskError>;
+    /// The user's tasks in one board column, in rank order.
+    fn list_column(&self, user_id: u32, status: &TaskStatus) -> Result<Vec<Task>, TaskError>;
+    /// Moves a task to `index` within its owner's `status` column (the end
+    /// when `None`), checking the column's WIP limit when the task changes
+    /// column.
+    fn move_task(&mut self, task_id: u32, status: TaskStatus, index: Option<usize>) -> Result<Task, TaskError>;
+    fn wip_limit(&self, user_id: u32, status: &TaskStatus) -> Result<Option<u32>, TaskError>;
+    fn set_wip_limit(&mut self, user_id: u32, status: &TaskStatus, limit: Option<u32>) -> Result<(), TaskError>;
+    /// Tasks whose due date falls within `start..=end`, ordered by due date.
+    fn get_tasks_due_between(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Task>, TaskError>;
+}
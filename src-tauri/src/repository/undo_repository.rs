@@ -0,0 +1,276 @@
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::TaskError;
+use crate::models::Task;
+use crate::repository::{TaskRepository, UnitOfWork};
+use crate::utils::format_date::truncate_to_minute;
+
+/// Tables whose rows cascade with a deleted task, and the column pointing at it.
+const DEPENDENT_TABLES: &[(&str, &str)] = &[
+    ("time_logs", "task_id"),
+    ("reminders", "task_id"),
+    ("class_prep_tasks", "task_id"),
+    ("study_plan_blocks", "task_id"),
+    ("note_links", "target_task_id"),
+    ("attachments", "task_id"),
+    ("reading_tasks", "task_id"),
+];
+
+/// How many entries each user keeps; older ones are dropped as new ones are recorded.
+pub const MAX_UNDO_ENTRIES: u32 = 20;
+
+/// Outcome of reverting one undo entry.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UndoResult {
+    pub undo_id: u32,
+    pub label: String,
+    pub restored: u32,
+    /// Tasks left alone because they were edited after the operation.
+    pub skipped: Vec<u32>,
+}
+
+/// Rows of one table that referenced tasks about to be deleted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DependentRows {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// Undo log for operations that change many tasks at once. Each entry keeps
+/// a JSON snapshot of the affected tasks as they were before the operation,
+/// and belongs to the user who ran it.
+pub struct UndoRepository;
+
+impl UndoRepository {
+    pub fn record(conn: &Connection, user_id: u32, label: &str, snapshot: &[Task]) -> Result<u32, TaskError> {
+        UndoRepository::record_delete(conn, user_id, label, snapshot, &[])
+    }
+
+    /// Records deleted tasks together with the rows that cascaded with them,
+    /// as captured by [`capture_dependents`](Self::capture_dependents) before
+    /// the delete. The blobs behind captured attachments keep a reference
+    /// until the entry is undone or pruned, so garbage collection leaves
+    /// them alone in the meantime. Entries beyond [`MAX_UNDO_ENTRIES`] are
+    /// pruned, after which callers should collect garbage.
+    pub fn record_delete(
+        conn: &Connection,
+        user_id: u32,
+        label: &str,
+        snapshot: &[Task],
+        dependents: &[DependentRows],
+    ) -> Result<u32, TaskError> {
+        let snapshot_ids: Vec<u32> = snapshot.iter().filter_map(|task| task.id).collect();
+        let snapshot = serde_json::to_string(snapshot)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+        let encoded = serde_json::to_string(dependents)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+        let created_at = truncate_to_minute(Utc::now().naive_utc());
+
+        let mut outcome = Vec::new();
+        for task_id in snapshot_ids.iter() {
+            outcome.extend(current_state(conn, *task_id)?);
+        }
+        let outcome = serde_json::to_string(&outcome)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+
+        conn.execute(
+            "INSERT INTO undo_entries (user_id, label, snapshot, dependents, created_at, outcome)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![user_id, label, snapshot, encoded, created_at.format("%Y-%m-%dT%H:%M").to_string(), outcome],
+        )?;
+        let undo_id = conn.last_insert_rowid() as u32;
+
+        UndoRepository::pin_blobs(conn, dependents, 1)?;
+        UndoRepository::prune(conn, user_id)?;
+        Ok(undo_id)
+    }
+
+    /// Drops the user's entries beyond the newest [`MAX_UNDO_ENTRIES`] and
+    /// releases the blobs they kept referenced.
+    fn prune(conn: &Connection, user_id: u32) -> Result<(), TaskError> {
+        let mut stmt = conn.prepare(
+            "SELECT id, dependents FROM undo_entries WHERE user_id = ?1
+             ORDER BY id DESC LIMIT -1 OFFSET ?2",
+        )?;
+        let expired = stmt
+            .query_map(params![user_id, MAX_UNDO_ENTRIES], |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (undo_id, dependents) in expired {
+            let dependents: Vec<DependentRows> = serde_json::from_str(&dependents)
+                .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+            UndoRepository::pin_blobs(conn, &dependents, -1)?;
+            conn.execute("DELETE FROM undo_entries WHERE id = ?1", params![undo_id])?;
+        }
+        Ok(())
+    }
+
+    /// Copies every row that would cascade with `task_ids`.
+    pub fn capture_dependents(conn: &Connection, task_ids: &[u32]) -> Result<Vec<DependentRows>, TaskError> {
+        let mut captured = Vec::new();
+
+        for (table, column) in DEPENDENT_TABLES {
+            let mut stmt = conn.prepare(&format!("SELECT * FROM {} WHERE {} = ?1", table, column))?;
+            let mut dependents = DependentRows {
+                table: table.to_string(),
+                columns: stmt.column_names().into_iter().map(str::to_owned).collect(),
+                rows: Vec::new(),
+            };
+
+            for task_id in task_ids {
+                let mut rows = stmt.query(params![task_id])?;
+                while let Some(row) = rows.next()? {
+                    let values = (0..dependents.columns.len())
+                        .map(|i| row.get::<_, Value>(i).map(to_json))
+                        .collect::<Result<_, _>>()?;
+                    dependents.rows.push(values);
+                }
+            }
+
+            if !dependents.rows.is_empty() {
+                captured.push(dependents);
+            }
+        }
+
+        Ok(captured)
+    }
+
+    /// Restores the tasks captured by the user's most recent entry, and the
+    /// rows deleted with them, and removes it. Tasks that no longer match the
+    /// state the operation left them in keep their current state and are reported as skipped,
+    /// along with their dependent rows. Rows whose other parent has since
+    /// been deleted are not brought back.
+    pub fn undo_last(uow: &UnitOfWork, user_id: u32) -> Result<Option<UndoResult>, TaskError> {
+        let entry: Option<(u32, String, String, String, String, Option<String>)> = uow
+            .query_row(
+                "SELECT id, label, snapshot, dependents, created_at, outcome FROM undo_entries
+                 WHERE user_id = ?1 ORDER BY id DESC LIMIT 1",
+                params![user_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+            )
+            .optional()?;
+
+        let Some((undo_id, label, snapshot, dependents, created_at, outcome)) = entry else {
+            return Ok(None);
+        };
+
+        let tasks: Vec<Task> = serde_json::from_str(&snapshot)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+        let dependents: Vec<DependentRows> = serde_json::from_str(&dependents)
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+        let outcome: Option<Vec<serde_json::Value>> = outcome
+            .map(|outcome| serde_json::from_str(&outcome))
+            .transpose()
+            .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+
+        let mut restored = 0;
+        let mut skipped = Vec::new();
+        for task in &tasks {
+            let Some(task_id) = task.id else { continue };
+            let edited = match &outcome {
+                Some(outcome) => current_state(uow, task_id)?.is_some_and(|current| {
+                    outcome.iter().find(|left| left["id"] == task_id).is_none_or(|left| *left != current)
+                }),
+                // Entries recorded before outcomes were kept only know the minute they were made.
+                None => uow
+                    .query_row("SELECT updated_at FROM tasks WHERE id = ?1", params![task_id], |row| row.get::<_, String>(0))
+                    .optional()?
+                    .is_some_and(|updated_at| updated_at > created_at),
+            };
+            if edited {
+                skipped.push(task_id);
+                continue;
+            }
+            TaskRepository::restore_task(uow, task)?;
+            restored += 1;
+        }
+
+        for table in &dependents {
+            let task_column = DEPENDENT_TABLES
+                .iter()
+                .find(|(name, _)| *name == table.table)
+                .and_then(|(_, column)| table.columns.iter().position(|c| c == column));
+            let sql = format!(
+                "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+                table.table,
+                table.columns.join(", "),
+                vec!["?"; table.columns.len()].join(", ")
+            );
+            let mut stmt = uow.prepare(&sql)?;
+            for row in &table.rows {
+                let task_id = task_column.and_then(|i| row[i].as_u64());
+                if task_id.is_some_and(|task_id| skipped.iter().any(|id| u64::from(*id) == task_id)) {
+                    continue;
+                }
+                // A row whose other parent (a note, a plan topic, a book) was deleted in the meantime stays deleted.
+                match stmt.execute(params_from_iter(row.iter().map(from_json))) {
+                    Err(rusqlite::Error::SqliteFailure(e, _))
+                        if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY => {}
+                    result => {
+                        result?;
+                    }
+                }
+            }
+        }
+        UndoRepository::pin_blobs(uow, &dependents, -1)?;
+
+        uow.execute("DELETE FROM undo_entries WHERE id = ?1", params![undo_id])?;
+
+        Ok(Some(UndoResult {
+            undo_id,
+            label,
+            restored,
+            skipped,
+        }))
+    }
+
+    /// Adds `delta` to the reference count of each captured attachment's blob.
+    fn pin_blobs(conn: &Connection, dependents: &[DependentRows], delta: i64) -> Result<(), TaskError> {
+        for table in dependents.iter().filter(|table| table.table == "attachments") {
+            let Some(hash) = table.columns.iter().position(|column| column == "hash") else {
+                continue;
+            };
+            for row in &table.rows {
+                conn.execute(
+                    "UPDATE attachment_blobs SET ref_count = ref_count + ?1 WHERE hash = ?2",
+                    params![delta, from_json(&row[hash])],
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The task as stored now, in the form kept in an entry's outcome.
+fn current_state(conn: &Connection, task_id: u32) -> Result<Option<serde_json::Value>, TaskError> {
+    let exists: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1)", params![task_id], |row| row.get(0))?;
+    if !exists {
+        return Ok(None);
+    }
+    let mut task = TaskRepository::get_task_by_id(conn, task_id)?;
+    // Derived from the clock, not part of what the operation changed.
+    task.overdue = false;
+    serde_json::to_value(task).map(Some).map_err(|e| TaskError::DatabaseError(e.to_string()))
+}
+
+fn to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null | Value::Blob(_) => serde_json::Value::Null,
+        Value::Integer(i) => i.into(),
+        Value::Real(f) => f.into(),
+        Value::Text(s) => s.into(),
+    }
+}
+
+fn from_json(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Number(n) => n.as_i64().map(Value::Integer).unwrap_or_else(|| Value::Real(n.as_f64().unwrap_or_default())),
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        _ => Value::Null,
+    }
+}
@@ -0,0 +1,222 @@
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+use std::ops::Deref;
+
+/// A single SQLite transaction shared by every repository call that spans
+/// more than one table. Dereferences to [`Connection`] so single-table
+/// helpers can run inside it unchanged.
+///
+/// Beginning a unit of work on a connection that already has an open
+/// transaction nests a savepoint in it: committing releases the savepoint
+/// into the outer transaction, rolling back undoes only the inner writes.
+pub struct UnitOfWork<'conn> {
+    scope: Scope<'conn>,
+}
+
+enum Scope<'conn> {
+    Owned(Transaction<'conn>),
+    Joined(Savepoint<'conn>),
+}
+
+/// Name of the savepoints opened by nested units of work. SQLite resolves
+/// `RELEASE` and `ROLLBACK TO` to the innermost savepoint of that name.
+const SAVEPOINT: &str = "unit_of_work";
+
+/// A savepoint inside an outer transaction, rolled back when dropped
+/// unfinished.
+struct Savepoint<'conn> {
+    conn: &'conn Connection,
+    finished: bool,
+}
+
+impl<'conn> Savepoint<'conn> {
+    fn new(conn: &'conn Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(&format!("SAVEPOINT {}", SAVEPOINT))?;
+        Ok(Self { conn, finished: false })
+    }
+
+    fn release(mut self) -> rusqlite::Result<()> {
+        self.finished = true;
+        self.conn.execute_batch(&format!("RELEASE {}", SAVEPOINT))
+    }
+
+    fn rollback(mut self) -> rusqlite::Result<()> {
+        self.finished = true;
+        self.conn.execute_batch(&format!("ROLLBACK TO {0}; RELEASE {0}", SAVEPOINT))
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.conn.execute_batch(&format!("ROLLBACK TO {0}; RELEASE {0}", SAVEPOINT));
+        }
+    }
+}
+
+impl<'conn> UnitOfWork<'conn> {
+    pub fn begin(conn: &'conn Connection) -> rusqlite::Result<Self> {
+        let scope = if conn.is_autocommit() {
+            Scope::Owned(Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?)
+        } else {
+            Scope::Joined(Savepoint::new(conn)?)
+        };
+        Ok(Self { scope })
+    }
+
+    pub fn commit(self) -> rusqlite::Result<()> {
+        match self.scope {
+            Scope::Owned(tx) => tx.commit(),
+            Scope::Joined(savepoint) => savepoint.release(),
+        }
+    }
+
+    pub fn rollback(self) -> rusqlite::Result<()> {
+        match self.scope {
+            Scope::Owned(tx) => tx.rollback(),
+            Scope::Joined(savepoint) => savepoint.rollback(),
+        }
+    }
+
+    /// Runs `f` in a new unit of work, committing on `Ok` and rolling back on `Err`.
+    pub fn run<T, E, F>(conn: &'conn Connection, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&UnitOfWork<'conn>) -> Result<T, E>,
+        E: From<rusqlite::Error>,
+    {
+        let uow = Self::begin(conn)?;
+        match f(&uow) {
+            Ok(value) => {
+                uow.commit()?;
+                Ok(value)
+            }
+            Err(e) => {
+                uow.rollback()?;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Deref for UnitOfWork<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match &self.scope {
+            Scope::Owned(tx) => tx,
+            Scope::Joined(savepoint) => savepoint.conn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::initialize_database;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn
+    }
+
+    fn count_users(conn: &Connection) -> u32 {
+        conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0)).unwrap()
+    }
+
+    fn insert_user(conn: &Connection, name: &str) -> rusqlite::Result<usize> {
+        conn.execute(
+            "INSERT INTO users (name, status) VALUES (?1, 'active')",
+            [name],
+        )
+    }
+
+    #[test]
+    fn test_run_commits_on_ok() {
+        let conn = setup_db();
+
+        let result: Result<(), rusqlite::Error> = UnitOfWork::run(&conn, |uow| {
+            insert_user(uow, "Alice")?;
+            insert_user(uow, "Bob")?;
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(count_users(&conn), 2);
+    }
+
+    #[test]
+    fn test_run_rolls_back_on_err() {
+        let conn = setup_db();
+
+        let result: Result<(), rusqlite::Error> = UnitOfWork::run(&conn, |uow| {
+            insert_user(uow, "Alice")?;
+            uow.execute("INSERT INTO users (name, status) VALUES ('Bob', 'banned')", [])?;
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(count_users(&conn), 0);
+    }
+
+    #[test]
+    fn test_explicit_rollback() {
+        let conn = setup_db();
+
+        let uow = UnitOfWork::begin(&conn).unwrap();
+        insert_user(&uow, "Alice").unwrap();
+        uow.rollback().unwrap();
+
+        assert_eq!(count_users(&conn), 0);
+    }
+
+    #[test]
+    fn test_dropped_unit_of_work_rolls_back() {
+        let conn = setup_db();
+
+        {
+            let uow = UnitOfWork::begin(&conn).unwrap();
+            insert_user(&uow, "Alice").unwrap();
+        }
+
+        assert_eq!(count_users(&conn), 0);
+    }
+
+    #[test]
+    fn test_nested_unit_of_work_joins_outer() {
+        let conn = setup_db();
+
+        let result: Result<(), rusqlite::Error> = UnitOfWork::run(&conn, |outer| {
+            insert_user(outer, "Alice")?;
+            UnitOfWork::run(outer, |inner| insert_user(inner, "Bob").map(|_| ()))?;
+            Err(rusqlite::Error::InvalidQuery)
+        });
+
+        assert!(result.is_err());
+        assert_eq!(count_users(&conn), 0);
+    }
+
+    #[test]
+    fn test_failed_nested_unit_of_work_rolls_back_only_its_writes() {
+        let conn = setup_db();
+
+        let result: Result<(), rusqlite::Error> = UnitOfWork::run(&conn, |outer| {
+            insert_user(outer, "Alice")?;
+            let inner: Result<(), rusqlite::Error> = UnitOfWork::run(outer, |inner| {
+                insert_user(inner, "Bob")?;
+                Err(rusqlite::Error::InvalidQuery)
+            });
+            assert!(inner.is_err());
+            UnitOfWork::run(outer, |inner| insert_user(inner, "Carol").map(|_| ()))
+        });
+
+        assert!(result.is_ok());
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM users ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(names, ["Alice", "Carol"]);
+    }
+}
@@ -29,7 +29,7 @@ impl UserRepository {
 
     pub fn find_active_id(conn: &Connection) -> Result<Option<u32>, UserError> {
         let mut stmt = conn
-            .prepare("SELECT id FROM users WHERE status = 'active' LIMIT 1")
+            .prepare("SELECT id FROM users WHERE status = 'active' ORDER BY id LIMIT 1")
             .map_err(|e| UserError::DatabaseError(e.to_string()))?;
 
         let mut rows = stmt
@@ -51,18 +51,11 @@ mod tests {
     use chrono::{Utc, NaiveDateTime};
     use crate::models::UserStatus;
     use std::str::FromStr;
+    use crate::utils::initialize_database;
 
     fn setup_db() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
-        conn.execute(
-            "CREATE TABLE users (
-                id INTEGER PRIMARY KEY,
-                name TEXT NOT NULL,
-                status TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        ).unwrap();
+        initialize_database(&conn).unwrap();
         conn
     }
 
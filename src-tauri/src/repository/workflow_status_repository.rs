@@ -0,0 +1,392 @@
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::errors::WorkflowStatusError;
+use crate::models::{
+    task_status::TaskStatus,
+    validate_color, validate_name, StatusCategory, WorkflowStatus,
+};
+use crate::repository::{TaskRepository, UnitOfWork};
+use crate::utils::rank::rank_between;
+use crate::utils::truncate_to_minute;
+
+const STATUS_COLUMNS: &str = "id, user_id, key, name, category, position, color";
+
+/// Per-user status definitions. Every user starts with the four defaults
+/// (seeded by a trigger on `users`) and always keeps at least one status in
+/// each category.
+pub struct WorkflowStatusRepository;
+
+impl WorkflowStatusRepository {
+    pub fn list(conn: &Connection, user_id: u32) -> Result<Vec<WorkflowStatus>, WorkflowStatusError> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM workflow_statuses WHERE user_id = ?1 ORDER BY position, id",
+            STATUS_COLUMNS
+        ))?;
+        let statuses = stmt
+            .query_map(params![user_id], |row| WorkflowStatus::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(statuses)
+    }
+
+    pub fn find(
+        conn: &Connection,
+        user_id: u32,
+        status: &TaskStatus,
+    ) -> Result<Option<WorkflowStatus>, WorkflowStatusError> {
+        Ok(conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM workflow_statuses WHERE user_id = ?1 AND key = ?2",
+                    STATUS_COLUMNS
+                ),
+                params![user_id, status.as_str()],
+                |row| WorkflowStatus::try_from(row),
+            )
+            .optional()?)
+    }
+
+    /// The status new tasks start in: the first "not started" one.
+    pub fn initial_status(conn: &Connection, user_id: u32) -> Result<TaskStatus, WorkflowStatusError> {
+        conn.query_row(
+            "SELECT key FROM workflow_statuses
+             WHERE user_id = ?1 AND category = ?2
+             ORDER BY position, id LIMIT 1",
+            params![user_id, StatusCategory::NotStarted.as_str()],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| WorkflowStatusError::NotFound(format!("no statuses for user {}", user_id)))
+    }
+
+    /// Saves a new definition at the end of the user's list, suffixing its key
+    /// if another status of the user already uses it.
+    pub fn create(conn: &Connection, status: &mut WorkflowStatus) -> Result<(), WorkflowStatusError> {
+        let base = status.status.as_str().to_string();
+        let mut key = status.status.clone();
+        let mut suffix = 1;
+        while WorkflowStatusRepository::find(conn, status.user_id, &key)?.is_some() {
+            suffix += 1;
+            key = TaskStatus::from_key(&format!("{}_{}", base, suffix))
+                .map_err(|e| WorkflowStatusError::InvalidName(e.to_string()))?;
+        }
+
+        let position: u32 = conn.query_row(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM workflow_statuses WHERE user_id = ?1",
+            params![status.user_id],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "INSERT INTO workflow_statuses (user_id, key, name, category, position, color)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                status.user_id,
+                key.as_str(),
+                status.name,
+                status.category.as_str(),
+                position,
+                status.color
+            ],
+        )?;
+
+        status.id = Some(conn.last_insert_rowid() as u32);
+        status.status = key;
+        status.position = position;
+        Ok(())
+    }
+
+    /// Renames, recolors or recategorizes a status. The key tasks refer to
+    /// never changes.
+    pub fn update(
+        uow: &UnitOfWork,
+        user_id: u32,
+        status: &TaskStatus,
+        name: Option<String>,
+        color: Option<String>,
+        category: Option<StatusCategory>,
+    ) -> Result<WorkflowStatus, WorkflowStatusError> {
+        let mut current = WorkflowStatusRepository::find(uow, user_id, status)?
+            .ok_or_else(|| WorkflowStatusError::NotFound(status.to_string()))?;
+        let previous_category = current.category;
+
+        if let Some(name) = name {
+            validate_name(&name)?;
+            current.name = name;
+        }
+        if let Some(color) = color {
+            validate_color(&color)?;
+            current.color = color;
+        }
+        if let Some(category) = category {
+            if category != current.category {
+                WorkflowStatusRepository::ensure_not_last(uow, &current)?;
+            }
+            current.category = category;
+        }
+
+        let recategorized = category.is_some_and(|category| category != previous_category);
+        uow.execute(
+            "UPDATE workflow_statuses SET name = ?1, color = ?2, category = ?3 WHERE id = ?4",
+            params![current.name, current.color, current.category.as_str(), current.id],
+        )?;
+        if recategorized {
+            WorkflowStatusRepository::sync_completed_at(uow, user_id, status)?;
+        }
+        Ok(current)
+    }
+
+    /// Sets the column order; `order` must list each of the user's statuses once.
+    pub fn reorder(
+        uow: &UnitOfWork,
+        user_id: u32,
+        order: &[TaskStatus],
+    ) -> Result<Vec<WorkflowStatus>, WorkflowStatusError> {
+        let existing = WorkflowStatusRepository::list(uow, user_id)?;
+        let complete = order.len() == existing.len()
+            && existing.iter().all(|definition| order.contains(&definition.status));
+        if !complete {
+            return Err(WorkflowStatusError::NotFound(
+                "the new order must list every status exactly once".to_owned(),
+            ));
+        }
+
+        for (position, status) in order.iter().enumerate() {
+            uow.execute(
+                "UPDATE workflow_statuses SET position = ?1 WHERE user_id = ?2 AND key = ?3",
+                params![position as u32, user_id, status.as_str()],
+            )?;
+        }
+        WorkflowStatusRepository::list(uow, user_id)
+    }
+
+    /// Deletes a status after moving its tasks, in their order, to the end of
+    /// `move_tasks_to`. Returns how many tasks were moved.
+    pub fn delete(
+        uow: &UnitOfWork,
+        user_id: u32,
+        status: &TaskStatus,
+        move_tasks_to: &TaskStatus,
+    ) -> Result<u32, WorkflowStatusError> {
+        let current = WorkflowStatusRepository::find(uow, user_id, status)?
+            .ok_or_else(|| WorkflowStatusError::NotFound(status.to_string()))?;
+        if status == move_tasks_to
+            || WorkflowStatusRepository::find(uow, user_id, move_tasks_to)?.is_none()
+        {
+            return Err(WorkflowStatusError::NotFound(format!(
+                "cannot move tasks of '{}' to '{}'", status, move_tasks_to
+            )));
+        }
+        WorkflowStatusRepository::ensure_not_last(uow, &current)?;
+
+        let mut last: Option<String> = uow.query_row(
+            "SELECT MAX(rank) FROM tasks WHERE user_id = ?1 AND status = ?2 AND rank != ''",
+            params![user_id, move_tasks_to.as_str()],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = uow.prepare(
+            "SELECT id FROM tasks WHERE user_id = ?1 AND status = ?2 ORDER BY rank, id",
+        )?;
+        let task_ids = stmt
+            .query_map(params![user_id, status.as_str()], |row| row.get(0))?
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        for task_id in &task_ids {
+            let rank = rank_between(last.as_deref(), None).map_err(WorkflowStatusError::DatabaseError)?;
+            uow.execute(
+                "UPDATE tasks SET status = ?1, rank = ?2 WHERE id = ?3",
+                params![move_tasks_to.as_str(), rank, task_id],
+            )?;
+            last = Some(rank);
+        }
+        WorkflowStatusRepository::sync_completed_at(uow, user_id, move_tasks_to)?;
+
+        uow.execute("DELETE FROM workflow_statuses WHERE id = ?1", params![current.id])?;
+        Ok(task_ids.len() as u32)
+    }
+
+    /// Re-evaluates `completed_at` for the user's tasks in `status`.
+    fn sync_completed_at(conn: &Connection, user_id: u32, status: &TaskStatus) -> Result<(), WorkflowStatusError> {
+        let now = truncate_to_minute(Utc::now().naive_utc());
+        let mut stmt = conn.prepare("SELECT id FROM tasks WHERE user_id = ?1 AND status = ?2")?;
+        let task_ids = stmt
+            .query_map(params![user_id, status.as_str()], |row| row.get(0))?
+            .collect::<Result<Vec<u32>, _>>()?;
+        for task_id in task_ids {
+            TaskRepository::sync_completed_at(conn, task_id, now)
+                .map_err(|e| WorkflowStatusError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn ensure_not_last(conn: &Connection, status: &WorkflowStatus) -> Result<(), WorkflowStatusError> {
+        let in_category: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM workflow_statuses WHERE user_id = ?1 AND category = ?2",
+            params![status.user_id, status.category.as_str()],
+            |row| row.get(0),
+        )?;
+        if in_category <= 1 {
+            return Err(WorkflowStatusError::LastInCategory(format!(
+                "'{}' is the only {} status", status.name, status.category
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+    use crate::repository::TaskRepository;
+    use crate::utils::initialize_database;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn create(conn: &Connection, name: &str, category: StatusCategory) -> WorkflowStatus {
+        let mut status = WorkflowStatus::new(1, name.to_string(), category, "teal".to_string()).unwrap();
+        WorkflowStatusRepository::create(conn, &mut status).unwrap();
+        status
+    }
+
+    fn keys(conn: &Connection) -> Vec<String> {
+        WorkflowStatusRepository::list(conn, 1)
+            .unwrap()
+            .iter()
+            .map(|definition| definition.status.to_string())
+            .collect()
+    }
+
+    fn add_task(conn: &Connection, title: &str, status: TaskStatus) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.status = status;
+        TaskRepository::insert_task(conn, &mut task).unwrap();
+        task.id.unwrap()
+    }
+
+    #[test]
+    fn test_create_appends_with_unique_key() {
+        let conn = setup_db();
+
+        let reading = create(&conn, "Reading", StatusCategory::Active);
+        let again = create(&conn, "reading", StatusCategory::Active);
+        let to_do = create(&conn, "To do", StatusCategory::NotStarted);
+
+        assert_eq!(reading.status, TaskStatus::Custom("reading".to_string()));
+        assert_eq!(reading.position, 4);
+        assert_eq!(again.status, TaskStatus::Custom("reading_2".to_string()));
+        assert_eq!(to_do.status, TaskStatus::Custom("todo_2".to_string()));
+        assert_eq!(keys(&conn).len(), 7);
+    }
+
+    #[test]
+    fn test_tasks_can_use_custom_statuses() {
+        let conn = setup_db();
+        let reviewing = create(&conn, "Reviewing", StatusCategory::Active);
+
+        let task_id = add_task(&conn, "Essay", reviewing.status.clone());
+
+        let task = TaskRepository::get_task_by_id(&conn, task_id).unwrap();
+        assert_eq!(task.status, reviewing.status);
+    }
+
+    #[test]
+    fn test_update_keeps_key_and_guards_categories() {
+        let conn = setup_db();
+
+        let updated = UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::update(uow, 1, &TaskStatus::Todo, Some("A fazer".to_string()), Some("#123".to_string()), None)
+        })
+        .unwrap();
+        assert_eq!((updated.status, updated.name.as_str()), (TaskStatus::Todo, "A fazer"));
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::update(uow, 1, &TaskStatus::Done, None, None, Some(StatusCategory::Active))
+        });
+        assert!(matches!(result, Err(WorkflowStatusError::LastInCategory(_))));
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::update(uow, 1, &TaskStatus::Todo, Some(" ".to_string()), None, None)
+        });
+        assert!(matches!(result, Err(WorkflowStatusError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_recategorizing_a_status_updates_completion() {
+        let conn = setup_db();
+        let graded = create(&conn, "Graded", StatusCategory::Active);
+        let task_id = add_task(&conn, "Essay", graded.status.clone());
+        let completed_at = |conn: &Connection| TaskRepository::get_task_by_id(conn, task_id).unwrap().completed_at;
+        assert_eq!(completed_at(&conn), None);
+
+        let recategorize = |category| {
+            UnitOfWork::run(&conn, |uow| WorkflowStatusRepository::update(uow, 1, &graded.status, None, None, Some(category)))
+                .unwrap()
+        };
+        recategorize(StatusCategory::Completed);
+        assert!(completed_at(&conn).is_some());
+        recategorize(StatusCategory::Active);
+        assert_eq!(completed_at(&conn), None);
+    }
+
+    #[test]
+    fn test_reorder_requires_every_status() {
+        let conn = setup_db();
+        let order = [TaskStatus::Todo, TaskStatus::InProgress, TaskStatus::Done, TaskStatus::Backlog];
+
+        UnitOfWork::run(&conn, |uow| WorkflowStatusRepository::reorder(uow, 1, &order)).unwrap();
+        assert_eq!(keys(&conn), ["todo", "in_progress", "done", "backlog"]);
+
+        let result = UnitOfWork::run(&conn, |uow| WorkflowStatusRepository::reorder(uow, 1, &order[..3]));
+        assert!(result.is_err());
+        assert_eq!(keys(&conn), ["todo", "in_progress", "done", "backlog"]);
+    }
+
+    #[test]
+    fn test_delete_moves_tasks_in_order() {
+        let conn = setup_db();
+        let reading = create(&conn, "Reading", StatusCategory::Active);
+        add_task(&conn, "Doing", TaskStatus::InProgress);
+        add_task(&conn, "First", reading.status.clone());
+        add_task(&conn, "Second", reading.status.clone());
+        TaskRepository::set_wip_limit(&conn, 1, &reading.status, Some(5)).unwrap();
+
+        let moved = UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::delete(uow, 1, &reading.status, &TaskStatus::InProgress)
+        })
+        .unwrap();
+
+        assert_eq!(moved, 2);
+        let titles: Vec<_> = TaskRepository::get_column(&conn, 1, &TaskStatus::InProgress)
+            .unwrap()
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+        assert_eq!(titles, ["Doing", "First", "Second"]);
+        assert!(WorkflowStatusRepository::find(&conn, 1, &reading.status).unwrap().is_none());
+        assert_eq!(TaskRepository::get_wip_limit(&conn, 1, &reading.status).unwrap(), None);
+    }
+
+    #[test]
+    fn test_delete_keeps_one_status_per_category() {
+        let conn = setup_db();
+
+        let result = UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::delete(uow, 1, &TaskStatus::Done, &TaskStatus::Todo)
+        });
+        assert!(matches!(result, Err(WorkflowStatusError::LastInCategory(_))));
+
+        UnitOfWork::run(&conn, |uow| {
+            WorkflowStatusRepository::delete(uow, 1, &TaskStatus::Backlog, &TaskStatus::Todo)
+        })
+        .unwrap();
+        assert_eq!(WorkflowStatusRepository::initial_status(&conn, 1).unwrap(), TaskStatus::Todo);
+    }
+}
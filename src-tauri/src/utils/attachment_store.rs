@@ -0,0 +1,165 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the blob directory created next to `app.db`.
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+/// A file copied into the store, named by the SHA-256 of its contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredBlob {
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobStatus {
+    Intact,
+    Missing,
+    Corrupted,
+}
+
+/// Content-addressed blob directory. Blobs live at `<root>/<ab>/<abcdef…>`,
+/// so importing the same bytes twice stores them once. Which blobs are still
+/// referenced is tracked in the database, not here.
+#[derive(Debug, Clone)]
+pub struct AttachmentStore {
+    root: PathBuf,
+}
+
+impl AttachmentStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[..2]).join(hash)
+    }
+
+    /// Copies `source` into the store, hashing while copying. An intact blob
+    /// with the same hash is reused; a damaged one is replaced.
+    pub fn import(&self, source: &Path) -> io::Result<StoredBlob> {
+        fs::create_dir_all(&self.root)?;
+        let incoming = self.root.join(format!(".incoming-{}", std::process::id()));
+
+        let copied = File::open(source).and_then(|mut reader| {
+            let writer = File::create(&incoming)?;
+            reader_hash(&mut reader, Some(writer))
+        });
+        let blob = match copied {
+            Ok((hash, size)) => StoredBlob { hash, size },
+            Err(e) => {
+                let _ = fs::remove_file(&incoming);
+                return Err(e);
+            }
+        };
+
+        let target = self.path(&blob.hash);
+        if self.check(&blob.hash, blob.size)? == BlobStatus::Intact {
+            fs::remove_file(&incoming)?;
+        } else {
+            fs::create_dir_all(target.parent().unwrap_or(&self.root))?;
+            fs::rename(&incoming, &target)?;
+        }
+        Ok(blob)
+    }
+
+    /// Deletes a blob; a blob that is already gone is not an error.
+    pub fn remove(&self, hash: &str) -> io::Result<()> {
+        let path = self.path(hash);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        // Only succeeds once the fan-out directory is empty.
+        if let Some(parent) = path.parent() {
+            let _ = fs::remove_dir(parent);
+        }
+        Ok(())
+    }
+
+    /// Re-hashes a blob and compares it with the recorded hash and size.
+    pub fn check(&self, hash: &str, size: u64) -> io::Result<BlobStatus> {
+        let mut file = match File::open(self.path(hash)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BlobStatus::Missing),
+            Err(e) => return Err(e),
+        };
+        let (actual, actual_size) = reader_hash(&mut file, None::<File>)?;
+        Ok(if actual == hash && actual_size == size {
+            BlobStatus::Intact
+        } else {
+            BlobStatus::Corrupted
+        })
+    }
+}
+
+/// Hashes everything `reader` yields, copying it into `sink` when given.
+fn reader_hash(reader: &mut impl Read, mut sink: Option<impl Write>) -> io::Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        if let Some(sink) = sink.as_mut() {
+            sink.write_all(&buffer[..read])?;
+        }
+        size += read as u64;
+    }
+    if let Some(mut sink) = sink {
+        sink.flush()?;
+    }
+    Ok((format!("{:x}", hasher.finalize()), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("attachment-store-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_import_deduplicates_by_content() {
+        let dir = temp_dir("dedup");
+        let store = AttachmentStore::new(dir.join(ATTACHMENTS_DIR));
+        fs::write(dir.join("a.txt"), b"hello").unwrap();
+        fs::write(dir.join("b.txt"), b"hello").unwrap();
+
+        let first = store.import(&dir.join("a.txt")).unwrap();
+        let second = store.import(&dir.join("b.txt")).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.hash, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        assert_eq!(fs::read(store.path(&first.hash)).unwrap(), b"hello");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_detects_damage_and_import_repairs_it() {
+        let dir = temp_dir("check");
+        let store = AttachmentStore::new(dir.join(ATTACHMENTS_DIR));
+        fs::write(dir.join("a.txt"), b"hello").unwrap();
+        let blob = store.import(&dir.join("a.txt")).unwrap();
+
+        fs::write(store.path(&blob.hash), b"jello").unwrap();
+        assert_eq!(store.check(&blob.hash, blob.size).unwrap(), BlobStatus::Corrupted);
+
+        store.import(&dir.join("a.txt")).unwrap();
+        assert_eq!(store.check(&blob.hash, blob.size).unwrap(), BlobStatus::Intact);
+
+        store.remove(&blob.hash).unwrap();
+        store.remove(&blob.hash).unwrap();
+        assert_eq!(store.check(&blob.hash, blob.size).unwrap(), BlobStatus::Missing);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+use rusqlite::{Connection, OpenFlags};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::errors::PoolError;
+use crate::utils::initialize_database::initialize_database;
+
+pub const DEFAULT_READERS: usize = 4;
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// SQLite pool with a single writer and a fixed set of read-only connections.
+///
+/// The database runs in WAL mode, so readers keep seeing the last committed
+/// snapshot while the writer holds an open transaction.
+pub struct DbPool {
+    path: PathBuf,
+    writer: Mutex<Connection>,
+    readers: Mutex<Vec<Connection>>,
+    reader_released: Condvar,
+}
+
+/// Read-only connection checked out of a [`DbPool`]; returned to the pool on drop.
+pub struct PooledReader<'a> {
+    pool: &'a DbPool,
+    conn: Option<Connection>,
+}
+
+impl DbPool {
+    pub fn open<P: AsRef<Path>>(path: P, readers: usize) -> Result<Self, PoolError> {
+        let path = path.as_ref().to_path_buf();
+
+        let writer = Connection::open(&path)?;
+        writer.busy_timeout(BUSY_TIMEOUT)?;
+        writer.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        writer.pragma_update(None, "synchronous", "NORMAL")?;
+        initialize_database(&writer)?;
+
+        let readers = (0..readers.max(1))
+            .map(|_| Self::open_reader(&path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            path,
+            writer: Mutex::new(writer),
+            readers: Mutex::new(readers),
+            reader_released: Condvar::new(),
+        })
+    }
+
+    fn open_reader(path: &Path) -> Result<Connection, PoolError> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX
+                | OpenFlags::SQLITE_OPEN_URI,
+        )?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Blocks until a reader is free. Readers never wait on the writer.
+    pub fn reader(&self) -> Result<PooledReader<'_>, PoolError> {
+        let mut readers = self.readers.lock().map_err(|_| PoolError::LockFailed)?;
+        loop {
+            if let Some(conn) = readers.pop() {
+                return Ok(PooledReader { pool: self, conn: Some(conn) });
+            }
+            readers = self
+                .reader_released
+                .wait(readers)
+                .map_err(|_| PoolError::LockFailed)?;
+        }
+    }
+
+    pub fn writer(&self) -> Result<MutexGuard<'_, Connection>, PoolError> {
+        self.writer.lock().map_err(|_| PoolError::LockFailed)
+    }
+}
+
+impl Deref for PooledReader<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("reader connection already released")
+    }
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            if let Ok(mut readers) = self.pool.readers.lock() {
+                readers.push(conn);
+                self.pool.reader_released.notify_one();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+    use std::time::Instant;
+
+    struct TempDb(PathBuf);
+
+    impl TempDb {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("studystudio-{}-{}.db", name, std::process::id()));
+            let db = TempDb(path);
+            db.cleanup();
+            db
+        }
+
+        fn cleanup(&self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{}", self.0.display(), suffix));
+            }
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            self.cleanup();
+        }
+    }
+
+    fn count_users(conn: &Connection) -> u32 {
+        conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_open_enables_wal_and_schema() {
+        let db = TempDb::new("pool-wal");
+        let pool = DbPool::open(&db.0, 2).unwrap();
+
+        let mode: String = pool
+            .writer()
+            .unwrap()
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode.to_lowercase(), "wal");
+
+        let reader = pool.reader().unwrap();
+        assert_eq!(count_users(&reader), 0);
+    }
+
+    #[test]
+    fn test_readers_are_read_only() {
+        let db = TempDb::new("pool-readonly");
+        let pool = DbPool::open(&db.0, 1).unwrap();
+
+        let reader = pool.reader().unwrap();
+        let result = reader.execute(
+            "INSERT INTO users (name, status) VALUES ('Alice', 'active')",
+            [],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reader_is_returned_on_drop() {
+        let db = TempDb::new("pool-return");
+        let pool = DbPool::open(&db.0, 1).unwrap();
+
+        drop(pool.reader().unwrap());
+        drop(pool.reader().unwrap());
+        assert_eq!(pool.readers.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_reads_progress_during_long_write() {
+        let db = TempDb::new("pool-concurrent");
+        let pool = Arc::new(DbPool::open(&db.0, 2).unwrap());
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let (finish_tx, finish_rx) = mpsc::channel::<()>();
+
+        let writer_pool = Arc::clone(&pool);
+        let writer = thread::spawn(move || {
+            let mut conn = writer_pool.writer().unwrap();
+            let tx = conn.transaction().unwrap();
+            tx.execute(
+                "INSERT INTO users (name, status) VALUES ('Alice', 'active')",
+                [],
+            )
+            .unwrap();
+            started_tx.send(()).unwrap();
+            finish_rx.recv().unwrap();
+            tx.commit().unwrap();
+        });
+
+        started_rx.recv().unwrap();
+
+        let started = Instant::now();
+        for _ in 0..10 {
+            let reader = pool.reader().unwrap();
+            assert_eq!(count_users(&reader), 0);
+        }
+        assert!(started.elapsed() < BUSY_TIMEOUT);
+
+        finish_tx.send(()).unwrap();
+        writer.join().unwrap();
+
+        let reader = pool.reader().unwrap();
+        assert_eq!(count_users(&reader), 1);
+    }
+
+    #[test]
+    fn test_concurrent_readers_across_threads() {
+        let db = TempDb::new("pool-threads");
+        let pool = Arc::new(DbPool::open(&db.0, 2).unwrap());
+        pool.writer()
+            .unwrap()
+            .execute(
+                "INSERT INTO users (name, status) VALUES ('Alice', 'active')",
+                [],
+            )
+            .unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    let reader = pool.reader().unwrap();
+                    count_users(&reader)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 1);
+        }
+    }
+}
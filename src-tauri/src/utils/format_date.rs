@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 
 pub fn format_datetime(dt: NaiveDateTime) -> String {
     dt.format("%Y-%m-%d %H:%M:%S").to_string()
@@ -9,6 +9,44 @@ pub fn truncate_to_minute(dt: NaiveDateTime) -> NaiveDateTime {
     NaiveDateTime::parse_from_str(&dt_str, "%Y-%m-%dT%H:%M").unwrap()
 }
 
+/// Parses a date sent by the frontend (`2025-03-10T09:00`, optionally with
+/// seconds, milliseconds and a trailing `Z`), truncated to the minute.
+pub fn parse_frontend_datetime(value: &str) -> Result<NaiveDateTime, String> {
+    let date_replaced = value.replace("Z", "");
+    let trimmed_date = if date_replaced.len() > 16 {
+        &date_replaced[..16]
+    } else {
+        &date_replaced
+    };
+
+    NaiveDateTime::parse_from_str(trimmed_date, "%Y-%m-%dT%H:%M")
+        .map_err(|e| format!("Invalid date format: {}", e))
+}
+
+/// Parses a date sent by the frontend (`2025-03-10`, or the date part of a
+/// datetime).
+pub fn parse_frontend_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.get(..10).unwrap_or(value), "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format: {}", e))
+}
+
+/// Parses a wall-clock time sent by the frontend (`08:30`).
+pub fn parse_frontend_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.get(..5).unwrap_or(value), "%H:%M")
+        .map_err(|e| format!("Invalid time format: {}", e))
+}
+
+/// Converts a wall-clock time on this machine to the UTC time stored in the
+/// database. Times skipped by a DST jump resolve to one hour later.
+pub fn local_to_utc(dt: NaiveDateTime) -> NaiveDateTime {
+    Local
+        .from_local_datetime(&dt)
+        .earliest()
+        .or_else(|| Local.from_local_datetime(&(dt + Duration::hours(1))).earliest())
+        .map(|local| local.naive_utc())
+        .unwrap_or(dt)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +87,23 @@ mod tests {
         let formatted = format_datetime(truncated);
         assert_eq!(formatted, "2023-10-15 14:30:00");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_frontend_datetime() {
+        let expected = NaiveDateTime::parse_from_str("2025-03-10 09:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(parse_frontend_datetime("2025-03-10T09:30").unwrap(), expected);
+        assert_eq!(parse_frontend_datetime("2025-03-10T09:30:59.123Z").unwrap(), expected);
+        assert!(parse_frontend_datetime("10/03/2025").is_err());
+    }
+
+    #[test]
+    fn test_parse_frontend_date_and_time() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        assert_eq!(parse_frontend_date("2025-03-10").unwrap(), date);
+        assert_eq!(parse_frontend_date("2025-03-10T09:30:00Z").unwrap(), date);
+        assert!(parse_frontend_date("10/03").is_err());
+
+        assert_eq!(parse_frontend_time("08:30").unwrap(), NaiveTime::from_hms_opt(8, 30, 0).unwrap());
+        assert!(parse_frontend_time("8h").is_err());
+    }
+}
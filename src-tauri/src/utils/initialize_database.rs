@@ -1,5 +1,7 @@
 use rusqlite::{Connection, Result};
 
+use crate::utils::migrations::run_migrations;
+
 pub fn initialize_database(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         r#"
@@ -43,7 +45,14 @@ pub fn initialize_database(conn: &Connection) -> Result<()> {
             FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
             FOREIGN KEY (tag_id) REFERENCES tags(id)
         );
+
+        CREATE TABLE IF NOT EXISTS undo_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            label TEXT NOT NULL,
+            snapshot TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
         "#,
     )?;
-    Ok(())
+    run_migrations(conn)
 }
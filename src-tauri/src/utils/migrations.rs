@@ -0,0 +1,826 @@
+use rusqlite::{params, Connection, Result};
+
+use crate::models::DEFAULT_STATUSES;
+use crate::utils::rank::initial_ranks;
+
+/// One schema change applied on top of the tables created by
+/// `initialize_database`. Applied in order and tracked with `PRAGMA user_version`.
+pub type Migration = fn(&Connection) -> Result<()>;
+
+pub const MIGRATIONS: &[Migration] = &[add_task_ranks, add_workflow_statuses, numeric_priorities, add_time_tracking, add_reminders, add_subjects, add_timetable, add_assessments, add_study_plans, add_notes, add_attachments, add_reading, add_quizzes, add_goals, add_completed_at, optional_due_dates, add_undo_dependents, add_undo_owners, per_user_wip_limits, rearm_relative_reminders, add_undo_outcomes];
+
+pub fn schema_version(conn: &Connection) -> Result<usize> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Applies every pending migration, each in its own transaction.
+///
+/// Foreign keys are disabled while migrating so tables can be rebuilt, and
+/// checked before each migration commits.
+pub fn run_migrations(conn: &Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current >= MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.pragma_update(None, "foreign_keys", false)?;
+
+    let result = MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current)
+        .try_for_each(|(index, migration)| {
+            let tx = conn.unchecked_transaction()?;
+            migration(&tx)?;
+            check_foreign_keys(&tx)?;
+            tx.pragma_update(None, "user_version", index + 1)?;
+            tx.commit()
+        });
+
+    conn.pragma_update(None, "foreign_keys", foreign_keys)?;
+    result
+}
+
+fn check_foreign_keys(conn: &Connection) -> Result<()> {
+    let violations: u32 =
+        conn.query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| row.get(0))?;
+    if violations > 0 {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY),
+            Some(format!("{} foreign key violations after migration", violations)),
+        ));
+    }
+    Ok(())
+}
+
+/// Kanban ordering: a fractional `rank` per task within its status column,
+/// seeded from due date order, plus optional WIP limits per column.
+fn add_task_ranks(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tasks ADD COLUMN rank TEXT NOT NULL DEFAULT '';
+        CREATE INDEX IF NOT EXISTS idx_tasks_status_rank ON tasks (status, rank);
+
+        CREATE TABLE IF NOT EXISTS wip_limits (
+            status TEXT PRIMARY KEY,
+            max_tasks INTEGER NOT NULL CHECK(max_tasks > 0)
+        );
+        "#,
+    )?;
+
+    let mut stmt = conn.prepare("SELECT id, status FROM tasks ORDER BY status, due_date, id")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>>>()?;
+
+    for status_rows in rows.chunk_by(|a, b| a.1 == b.1) {
+        for ((id, _), rank) in status_rows.iter().zip(initial_ranks(status_rows.len())) {
+            conn.execute("UPDATE tasks SET rank = ?1 WHERE id = ?2", params![rank, id])?;
+        }
+    }
+    Ok(())
+}
+
+/// Per-user workflow statuses. Every user gets `DEFAULT_STATUSES` (also for
+/// users created later, via trigger), and `tasks` is rebuilt so its status
+/// references the owner's definitions instead of a fixed CHECK list.
+fn add_workflow_statuses(conn: &Connection) -> Result<()> {
+    let defaults = DEFAULT_STATUSES
+        .iter()
+        .enumerate()
+        .map(|(position, (key, name, category, color))| {
+            format!("(NEW.id, '{}', '{}', '{}', {}, '{}')", key, name, category, position, color)
+        })
+        .collect::<Vec<_>>()
+        .join(",\n                ");
+
+    conn.execute_batch(&format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS workflow_statuses (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            key TEXT NOT NULL,
+            name TEXT NOT NULL,
+            category TEXT CHECK(category IN ('not_started', 'active', 'completed')) NOT NULL,
+            position INTEGER NOT NULL,
+            color TEXT NOT NULL,
+            UNIQUE (user_id, key),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TRIGGER IF NOT EXISTS seed_workflow_statuses AFTER INSERT ON users
+        BEGIN
+            INSERT INTO workflow_statuses (user_id, key, name, category, position, color) VALUES
+                {defaults};
+        END;
+
+        CREATE TABLE tasks_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            status TEXT NOT NULL,
+            priority TEXT CHECK(priority IN ('low', 'medium', 'high')) NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            due_date TEXT NOT NULL,
+            rank TEXT NOT NULL DEFAULT '',
+            FOREIGN KEY (user_id) REFERENCES users(id),
+            FOREIGN KEY (user_id, status) REFERENCES workflow_statuses(user_id, key)
+        );
+
+        INSERT INTO tasks_new (id, user_id, title, description, status, priority, created_at, updated_at, due_date, rank)
+            SELECT id, user_id, title, description, status, priority, created_at, updated_at, due_date, rank FROM tasks;
+        DROP TABLE tasks;
+        ALTER TABLE tasks_new RENAME TO tasks;
+        CREATE INDEX IF NOT EXISTS idx_tasks_status_rank ON tasks (status, rank);
+        "#
+    ))?;
+
+    for (position, (key, name, category, color)) in DEFAULT_STATUSES.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO workflow_statuses (user_id, key, name, category, position, color)
+             SELECT id, ?1, ?2, ?3, ?4, ?5 FROM users",
+            params![key, name, category.as_str(), position, color],
+        )?;
+    }
+    Ok(())
+}
+
+/// Priorities become a 1–5 level; the old low/medium/high map to 2/3/4.
+fn numeric_priorities(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE tasks_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            status TEXT NOT NULL,
+            priority INTEGER CHECK(priority BETWEEN 1 AND 5) NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            due_date TEXT NOT NULL,
+            rank TEXT NOT NULL DEFAULT '',
+            FOREIGN KEY (user_id) REFERENCES users(id),
+            FOREIGN KEY (user_id, status) REFERENCES workflow_statuses(user_id, key)
+        );
+
+        INSERT INTO tasks_new (id, user_id, title, description, status, priority, created_at, updated_at, due_date, rank)
+            SELECT id, user_id, title, description, status,
+                CASE priority WHEN 'low' THEN 2 WHEN 'high' THEN 4 ELSE 3 END,
+                created_at, updated_at, due_date, rank
+            FROM tasks;
+        DROP TABLE tasks;
+        ALTER TABLE tasks_new RENAME TO tasks;
+        CREATE INDEX IF NOT EXISTS idx_tasks_status_rank ON tasks (status, rank);
+        "#,
+    )
+}
+
+/// Estimates on tasks and the time actually spent on them. A log with no
+/// `ended_at` is a running timer; a task has at most one.
+fn add_time_tracking(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tasks ADD COLUMN estimated_minutes INTEGER CHECK(estimated_minutes > 0);
+
+        CREATE TABLE IF NOT EXISTS time_logs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            minutes INTEGER CHECK(minutes >= 0),
+            note TEXT,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_time_logs_task ON time_logs (task_id);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_time_logs_running ON time_logs (task_id)
+            WHERE ended_at IS NULL;
+        "#,
+    )
+}
+
+/// Reminder rules: either `remind_at` or `minutes_before_due` is set.
+fn add_reminders(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            remind_at TEXT,
+            minutes_before_due INTEGER CHECK(minutes_before_due >= 0),
+            snoozed_until TEXT,
+            fired_at TEXT,
+            dismissed_at TEXT,
+            CHECK((remind_at IS NULL) != (minutes_before_due IS NULL)),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_reminders_pending ON reminders (task_id)
+            WHERE fired_at IS NULL AND dismissed_at IS NULL;
+        "#,
+    )
+}
+
+/// Subjects, plus the task's subject and kind (regular task or exam).
+fn add_subjects(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS subjects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            teacher TEXT,
+            color TEXT NOT NULL,
+            term TEXT,
+            credit_hours INTEGER CHECK(credit_hours > 0),
+            weekly_goal_minutes INTEGER CHECK(weekly_goal_minutes > 0),
+            UNIQUE(user_id, name),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        ALTER TABLE tasks ADD COLUMN subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL;
+        ALTER TABLE tasks ADD COLUMN kind TEXT NOT NULL DEFAULT 'task' CHECK(kind IN ('task', 'exam'));
+        CREATE INDEX IF NOT EXISTS idx_tasks_subject ON tasks (subject_id);
+        "#,
+    )
+}
+
+/// Terms, weekly class slots (weekday 0 = Monday), holidays/cancellations
+/// and the prep tasks generated per class meeting.
+fn add_timetable(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS terms (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            CHECK(end_date >= start_date),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS class_slots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            term_id INTEGER NOT NULL,
+            subject_id INTEGER NOT NULL,
+            weekday INTEGER NOT NULL CHECK(weekday BETWEEN 0 AND 6),
+            start_time TEXT NOT NULL,
+            end_time TEXT NOT NULL,
+            room TEXT,
+            CHECK(end_time > start_time),
+            FOREIGN KEY (term_id) REFERENCES terms(id) ON DELETE CASCADE,
+            FOREIGN KEY (subject_id) REFERENCES subjects(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS term_exceptions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            term_id INTEGER NOT NULL,
+            class_id INTEGER,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            description TEXT NOT NULL,
+            CHECK(end_date >= start_date),
+            FOREIGN KEY (term_id) REFERENCES terms(id) ON DELETE CASCADE,
+            FOREIGN KEY (class_id) REFERENCES class_slots(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS class_prep_tasks (
+            class_id INTEGER NOT NULL,
+            date TEXT NOT NULL,
+            task_id INTEGER NOT NULL,
+            PRIMARY KEY (class_id, date),
+            FOREIGN KEY (class_id) REFERENCES class_slots(id) ON DELETE CASCADE,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+}
+
+/// Graded assessments per subject and each subject's grading scheme.
+fn add_assessments(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE subjects ADD COLUMN grading_scheme TEXT NOT NULL DEFAULT 'ten_point'
+            CHECK(grading_scheme IN ('ten_point', 'percentage', 'letter'));
+        ALTER TABLE subjects ADD COLUMN passing_grade REAL CHECK(passing_grade BETWEEN 0 AND 1);
+
+        CREATE TABLE IF NOT EXISTS assessments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            subject_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            weight REAL NOT NULL CHECK(weight > 0),
+            date TEXT,
+            max_score REAL NOT NULL CHECK(max_score > 0),
+            score REAL CHECK(score >= 0 AND score <= max_score),
+            is_exam INTEGER NOT NULL DEFAULT 0,
+            task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL,
+            FOREIGN KEY (subject_id) REFERENCES subjects(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_assessments_subject ON assessments (subject_id);
+        "#,
+    )
+}
+
+/// Study plans, their topics and the block tasks generated for them.
+fn add_study_plans(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS study_plans (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            title TEXT NOT NULL,
+            exam_date TEXT NOT NULL,
+            daily_capacity_minutes INTEGER NOT NULL CHECK(daily_capacity_minutes > 0),
+            block_minutes INTEGER NOT NULL CHECK(block_minutes > 0),
+            rest_days TEXT NOT NULL DEFAULT '',
+            unscheduled_minutes INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS study_plan_topics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            minutes INTEGER NOT NULL CHECK(minutes > 0),
+            position INTEGER NOT NULL,
+            FOREIGN KEY (plan_id) REFERENCES study_plans(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS study_plan_blocks (
+            task_id INTEGER PRIMARY KEY,
+            plan_id INTEGER NOT NULL,
+            topic_id INTEGER NOT NULL,
+            minutes INTEGER NOT NULL CHECK(minutes > 0),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (plan_id) REFERENCES study_plans(id) ON DELETE CASCADE,
+            FOREIGN KEY (topic_id) REFERENCES study_plan_topics(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+}
+
+/// Markdown notes, their outgoing wiki-links and a full-text index kept in
+/// sync by triggers.
+fn add_notes(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            title TEXT NOT NULL COLLATE NOCASE,
+            body TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE (user_id, title),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS note_links (
+            note_id INTEGER NOT NULL,
+            target_title TEXT COLLATE NOCASE,
+            target_task_id INTEGER,
+            CHECK ((target_title IS NULL) != (target_task_id IS NULL)),
+            FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE,
+            FOREIGN KEY (target_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_note_links_title ON note_links(target_title);
+        CREATE INDEX IF NOT EXISTS idx_note_links_task ON note_links(target_task_id);
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+            title, body, content = 'notes', content_rowid = 'id', tokenize = 'unicode61 remove_diacritics 2'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_insert AFTER INSERT ON notes BEGIN
+            INSERT INTO notes_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_delete AFTER DELETE ON notes BEGIN
+            INSERT INTO notes_fts (notes_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_update AFTER UPDATE ON notes BEGIN
+            INSERT INTO notes_fts (notes_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+            INSERT INTO notes_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+        END;
+        "#,
+    )
+}
+
+/// Attachment metadata and the content-addressed blobs behind it. Triggers
+/// keep each blob's reference count, including on cascading deletes.
+fn add_attachments(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS attachment_blobs (
+            hash TEXT PRIMARY KEY,
+            size INTEGER NOT NULL,
+            ref_count INTEGER NOT NULL DEFAULT 0 CHECK(ref_count >= 0)
+        );
+
+        CREATE TABLE IF NOT EXISTS attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            task_id INTEGER,
+            note_id INTEGER,
+            hash TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            CHECK ((task_id IS NULL) != (note_id IS NULL)),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE,
+            FOREIGN KEY (hash) REFERENCES attachment_blobs(hash)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_attachments_task ON attachments(task_id);
+        CREATE INDEX IF NOT EXISTS idx_attachments_note ON attachments(note_id);
+        CREATE INDEX IF NOT EXISTS idx_attachments_hash ON attachments(hash);
+
+        CREATE TRIGGER IF NOT EXISTS attachments_ref_insert AFTER INSERT ON attachments BEGIN
+            UPDATE attachment_blobs SET ref_count = ref_count + 1 WHERE hash = new.hash;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS attachments_ref_delete AFTER DELETE ON attachments BEGIN
+            UPDATE attachment_blobs SET ref_count = ref_count - 1 WHERE hash = old.hash;
+        END;
+        "#,
+    )
+}
+
+/// Books, the reading sessions logged against them and the daily reading
+/// tasks generated to finish them on time.
+fn add_reading(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS books (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            title TEXT NOT NULL,
+            author TEXT,
+            total_units INTEGER NOT NULL CHECK(total_units > 0),
+            unit TEXT NOT NULL DEFAULT 'pages',
+            target_date TEXT,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS reading_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            book_id INTEGER NOT NULL,
+            read_on TEXT NOT NULL,
+            units INTEGER NOT NULL CHECK(units > 0),
+            minutes INTEGER CHECK(minutes > 0),
+            FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_reading_sessions_book ON reading_sessions(book_id, read_on);
+
+        CREATE TABLE IF NOT EXISTS reading_tasks (
+            task_id INTEGER PRIMARY KEY,
+            book_id INTEGER NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (book_id) REFERENCES books(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+}
+
+/// Question bank, quiz attempts and the answers given in each.
+fn add_quizzes(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS questions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            note_id INTEGER REFERENCES notes(id) ON DELETE SET NULL,
+            task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL,
+            kind TEXT NOT NULL,
+            prompt TEXT NOT NULL,
+            choices TEXT NOT NULL DEFAULT '[]',
+            answers TEXT NOT NULL,
+            explanation TEXT,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS question_tags (
+            question_id INTEGER NOT NULL,
+            tag_id INTEGER NOT NULL,
+            PRIMARY KEY (question_id, tag_id),
+            FOREIGN KEY (question_id) REFERENCES questions(id) ON DELETE CASCADE,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS quiz_attempts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            tag_id INTEGER REFERENCES tags(id) ON DELETE SET NULL,
+            seed INTEGER NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS quiz_answers (
+            attempt_id INTEGER NOT NULL,
+            question_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            given_answer TEXT,
+            correct INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (attempt_id, question_id),
+            FOREIGN KEY (attempt_id) REFERENCES quiz_attempts(id) ON DELETE CASCADE,
+            FOREIGN KEY (question_id) REFERENCES questions(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+}
+
+/// Per-user calendar settings, study goals and the days frozen for streaks.
+fn add_goals(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS user_settings (
+            user_id INTEGER PRIMARY KEY,
+            week_start INTEGER NOT NULL DEFAULT 0 CHECK(week_start BETWEEN 0 AND 6),
+            utc_offset_minutes INTEGER,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            metric TEXT NOT NULL,
+            period TEXT NOT NULL,
+            target INTEGER NOT NULL CHECK(target > 0),
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE CASCADE,
+            starts_on TEXT NOT NULL,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS streak_freezes (
+            user_id INTEGER NOT NULL,
+            date TEXT NOT NULL,
+            PRIMARY KEY (user_id, date),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+}
+
+/// Records when tasks were completed. Tasks already in a completed status get
+/// their last update as the best available estimate.
+fn add_completed_at(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE tasks ADD COLUMN completed_at TEXT;
+
+        UPDATE tasks SET completed_at = updated_at
+        WHERE EXISTS (
+            SELECT 1 FROM workflow_statuses ws
+            WHERE ws.user_id = tasks.user_id AND ws.key = tasks.status AND ws.category = 'completed'
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tasks_completed_at ON tasks (user_id, completed_at);
+        "#,
+    )
+}
+
+/// Due dates become optional, with an all-day mode and a start date. Until
+/// now `create_task` ignored the date it was given and stored the creation
+/// minute, so due dates equal to `created_at` are placeholders and are cleared.
+fn optional_due_dates(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE tasks_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            status TEXT NOT NULL,
+            priority INTEGER CHECK(priority BETWEEN 1 AND 5) NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            due_date TEXT,
+            all_day INTEGER NOT NULL DEFAULT 0 CHECK(all_day IN (0, 1)),
+            start_date TEXT,
+            rank TEXT NOT NULL DEFAULT '',
+            estimated_minutes INTEGER CHECK(estimated_minutes > 0),
+            subject_id INTEGER REFERENCES subjects(id) ON DELETE SET NULL,
+            kind TEXT NOT NULL DEFAULT 'task' CHECK(kind IN ('task', 'exam')),
+            completed_at TEXT,
+            FOREIGN KEY (user_id) REFERENCES users(id),
+            FOREIGN KEY (user_id, status) REFERENCES workflow_statuses(user_id, key),
+            CHECK(all_day = 0 OR due_date IS NOT NULL)
+        );
+
+        INSERT INTO tasks_new (id, user_id, title, description, status, priority, created_at, updated_at, due_date,
+                rank, estimated_minutes, subject_id, kind, completed_at)
+            SELECT id, user_id, title, description, status, priority, created_at, updated_at,
+                NULLIF(due_date, created_at), rank, estimated_minutes, subject_id, kind, completed_at
+            FROM tasks;
+        DROP TABLE tasks;
+        ALTER TABLE tasks_new RENAME TO tasks;
+        CREATE INDEX IF NOT EXISTS idx_tasks_status_rank ON tasks (status, rank);
+        CREATE INDEX IF NOT EXISTS idx_tasks_subject ON tasks (subject_id);
+        CREATE INDEX IF NOT EXISTS idx_tasks_completed_at ON tasks (user_id, completed_at);
+        CREATE INDEX IF NOT EXISTS idx_tasks_due_date ON tasks (user_id, due_date);
+        "#,
+    )
+}
+
+/// Rows that cascade with a deleted task, kept so undoing the delete can
+/// put them back.
+fn add_undo_dependents(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE undo_entries ADD COLUMN dependents TEXT NOT NULL DEFAULT '[]';
+        "#,
+    )
+}
+
+/// Undo entries belong to the user whose tasks they captured.
+fn add_undo_owners(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE undo_entries ADD COLUMN user_id INTEGER REFERENCES users(id) ON DELETE CASCADE;
+        UPDATE undo_entries SET user_id = json_extract(snapshot, '$[0].user_id');
+        CREATE INDEX IF NOT EXISTS idx_undo_entries_user ON undo_entries (user_id, id);
+        "#,
+    )
+}
+
+/// WIP limits belong to one user's column and go with it. Existing limits
+/// were shared, so every user with that column keeps them.
+fn per_user_wip_limits(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE wip_limits_new (
+            user_id INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            max_tasks INTEGER NOT NULL CHECK(max_tasks > 0),
+            PRIMARY KEY (user_id, status),
+            FOREIGN KEY (user_id, status) REFERENCES workflow_statuses(user_id, key) ON DELETE CASCADE
+        );
+
+        INSERT INTO wip_limits_new (user_id, status, max_tasks)
+            SELECT ws.user_id, w.status, w.max_tasks
+            FROM wip_limits w JOIN workflow_statuses ws ON ws.key = w.status;
+        DROP TABLE wip_limits;
+        ALTER TABLE wip_limits_new RENAME TO wip_limits;
+        "#,
+    )
+}
+
+/// Reminders relative to the due date fire again once it moves, whichever
+/// write moved it. A snooze is dropped with the old date; dismissed reminders
+/// stay retired.
+fn rearm_relative_reminders(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TRIGGER IF NOT EXISTS tasks_rearm_reminders AFTER UPDATE OF due_date ON tasks
+        WHEN old.due_date IS NOT new.due_date BEGIN
+            UPDATE reminders SET fired_at = NULL, snoozed_until = NULL
+            WHERE task_id = new.id AND minutes_before_due IS NOT NULL;
+        END;
+        "#,
+    )
+}
+
+/// Undo entries keep the tasks as the operation left them, so later edits
+/// are told apart from the operation itself within the same minute.
+fn add_undo_outcomes(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE undo_entries ADD COLUMN outcome TEXT;
+        "#,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::initialize_database;
+
+    #[test]
+    fn test_fresh_database_is_fully_migrated() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+        let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        assert!(foreign_keys);
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        initialize_database(&conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_existing_tasks_get_ranks_per_column() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "PRAGMA user_version = 0;
+             CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL,
+                 status TEXT NOT NULL, created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL);
+             CREATE TABLE tasks (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 user_id INTEGER NOT NULL,
+                 title TEXT NOT NULL,
+                 description TEXT,
+                 status TEXT CHECK(status IN ('todo', 'in_progress', 'done', 'backlog')) NOT NULL,
+                 priority TEXT CHECK(priority IN ('low', 'medium', 'high')) NOT NULL,
+                 created_at TEXT NOT NULL,
+                 updated_at TEXT NOT NULL,
+                 due_date TEXT NOT NULL,
+                 FOREIGN KEY (user_id) REFERENCES users(id)
+             );
+             INSERT INTO users (name, status) VALUES ('Alice', 'active');
+             INSERT INTO tasks (user_id, title, status, priority, created_at, updated_at, due_date) VALUES
+                 (1, 'Later', 'todo', 'low', '2025-03-01T09:00', '2025-03-01T09:00', '2025-03-12T09:00'),
+                 (1, 'Sooner', 'todo', 'low', '2025-03-01T09:00', '2025-03-01T09:00', '2025-03-10T09:00'),
+                 (1, 'Doing', 'in_progress', 'low', '2025-03-01T09:00', '2025-03-01T09:00', '2025-03-11T09:00');",
+        )
+        .unwrap();
+
+        initialize_database(&conn).unwrap();
+
+        let rank = |title: &str| -> String {
+            conn.query_row("SELECT rank FROM tasks WHERE title = ?1", [title], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(rank("Sooner") < rank("Later"));
+        let priority: u8 = conn
+            .query_row("SELECT priority FROM tasks WHERE title = 'Later'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(priority, 2);
+        assert_eq!(rank("Sooner"), rank("Doing"));
+        assert!(!rank("Later").is_empty());
+    }
+
+    #[test]
+    fn test_users_get_default_statuses() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL,
+                 status TEXT NOT NULL, created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL);
+             INSERT INTO users (name, status) VALUES ('Alice', 'active');",
+        )
+        .unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Bob', 'active')", [])
+            .unwrap();
+
+        let keys = |user_id: u32| -> Vec<String> {
+            let mut stmt = conn
+                .prepare("SELECT key FROM workflow_statuses WHERE user_id = ?1 ORDER BY position")
+                .unwrap();
+            stmt.query_map([user_id], |row| row.get(0))
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap()
+        };
+        assert_eq!(keys(1), ["backlog", "todo", "in_progress", "done"]);
+        assert_eq!(keys(2), keys(1));
+    }
+
+    #[test]
+    fn test_task_status_must_be_defined_for_its_user() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+
+        let insert = |status: &str| {
+            conn.execute(
+                "INSERT INTO tasks (user_id, title, status, priority, created_at, updated_at, due_date)
+                 VALUES (1, 'Read', ?1, 2, '2025-03-01T09:00', '2025-03-01T09:00', '2025-03-01T09:00')",
+                [status],
+            )
+        };
+        assert!(insert("done").is_ok());
+        assert!(insert("reading").is_err());
+
+        conn.execute(
+            "INSERT INTO workflow_statuses (user_id, key, name, category, position, color)
+             VALUES (1, 'reading', 'Reading', 'active', 4, 'blue')",
+            [],
+        )
+        .unwrap();
+        assert!(insert("reading").is_ok());
+    }
+}
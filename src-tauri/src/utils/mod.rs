@@ -5,4 +5,22 @@ pub mod format_date;
 pub use format_date::*;
 
 pub mod sql_types;
-pub use sql_types::*;
\ No newline at end of file
+pub use sql_types::*;
+
+pub mod db_pool;
+pub use db_pool::*;
+
+pub mod migrations;
+pub use migrations::run_migrations;
+
+pub mod rank;
+pub use rank::*;
+
+pub mod reminder_scheduler;
+pub use reminder_scheduler::*;
+
+pub mod quick_add;
+pub use quick_add::*;
+
+pub mod attachment_store;
+pub use attachment_store::*;
@@ -0,0 +1,457 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::models::task_priority::TaskPriority;
+
+/// Words dropped from the title when they introduce a date or time
+/// ("due tomorrow", "às 18h", "on 10/03").
+const CONNECTORS: &[&str] = &["at", "on", "by", "due", "as", "ate", "no", "na", "dia", "para"];
+
+/// Words after which a bare weekday name is read as a date ("on fri", "na
+/// segunda"). Elsewhere only the "-feira" form counts, so titles such as
+/// "segunda lei de Newton" or "Study for SAT" keep their words.
+const WEEKDAY_CONNECTORS: &[&str] = &["on", "no", "na", "next", "proxima", "proximo"];
+
+/// Due time used when only a day is given.
+pub fn default_due_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 0).unwrap()
+}
+
+/// What a quick-add line says, before tags are resolved against the database.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QuickAdd {
+    pub title: String,
+    /// Local wall-clock time.
+    pub due_date: Option<NaiveDateTime>,
+    /// Only a day was given; `due_date` is then at `default_due_time`.
+    pub all_day: bool,
+    pub priority: Option<TaskPriority>,
+    pub tags: Vec<String>,
+}
+
+/// Parses a line such as `Read chapter 4 of Physics tomorrow 18h #physics !high`.
+///
+/// Understands, in English and pt-BR: today/hoje, tomorrow/amanhã, weekdays
+/// after on/na/no/next/próxima or as "sexta-feira",
+/// "in 3 days"/"em 3 dias", next week/semana que vem, ISO and dd/mm[/yyyy]
+/// dates, "10 de março"/"march 10", and times like 18h, 18h30, 18:30, 6pm.
+/// `!high`/`!alta`/`!4` sets the priority and `#name` adds a tag. Only the
+/// first date, time and priority are taken; anything not understood stays in
+/// the title.
+pub fn parse_quick_add(input: &str, now: NaiveDateTime) -> QuickAdd {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|word| normalize(word)).collect();
+    let mut used = vec![false; words.len()];
+    let today = now.date();
+
+    let mut parsed = QuickAdd::default();
+    let mut date = None;
+    let mut time = None;
+
+    for i in 0..words.len() {
+        if used[i] {
+            continue;
+        }
+        let word = normalized[i].as_str();
+
+        let matched = if let Some(name) = words[i].strip_prefix('#') {
+            let name = name.trim_end_matches([',', '.', ';']);
+            if !name.is_empty() {
+                if !parsed.tags.iter().any(|tag| tag.to_lowercase() == name.to_lowercase()) {
+                    parsed.tags.push(name.to_string());
+                }
+                used[i] = true;
+            }
+            continue;
+        } else if let Some(level) = word.strip_prefix('!') {
+            if parsed.priority.is_none() {
+                parsed.priority = parse_priority(level);
+                used[i] = parsed.priority.is_some();
+            }
+            continue;
+        } else if let Some((len, day, at)) = date
+            .is_none()
+            .then(|| match_date(&normalized[i..], i.checked_sub(1).map(|j| normalized[j].as_str()), today))
+            .flatten()
+        {
+            date = Some(day);
+            time = time.or(at);
+            len
+        } else if let Some((len, at)) = time.is_none().then(|| match_time(&normalized[i..])).flatten() {
+            time = Some(at);
+            len
+        } else {
+            continue;
+        };
+
+        used[i..i + matched].iter_mut().for_each(|u| *u = true);
+        if i > 0 && !used[i - 1] && CONNECTORS.contains(&normalized[i - 1].as_str()) {
+            used[i - 1] = true;
+        }
+    }
+
+    parsed.all_day = date.is_some() && time.is_none();
+    parsed.due_date = match (date, time) {
+        (Some(date), time) => Some(date.and_time(time.unwrap_or_else(default_due_time))),
+        (None, Some(time)) if time > now.time() => Some(today.and_time(time)),
+        (None, Some(time)) => Some((today + Duration::days(1)).and_time(time)),
+        (None, None) => None,
+    };
+    parsed.title = words
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|(word, _)| *word)
+        .collect::<Vec<_>>()
+        .join(" ");
+    parsed
+}
+
+/// Lowercases, strips Portuguese accents and trailing punctuation.
+fn normalize(word: &str) -> String {
+    word.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' => 'a',
+            'é' | 'ê' => 'e',
+            'í' => 'i',
+            'ó' | 'ô' | 'õ' => 'o',
+            'ú' | 'ü' => 'u',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect::<String>()
+        .trim_end_matches([',', '.', ';'])
+        .to_string()
+}
+
+fn parse_priority(level: &str) -> Option<TaskPriority> {
+    match level {
+        "lowest" | "minima" => Some(TaskPriority::Lowest),
+        "low" | "baixa" => Some(TaskPriority::Low),
+        "medium" | "normal" | "media" => Some(TaskPriority::Medium),
+        "high" | "alta" => Some(TaskPriority::High),
+        "highest" | "urgent" | "maxima" | "urgente" => Some(TaskPriority::Highest),
+        _ => level.parse().ok(),
+    }
+}
+
+fn words_match(words: &[String], phrase: &[&str]) -> bool {
+    words.len() >= phrase.len() && words.iter().zip(phrase).all(|(word, expected)| word == expected)
+}
+
+/// Returns the number of words consumed, the day and an optional time.
+/// `previous` is the word before `words`, if any.
+fn match_date(words: &[String], previous: Option<&str>, today: NaiveDate) -> Option<(usize, NaiveDate, Option<NaiveTime>)> {
+    let day = |days: i64| today + Duration::days(days);
+
+    for (phrase, days) in [
+        (&["day", "after", "tomorrow"][..], 2),
+        (&["depois", "de", "amanha"][..], 2),
+        (&["today"][..], 0),
+        (&["hoje"][..], 0),
+        (&["tomorrow"][..], 1),
+        (&["amanha"][..], 1),
+    ] {
+        if words_match(words, phrase) {
+            return Some((phrase.len(), day(days), None));
+        }
+    }
+    if words_match(words, &["tonight"]) {
+        return Some((1, today, NaiveTime::from_hms_opt(20, 0, 0)));
+    }
+    for phrase in [&["next", "week"][..], &["semana", "que", "vem"], &["proxima", "semana"]] {
+        if words_match(words, phrase) {
+            return Some((phrase.len(), next_weekday(today, Weekday::Mon), None));
+        }
+    }
+
+    if let [preposition, count, unit, ..] = words {
+        if matches!(preposition.as_str(), "in" | "em") {
+            let count = match count.as_str() {
+                "a" | "one" | "um" | "uma" => Some(1),
+                count => count.parse::<i64>().ok(),
+            };
+            let unit = match unit.as_str() {
+                "day" | "days" | "dia" | "dias" => Some(1),
+                "week" | "weeks" | "semana" | "semanas" => Some(7),
+                _ => None,
+            };
+            if let (Some(count), Some(unit)) = (count, unit) {
+                return Some((3, day(count * unit), None));
+            }
+        }
+    }
+
+    let (skip, name) = match words {
+        [next, name, ..] if matches!(next.as_str(), "next" | "proxima" | "proximo") => (1, name),
+        [name, ..] => (0, name),
+        [] => return None,
+    };
+    let introduced = skip == 1 || previous.is_some_and(|word| WEEKDAY_CONNECTORS.contains(&word));
+    if let Some(weekday) = (introduced || name.ends_with("-feira")).then(|| parse_weekday(name)).flatten() {
+        return Some((skip + 1, next_weekday(today, weekday), None));
+    }
+
+    match_absolute_date(words, today).map(|(len, date)| (len, date, None))
+}
+
+fn match_absolute_date(words: &[String], today: NaiveDate) -> Option<(usize, NaiveDate)> {
+    let first = words.first()?;
+    if let Ok(date) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+        return Some((1, date));
+    }
+
+    let parts: Vec<&str> = first.split('/').collect();
+    if let [day, month, rest @ ..] = parts.as_slice() {
+        let (day, month) = (day.parse().ok()?, month.parse().ok()?);
+        let date = match rest {
+            [] => upcoming_date(today, month, day),
+            [year] => NaiveDate::from_ymd_opt(parse_year(year)?, month, day),
+            _ => None,
+        };
+        return date.map(|date| (1, date));
+    }
+
+    // "10 de março [de 2025]" / "10 march [2025]"
+    if let Some(day) = parse_day(first) {
+        let of = usize::from(words.get(1).is_some_and(|word| word == "de" || word == "of"));
+        if let Some(month) = words.get(1 + of).and_then(|word| parse_month(word)) {
+            let len = 2 + of;
+            let year_of = usize::from(words.get(len).is_some_and(|word| word == "de"));
+            if let Some(year) = words.get(len + year_of).and_then(|word| parse_year(word)) {
+                return NaiveDate::from_ymd_opt(year, month, day).map(|date| (len + year_of + 1, date));
+            }
+            return upcoming_date(today, month, day).map(|date| (len, date));
+        }
+    }
+
+    // "march 10 [2025]"
+    let month = parse_month(first)?;
+    let day = words.get(1).and_then(|word| parse_day(word))?;
+    if let Some(year) = words.get(2).and_then(|word| parse_year(word)) {
+        return NaiveDate::from_ymd_opt(year, month, day).map(|date| (3, date));
+    }
+    upcoming_date(today, month, day).map(|date| (2, date))
+}
+
+/// Returns the number of words consumed and the time.
+fn match_time(words: &[String]) -> Option<(usize, NaiveTime)> {
+    let first = words.first()?.as_str();
+    match first {
+        "noon" | "meio-dia" => return Some((1, NaiveTime::from_hms_opt(12, 0, 0)?)),
+        "midnight" | "meia-noite" => return Some((1, NaiveTime::from_hms_opt(0, 0, 0)?)),
+        _ => {}
+    }
+    if words_match(words, &["meio", "dia"]) {
+        return Some((2, NaiveTime::from_hms_opt(12, 0, 0)?));
+    }
+
+    // "6pm", "6:30pm", "6 pm"
+    let (clock, meridiem, len) = if let Some(clock) = first.strip_suffix("am") {
+        (clock, Some(0), 1)
+    } else if let Some(clock) = first.strip_suffix("pm") {
+        (clock, Some(12), 1)
+    } else {
+        match words.get(1).map(String::as_str) {
+            Some("am") => (first, Some(0), 2),
+            Some("pm") => (first, Some(12), 2),
+            _ => (first, None, 1),
+        }
+    };
+    if let Some(offset) = meridiem {
+        let (hour, minute) = split_clock(clock, ':').or_else(|| Some((clock.parse().ok()?, 0)))?;
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+        return NaiveTime::from_hms_opt(hour % 12 + offset, minute, 0).map(|time| (len, time));
+    }
+
+    // "18h", "18h30", "18:30"
+    let (hour, minute) = match first.split_once('h') {
+        Some((hour, "" | "s")) => (hour.parse().ok()?, 0),
+        Some(_) => split_clock(first, 'h')?,
+        None => split_clock(first, ':')?,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).map(|time| (1, time))
+}
+
+fn split_clock(clock: &str, separator: char) -> Option<(u32, u32)> {
+    let (hour, minute) = clock.split_once(separator)?;
+    if hour.is_empty() || hour.len() > 2 || minute.len() != 2 {
+        return None;
+    }
+    Some((hour.parse().ok()?, minute.parse().ok()?))
+}
+
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == 'º');
+    let suffix = &word[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th" | "º") {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn parse_year(word: &str) -> Option<i32> {
+    match word.len() {
+        2 => word.parse::<i32>().ok().map(|year| 2000 + year),
+        4 => word.parse().ok(),
+        _ => None,
+    }
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    let month = match word {
+        "january" | "jan" | "janeiro" => 1,
+        "february" | "feb" | "fevereiro" | "fev" => 2,
+        "march" | "mar" | "marco" => 3,
+        "april" | "apr" | "abril" | "abr" => 4,
+        "may" | "maio" | "mai" => 5,
+        "june" | "jun" | "junho" => 6,
+        "july" | "jul" | "julho" => 7,
+        "august" | "aug" | "agosto" | "ago" => 8,
+        "september" | "sep" | "sept" | "setembro" | "set" => 9,
+        "october" | "oct" | "outubro" | "out" => 10,
+        "november" | "nov" | "novembro" => 11,
+        "december" | "dec" | "dezembro" | "dez" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word.strip_suffix("-feira").unwrap_or(word) {
+        "monday" | "mon" | "segunda" => Weekday::Mon,
+        "tuesday" | "tue" | "terca" => Weekday::Tue,
+        "wednesday" | "wed" | "quarta" => Weekday::Wed,
+        "thursday" | "thu" | "quinta" => Weekday::Thu,
+        "friday" | "fri" | "sexta" => Weekday::Fri,
+        "saturday" | "sat" | "sabado" => Weekday::Sat,
+        "sunday" | "sun" | "domingo" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// The next `weekday` strictly after `today`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { ahead as i64 })
+}
+
+/// `day`/`month` this year, or next year if it has already passed.
+fn upcoming_date(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date < today {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    } else {
+        Some(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Monday.
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2025-03-10 14:00", "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn at(s: &str) -> Option<NaiveDateTime> {
+        Some(NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap())
+    }
+
+    fn due(input: &str) -> Option<NaiveDateTime> {
+        parse_quick_add(input, now()).due_date
+    }
+
+    #[test]
+    fn test_parses_full_line() {
+        let parsed = parse_quick_add("Read chapter 4 of Physics tomorrow 18h #physics !high", now());
+
+        assert_eq!(parsed.title, "Read chapter 4 of Physics");
+        assert_eq!(parsed.due_date, at("2025-03-11 18:00"));
+        assert!(!parsed.all_day);
+        assert_eq!(parsed.priority, Some(TaskPriority::High));
+        assert_eq!(parsed.tags, ["physics"]);
+    }
+
+    #[test]
+    fn test_parses_portuguese_line() {
+        let parsed = parse_quick_add("Lista de cálculo sexta-feira às 9h30 #Cálculo #cálculo !alta", now());
+
+        assert_eq!(parsed.title, "Lista de cálculo");
+        assert_eq!(parsed.due_date, at("2025-03-14 09:30"));
+        assert_eq!(parsed.priority, Some(TaskPriority::High));
+        assert_eq!(parsed.tags, ["Cálculo"]);
+    }
+
+    #[test]
+    fn test_relative_dates() {
+        assert_eq!(due("Essay today"), at("2025-03-10 23:59"));
+        assert_eq!(due("Essay hoje"), at("2025-03-10 23:59"));
+        assert_eq!(due("Essay amanhã"), at("2025-03-11 23:59"));
+        assert_eq!(due("Essay day after tomorrow"), at("2025-03-12 23:59"));
+        assert_eq!(due("Essay depois de amanhã"), at("2025-03-12 23:59"));
+        assert_eq!(due("Essay in 3 days"), at("2025-03-13 23:59"));
+        assert_eq!(due("Essay em 2 semanas"), at("2025-03-24 23:59"));
+        assert_eq!(due("Essay next week"), at("2025-03-17 23:59"));
+        assert_eq!(due("Essay semana que vem"), at("2025-03-17 23:59"));
+        assert_eq!(due("Essay on monday"), at("2025-03-17 23:59"));
+        assert_eq!(due("Redação na segunda"), at("2025-03-17 23:59"));
+        assert_eq!(due("Essay on fri"), at("2025-03-14 23:59"));
+        assert_eq!(due("Essay next wednesday"), at("2025-03-12 23:59"));
+        assert_eq!(due("Essay tonight"), at("2025-03-10 20:00"));
+    }
+
+    #[test]
+    fn test_absolute_dates() {
+        assert_eq!(due("Exam 2025-04-02"), at("2025-04-02 23:59"));
+        assert_eq!(due("Exam 02/04"), at("2025-04-02 23:59"));
+        assert_eq!(due("Exam 02/03"), at("2026-03-02 23:59"));
+        assert_eq!(due("Exam 02/04/26"), at("2026-04-02 23:59"));
+        assert_eq!(due("Prova 2 de abril às 8h"), at("2025-04-02 08:00"));
+        assert_eq!(due("Exam april 2nd 8am"), at("2025-04-02 08:00"));
+        assert_eq!(due("Exam 2 april 2026"), at("2026-04-02 23:59"));
+    }
+
+    #[test]
+    fn test_times() {
+        assert_eq!(due("Call at 18:30"), at("2025-03-10 18:30"));
+        assert_eq!(due("Call 6pm"), at("2025-03-10 18:00"));
+        assert_eq!(due("Call 6:15 pm"), at("2025-03-10 18:15"));
+        assert_eq!(due("Call 12am"), at("2025-03-11 00:00"));
+        assert_eq!(due("Call 9h"), at("2025-03-11 09:00"));
+        assert_eq!(due("Call meio-dia amanhã"), at("2025-03-11 12:00"));
+    }
+
+    #[test]
+    fn test_unrecognized_words_stay_in_title() {
+        let parsed = parse_quick_add("Read at least 25h of Physics !soon #", now());
+
+        assert_eq!(parsed.title, "Read at least 25h of Physics !soon #");
+        assert_eq!(parsed.due_date, None);
+        assert_eq!(parsed.priority, None);
+        assert!(parsed.tags.is_empty());
+    }
+
+    #[test]
+    fn test_bare_weekday_names_stay_in_title() {
+        for title in ["Revisar segunda lei de Newton", "Study for SAT", "Ouvir a quinta sinfonia", "Essay monday"] {
+            let parsed = parse_quick_add(title, now());
+            assert_eq!(parsed.title, title);
+            assert_eq!(parsed.due_date, None);
+        }
+    }
+
+    #[test]
+    fn test_only_first_date_and_priority_are_taken() {
+        let parsed = parse_quick_add("Move exam from today to tomorrow !2 !5", now());
+
+        assert_eq!(parsed.title, "Move exam from to tomorrow !5");
+        assert_eq!(parsed.due_date, at("2025-03-10 23:59"));
+        assert!(parsed.all_day);
+        assert_eq!(parsed.priority, Some(TaskPriority::Low));
+    }
+}
@@ -0,0 +1,138 @@
+//! Fractional ranks for manual ordering. A rank is a string of base-62 digits
+//! that never ends in the zero digit; any two distinct ranks have room for a
+//! third between them, so moving an item only rewrites that item's rank.
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+fn digit_value(c: u8) -> Option<usize> {
+    DIGITS.iter().position(|&d| d == c)
+}
+
+fn is_valid_rank(rank: &str) -> bool {
+    !rank.is_empty()
+        && !rank.ends_with('0')
+        && rank.bytes().all(|c| digit_value(c).is_some())
+}
+
+/// Returns a rank strictly between `before` and `after`, where `None` means
+/// the start or end of the list respectively.
+pub fn rank_between(before: Option<&str>, after: Option<&str>) -> Result<String, String> {
+    for rank in [before, after].into_iter().flatten() {
+        if !is_valid_rank(rank) {
+            return Err(format!("Invalid rank '{}'", rank));
+        }
+    }
+    if let (Some(before), Some(after)) = (before, after) {
+        if before >= after {
+            return Err(format!("Rank '{}' is not before '{}'", before, after));
+        }
+    }
+
+    Ok(midpoint(before.unwrap_or("").as_bytes(), after.map(str::as_bytes)))
+}
+
+fn midpoint(a: &[u8], b: Option<&[u8]>) -> String {
+    if let Some(b) = b {
+        let common = b
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &digit)| a.get(i).copied().unwrap_or(DIGITS[0]) == digit)
+            .count();
+        if common > 0 {
+            let prefix = String::from_utf8_lossy(&b[..common]).into_owned();
+            let rest = if common < a.len() { &a[common..] } else { &[] };
+            return prefix + &midpoint(rest, Some(&b[common..]));
+        }
+    }
+
+    let digit_a = a.first().and_then(|&c| digit_value(c)).unwrap_or(0);
+    let digit_b = b
+        .and_then(|b| b.first())
+        .and_then(|&c| digit_value(c))
+        .unwrap_or(DIGITS.len());
+
+    if digit_b - digit_a > 1 {
+        return (DIGITS[(digit_a + digit_b) / 2] as char).to_string();
+    }
+
+    match b {
+        Some(b) if b.len() > 1 => (b[0] as char).to_string(),
+        _ => {
+            let rest = if a.len() > 1 { &a[1..] } else { &[] };
+            (DIGITS[digit_a] as char).to_string() + &midpoint(rest, None)
+        }
+    }
+}
+
+/// Ranks for `count` items appended in order to an empty list.
+pub fn initial_ranks(count: usize) -> Vec<String> {
+    let mut ranks: Vec<String> = Vec::with_capacity(count);
+    for _ in 0..count {
+        let next = rank_between(ranks.last().map(String::as_str), None)
+            .expect("generated ranks are always valid");
+        ranks.push(next);
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_in_empty_list() {
+        assert_eq!(rank_between(None, None).unwrap(), "V");
+    }
+
+    #[test]
+    fn test_rank_between_neighbours() {
+        let rank = rank_between(Some("V"), Some("W")).unwrap();
+        assert!(rank.as_str() > "V" && rank.as_str() < "W");
+        assert_eq!(rank, "VV");
+
+        let rank = rank_between(Some("a1"), Some("a2")).unwrap();
+        assert!(rank.as_str() > "a1" && rank.as_str() < "a2");
+    }
+
+    #[test]
+    fn test_rank_before_first_and_after_last() {
+        let first = rank_between(None, Some("1")).unwrap();
+        assert!(first.as_str() < "1");
+        assert!(!first.ends_with('0'));
+
+        let last = rank_between(Some("z"), None).unwrap();
+        assert!(last.as_str() > "z");
+    }
+
+    #[test]
+    fn test_repeated_inserts_stay_ordered() {
+        let mut low = rank_between(None, None).unwrap();
+        let high = rank_between(Some(&low), None).unwrap();
+        for _ in 0..100 {
+            let mid = rank_between(Some(&low), Some(&high)).unwrap();
+            assert!(mid > low && mid < high, "{} < {} < {}", low, mid, high);
+            low = mid;
+        }
+
+        let mut high = high;
+        for _ in 0..100 {
+            let mid = rank_between(None, Some(&high)).unwrap();
+            assert!(mid < high);
+            high = mid;
+        }
+    }
+
+    #[test]
+    fn test_initial_ranks_are_increasing() {
+        let ranks = initial_ranks(50);
+        assert_eq!(ranks.len(), 50);
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_invalid_ranks_are_rejected() {
+        assert!(rank_between(Some("b"), Some("a")).is_err());
+        assert!(rank_between(Some("a0"), None).is_err());
+        assert!(rank_between(Some("a-"), None).is_err());
+    }
+}
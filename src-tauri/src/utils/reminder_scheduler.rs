@@ -0,0 +1,268 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::Connection;
+use std::collections::hash_map::{Entry, HashMap};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use crate::errors::ReminderError;
+use crate::models::DueReminder;
+use crate::repository::{ReminderRepository, SettingsRepository};
+use crate::utils::db_pool::DbPool;
+
+/// Event emitted to the frontend for every reminder that fires.
+pub const REMINDER_EVENT: &str = "reminder-fired";
+pub const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Reminders later than this are reported as missed rather than on time.
+pub const CATCH_UP_GRACE: Duration = Duration::minutes(5);
+/// Above this many missed reminders, a single summary notification is shown.
+pub const MAX_MISSED_NOTIFICATIONS: usize = 3;
+
+/// Delivers fired reminders: OS notifications plus an event for the UI.
+pub trait Notifier: Send + 'static {
+    fn notify(&self, title: &str, body: &str);
+    fn emit(&self, reminder: &DueReminder);
+}
+
+/// Fires every reminder due at `now` and marks it fired, so each one is
+/// delivered once even across restarts. Returns the reminders fired.
+pub fn fire_due_reminders(
+    conn: &Connection,
+    notifier: &dyn Notifier,
+    now: NaiveDateTime,
+) -> Result<Vec<DueReminder>, ReminderError> {
+    let fired = take_due_reminders(conn, now)?;
+    deliver_reminders(notifier, &fired);
+    Ok(fired.into_iter().map(|(reminder, _)| reminder).collect())
+}
+
+/// Marks every reminder due at `now` fired and returns each one with its
+/// notification body. Delivery is left to [`deliver_reminders`], so callers
+/// can release the connection first.
+pub fn take_due_reminders(conn: &Connection, now: NaiveDateTime) -> Result<Vec<(DueReminder, String)>, ReminderError> {
+    let due = ReminderRepository::due(conn, now, CATCH_UP_GRACE)?;
+    if due.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Due dates are shown in each user's time zone.
+    let mut calendars = HashMap::new();
+    let mut bodies = Vec::with_capacity(due.len());
+    for reminder in &due {
+        bodies.push(match reminder.due_date {
+            Some(due_date) => {
+                let calendar = match calendars.entry(reminder.user_id) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(SettingsRepository::get(conn, reminder.user_id)?.calendar()),
+                };
+                format!("Due {}", calendar.to_local(due_date).format("%Y-%m-%d %H:%M"))
+            }
+            None => "Reminder".to_string(),
+        });
+    }
+
+    let ids: Vec<u32> = due.iter().map(|reminder| reminder.reminder_id).collect();
+    ReminderRepository::mark_fired(conn, &ids, now)?;
+    Ok(due.into_iter().zip(bodies).collect())
+}
+
+/// Shows and emits reminders taken by [`take_due_reminders`], folding a
+/// backlog of missed ones into a single notification.
+pub fn deliver_reminders(notifier: &dyn Notifier, fired: &[(DueReminder, String)]) {
+    let missed = fired.iter().filter(|(reminder, _)| reminder.missed).count();
+    let summarize_missed = missed > MAX_MISSED_NOTIFICATIONS;
+    if summarize_missed {
+        notifier.notify(
+            "Missed reminders",
+            &format!("{} reminders came due while the app was closed", missed),
+        );
+    }
+
+    for (reminder, body) in fired {
+        if !(summarize_missed && reminder.missed) {
+            notifier.notify(&reminder.title, body);
+        }
+        notifier.emit(reminder);
+    }
+}
+
+/// Background thread polling for due reminders on the writer connection.
+/// The first poll runs immediately, catching up on reminders missed while
+/// the app was closed. Dropping the scheduler stops the thread.
+pub struct ReminderScheduler {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReminderScheduler {
+    pub fn spawn<N: Notifier>(
+        pool: Arc<DbPool>,
+        notifier: N,
+        interval: std::time::Duration,
+    ) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let handle = std::thread::spawn(move || loop {
+            // The writer is released before notifying, so a slow notification
+            // does not hold up other writes.
+            let result = pool
+                .writer()
+                .map_err(ReminderError::from)
+                .and_then(|conn| take_due_reminders(&conn, Utc::now().naive_utc()));
+            match result {
+                Ok(fired) => deliver_reminders(&notifier, &fired),
+                Err(e) => eprintln!("Failed to fire reminders: {}", e),
+            }
+
+            match stopped.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+
+        Self { stop: Some(stop), handle: Some(handle) }
+    }
+}
+
+impl Drop for ReminderScheduler {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Shows reminders through the notification plugin and emits
+/// [`REMINDER_EVENT`] to the webview.
+pub struct TauriNotifier(pub tauri::AppHandle);
+
+impl Notifier for TauriNotifier {
+    fn notify(&self, title: &str, body: &str) {
+        use tauri_plugin_notification::NotificationExt;
+
+        if let Err(e) = self.0.notification().builder().title(title).body(body).show() {
+            eprintln!("Failed to show notification: {}", e);
+        }
+    }
+
+    fn emit(&self, reminder: &DueReminder) {
+        use tauri::Emitter;
+
+        if let Err(e) = self.0.emit(REMINDER_EVENT, reminder) {
+            eprintln!("Failed to emit {}: {}", REMINDER_EVENT, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ReminderRule, Task, UserSettings};
+    use crate::repository::TaskRepository;
+    use crate::utils::initialize_database;
+    use std::sync::Mutex;
+
+    #[derive(Default, Clone)]
+    struct RecordingNotifier {
+        notifications: Arc<Mutex<Vec<String>>>,
+        bodies: Arc<Mutex<Vec<String>>>,
+        events: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, title: &str, body: &str) {
+            self.notifications.lock().unwrap().push(title.to_string());
+            self.bodies.lock().unwrap().push(body.to_string());
+        }
+
+        fn emit(&self, reminder: &DueReminder) {
+            self.events.lock().unwrap().push(reminder.reminder_id);
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+            .unwrap();
+        conn
+    }
+
+    fn remind(conn: &Connection, title: &str, remind_at: &str) -> u32 {
+        let mut task = Task::new(title.to_string(), 1, None, None).unwrap();
+        task.due_date = Some(at("2025-03-10 18:00"));
+        TaskRepository::insert_task(conn, &mut task).unwrap();
+        ReminderRepository::add(conn, task.id.unwrap(), ReminderRule::At(at(remind_at)))
+            .unwrap()
+            .id
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fires_each_reminder_once() {
+        let conn = setup_db();
+        let id = remind(&conn, "Essay", "2025-03-10 09:00");
+        let notifier = RecordingNotifier::default();
+
+        fire_due_reminders(&conn, &notifier, at("2025-03-10 08:59")).unwrap();
+        fire_due_reminders(&conn, &notifier, at("2025-03-10 09:00")).unwrap();
+        fire_due_reminders(&conn, &notifier, at("2025-03-10 09:01")).unwrap();
+
+        assert_eq!(*notifier.notifications.lock().unwrap(), ["Essay"]);
+        assert_eq!(*notifier.events.lock().unwrap(), [id]);
+    }
+
+    #[test]
+    fn test_due_date_is_shown_in_local_time() {
+        let conn = setup_db();
+        SettingsRepository::save(&conn, &UserSettings { utc_offset_minutes: Some(-180), ..UserSettings::new(1) }).unwrap();
+        remind(&conn, "Essay", "2025-03-10 09:00");
+        let notifier = RecordingNotifier::default();
+
+        fire_due_reminders(&conn, &notifier, at("2025-03-10 09:00")).unwrap();
+
+        assert_eq!(*notifier.bodies.lock().unwrap(), ["Due 2025-03-10 15:00"]);
+    }
+
+    #[test]
+    fn test_many_missed_reminders_are_summarized() {
+        let conn = setup_db();
+        for title in ["A", "B", "C", "D"] {
+            remind(&conn, title, "2025-03-09 09:00");
+        }
+        remind(&conn, "On time", "2025-03-10 08:58");
+        let notifier = RecordingNotifier::default();
+
+        let fired = fire_due_reminders(&conn, &notifier, at("2025-03-10 09:00")).unwrap();
+
+        assert_eq!(fired.iter().filter(|reminder| reminder.missed).count(), 4);
+        assert_eq!(*notifier.notifications.lock().unwrap(), ["Missed reminders", "On time"]);
+        assert_eq!(notifier.events.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_scheduler_catches_up_on_start() {
+        let path = std::env::temp_dir()
+            .join(format!("studystudio-reminders-{}.db", std::process::id()));
+        let pool = Arc::new(DbPool::open(&path, 1).unwrap());
+        {
+            let conn = pool.writer().unwrap();
+            conn.execute("INSERT INTO users (name, status) VALUES ('Alice', 'active')", [])
+                .unwrap();
+            remind(&conn, "Essay", "2025-03-10 09:00");
+        }
+        let notifier = RecordingNotifier::default();
+
+        let scheduler = ReminderScheduler::spawn(pool, notifier.clone(), std::time::Duration::from_secs(60));
+        drop(scheduler);
+
+        assert_eq!(*notifier.notifications.lock().unwrap(), ["Essay"]);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use rusqlite::types::{FromSql, FromSqlResult, ValueRef, FromSqlError};
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SqliteDateTime(pub NaiveDateTime);
@@ -25,6 +25,42 @@ impl From<SqliteDateTime> for NaiveDateTime {
     }
 }
 
+/// A calendar date stored as `%Y-%m-%d` text.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SqliteDate(pub NaiveDate);
+
+impl FromSql for SqliteDate {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        NaiveDate::parse_from_str(value.as_str()?, "%Y-%m-%d")
+            .map(SqliteDate)
+            .map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl From<SqliteDate> for NaiveDate {
+    fn from(sqlite_date: SqliteDate) -> Self {
+        sqlite_date.0
+    }
+}
+
+/// A wall-clock time stored as `%H:%M` text.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SqliteTime(pub NaiveTime);
+
+impl FromSql for SqliteTime {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        NaiveTime::parse_from_str(value.as_str()?, "%H:%M")
+            .map(SqliteTime)
+            .map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+impl From<SqliteTime> for NaiveTime {
+    fn from(sqlite_time: SqliteTime) -> Self {
+        sqlite_time.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,6 +108,20 @@ mod tests {
         assert_eq!(converted, dt);
     }
 
+    #[test]
+    fn test_from_sql_date_and_time() {
+        let date = SqliteDate::column_result(ValueRef::Text(b"2025-03-10")).unwrap();
+        assert_eq!(date.0, NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+        assert!(SqliteDate::column_result(ValueRef::Text(b"10/03/2025")).is_err());
+
+        let time = SqliteTime::column_result(ValueRef::Text(b"08:30")).unwrap();
+        assert_eq!(time.0, NaiveTime::from_hms_opt(8, 30, 0).unwrap());
+        assert!(matches!(
+            SqliteTime::column_result(ValueRef::Integer(830)),
+            Err(FromSqlError::InvalidType)
+        ));
+    }
+
     #[test]
     fn test_sqlite_datetime_debug() {
         let dt = NaiveDateTime::parse_from_str("2023-10-15 14:30", "%Y-%m-%d %H:%M").unwrap();